@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::FloorPlanner, plonk::*};
+
+// 把电路的 region / cell 布局导出成 Graphviz 的 DOT 格式，方便教学的时候看图
+// 用法：cargo run -- --dot | dot -Tpng > layout.png
+//
+// 做法是自己实现一个只做记录的 Assignment，让floor planner把电路synthesize一遍：
+// * 每个region变成一个 subgraph cluster，label 就是 region 的名字
+// * 每个被assign的cell变成一个node，label 是 annotation 和 (column, row)
+// * 每个copy constraint变成一条虚线
+// 注意：这里不处理constants（floor planner拿到的是空的constants column），
+// 用了 assign_advice_from_constant 的电路 synthesize 会报错，这个错误直接返回给调用方
+pub fn dot_layout<F: FieldExt, C: Circuit<F>>(circuit: &C, k: u32) -> Result<String, Error> {
+    let mut cs = ConstraintSystem::default();
+    let config = C::configure(&mut cs);
+
+    let mut layout = DotLayout::default();
+    C::FloorPlanner::synthesize(&mut layout, circuit, config, vec![])?;
+
+    Ok(layout.render(k))
+}
+
+#[derive(Default)]
+struct DotLayout {
+    // (region name, cells)，cells 里面是 (node id, label)
+    regions: Vec<(String, Vec<(String, String)>)>,
+    current_region: Option<usize>,
+    // 不在任何region里面的cell（比如 lookup table）
+    other_cells: Vec<(String, String)>,
+    copies: Vec<(String, String)>,
+    max_row: usize,
+}
+
+fn column_type(column: &Column<Any>) -> &'static str {
+    match column.column_type() {
+        Any::Advice => "advice",
+        Any::Fixed => "fixed",
+        Any::Instance => "instance",
+    }
+}
+
+fn node_id(column: &Column<Any>, row: usize) -> String {
+    format!("{}_{}_{}", column_type(column), column.index(), row)
+}
+
+// 给人看的column名字，比如 advice[0]
+fn column_name(column: &Column<Any>) -> String {
+    format!("{}[{}]", column_type(column), column.index())
+}
+
+// DOT 的 label 是双引号字符串，名字里面的 \ 和 " 要转义
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl DotLayout {
+    fn record_cell(&mut self, annotation: String, column: Column<Any>, row: usize) {
+        self.max_row = self.max_row.max(row + 1);
+
+        let id = node_id(&column, row);
+        let label = format!(
+            "{}\\n({}, {})",
+            escape(&annotation),
+            column_name(&column),
+            row
+        );
+        match self.current_region {
+            Some(index) => self.regions[index].1.push((id, label)),
+            None => self.other_cells.push((id, label)),
+        }
+    }
+
+    fn render(&self, k: u32) -> String {
+        let mut dot = String::from("digraph circuit {\n");
+        dot.push_str(&format!(
+            "    label=\"rows used: {} / {}\";\n    node [shape=box];\n",
+            self.max_row,
+            1u64 << k
+        ));
+
+        // 同一个cell可能在同一个region里面被assign多次（floor planner会跑两遍），去重一下
+        for (index, (name, cells)) in self.regions.iter().enumerate() {
+            dot.push_str(&format!("    subgraph cluster_{} {{\n", index));
+            dot.push_str(&format!("        label=\"{}\";\n", escape(name)));
+            let cells: BTreeMap<_, _> = cells.iter().cloned().collect();
+            for (id, label) in cells {
+                dot.push_str(&format!("        {} [label=\"{}\"];\n", id, label));
+            }
+            dot.push_str("    }\n");
+        }
+
+        let other_cells: BTreeMap<_, _> = self.other_cells.iter().cloned().collect();
+        for (id, label) in other_cells {
+            dot.push_str(&format!("    {} [label=\"{}\"];\n", id, label));
+        }
+
+        for (left, right) in &self.copies {
+            dot.push_str(&format!(
+                "    {} -> {} [style=dashed, dir=none];\n",
+                left, right
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<F: FieldExt> Assignment<F> for DotLayout {
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.regions.push((name_fn().into(), vec![]));
+        self.current_region = Some(self.regions.len() - 1);
+    }
+
+    fn exit_region(&mut self) {
+        self.current_region = None;
+    }
+
+    fn enable_selector<A, AR>(&mut self, _: A, _: &Selector, _: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Option<F>, Error> {
+        Ok(None)
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Advice>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Result<VR, Error>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.record_cell(annotation().into(), column.into(), row);
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Fixed>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Result<VR, Error>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.record_cell(annotation().into(), column.into(), row);
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        left_column: Column<Any>,
+        left_row: usize,
+        right_column: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        self.copies.push((
+            node_id(&left_column, left_row),
+            node_id(&right_column, right_row),
+        ));
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Option<Assigned<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::MyCircuit;
+
+    #[test]
+    fn dot_layout_names_every_region() {
+        let circuit = MyCircuit::with_witness(Fp::from(1), Fp::from(1), 4);
+        let dot = dot_layout(&circuit, 5).unwrap();
+
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("label=\"first row\";"));
+        assert!(dot.contains("label=\"next row\";"));
+        // (column, row)，不是把row打两遍
+        assert!(dot.contains("a\\n(advice[0], 0)"));
+        assert!(!dot.contains("advice_0_0, 0)"));
+    }
+
+    #[test]
+    fn escape_quotes_and_backslashes() {
+        assert_eq!(escape(r#"say "hi" \ bye"#), r#"say \"hi\" \\ bye"#);
+    }
+}
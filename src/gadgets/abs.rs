@@ -0,0 +1,169 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// y = |x|，这里把 x 当成有符号数：x 在 (-2^bits, 2^bits) 里面，负数就是 p - |x|
+// witness一个sign bit s，约束 y = (1 - 2s) * x，然后把 y range check到 bits 位
+// 如果 s 选错了，y 就会变成一个很大的field element，range check就过不了
+//
+//  x | s | y | selector
+//
+#[derive(Debug, Clone)]
+pub struct AbsConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct AbsChip<F: FieldExt> {
+    config: AbsConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> AbsChip<F> {
+    pub fn construct(config: AbsConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> AbsConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("abs", |meta| {
+            let s = meta.query_selector(selector);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let sign = meta.query_advice(advice[1], Rotation::cur());
+            let y = meta.query_advice(advice[2], Rotation::cur());
+            let one = Expression::Constant(F::one());
+            let two = Expression::Constant(F::from(2));
+
+            vec![
+                s.clone() * sign.clone() * (one.clone() - sign.clone()),
+                s * ((one - two * sign) * x - y),
+            ]
+        });
+
+        AbsConfig {
+            advice,
+            selector,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn abs(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let y = layouter.assign_region(
+            || "abs",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                x.0.copy_advice(|| "x", &mut region, self.config.advice[0], 0)?;
+
+                // x < 2^bits 就当成非负数，否则就是负数
+                let sign_val = x.0.value().map(|x| {
+                    let v = to_u128(x);
+                    F::from_u128(v) != *x || (bits < 128 && v >> bits != 0)
+                });
+                region.assign_advice(
+                    || "sign",
+                    self.config.advice[1],
+                    0,
+                    || sign_val.map(F::from).ok_or(Error::Synthesis),
+                )?;
+
+                let y_val =
+                    x.0.value()
+                        .zip(sign_val)
+                        .map(|(x, sign)| if sign { -*x } else { *x });
+                region
+                    .assign_advice(
+                        || "y",
+                        self.config.advice[2],
+                        0,
+                        || y_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check |x|"), &y, bits)?;
+
+        Ok(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Abs;
+
+    impl TestGadget<Fp> for Abs {
+        type Config = AbsConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> AbsConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            AbsChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: AbsConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                AbsChip::construct(config).abs(layouter, &inputs[0], 8)?
+            ])
+        }
+    }
+
+    #[test]
+    fn abs_matches_native() {
+        for x in [0i64, 1, -1, 5, -5, 255, -255] {
+            let x_f = if x < 0 {
+                -Fp::from(x.unsigned_abs())
+            } else {
+                Fp::from(x as u64)
+            };
+            assert_eq!(run(5, Abs, &[x_f], &[Fp::from(x.unsigned_abs())]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn abs_rejects_wrong_output() {
+        assert!(run(5, Abs, &[-Fp::from(5)], &[-Fp::from(5)]).is_err());
+        assert!(run(5, Abs, &[Fp::from(5)], &[Fp::from(6)]).is_err());
+    }
+
+    #[test]
+    fn abs_rejects_out_of_range_input() {
+        assert!(run(5, Abs, &[Fp::from(256)], &[Fp::from(256)]).is_err());
+        assert!(run(5, Abs, &[-Fp::from(256)], &[Fp::from(256)]).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// c = a + b
+//
+//  a | b | c | selector
+//
+#[derive(Debug, Clone)]
+pub struct AddConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+pub struct AddChip<F: FieldExt> {
+    config: AddConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> AddChip<F> {
+    pub fn construct(config: AddConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> AddConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![s * (a + b - c)]
+        });
+
+        AddConfig { advice, selector }
+    }
+
+    pub fn add(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "add",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = a.0.value().zip(b.0.value()).map(|(a, b)| *a + *b);
+
+                region
+                    .assign_advice(
+                        || "c",
+                        self.config.advice[2],
+                        0,
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Add;
+
+    impl TestGadget<Fp> for Add {
+        type Config = AddConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> AddConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            AddChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: AddConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                AddChip::construct(config).add(layouter, &inputs[0], &inputs[1])?
+            ])
+        }
+    }
+
+    #[test]
+    fn add_matches_native() {
+        assert_eq!(
+            run(4, Add, &[Fp::from(2), Fp::from(3)], &[Fp::from(5)]),
+            Ok(())
+        );
+        // field里面会wrap
+        assert_eq!(
+            run(4, Add, &[-Fp::one(), Fp::from(3)], &[Fp::from(2)]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn add_rejects_wrong_sum() {
+        assert!(run(4, Add, &[Fp::from(2), Fp::from(3)], &[Fp::from(6)]).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// 证明 (u, v) 是一个固定的图里面的边
+// 所有的边都放进一个lookup table，然后lookup (1, u, v)
+// table第一列是一个tag：selector关掉的时候lookup的是 (0, 0, 0)，
+// 所以table里面要有一行 (0, 0, 0)，加了tag之后这一行就不会被当成边 (0, 0)
+// 注意：table里面的边是有方向的，无向图要把 (u, v) 和 (v, u) 都放进去
+//
+//  u | v | q_lookup
+//
+#[derive(Debug, Clone)]
+pub struct AdjacencyConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_lookup: Selector,
+    pub table_tag: TableColumn,
+    pub table_u: TableColumn,
+    pub table_v: TableColumn,
+}
+
+impl AdjacencyConfig {
+    pub fn load<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        edges: &[(F, F)],
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || "adjacency table",
+            |mut table| {
+                let rows = std::iter::once((F::zero(), F::zero(), F::zero()))
+                    .chain(edges.iter().map(|(u, v)| (F::one(), *u, *v)));
+                for (offset, (tag, u, v)) in rows.enumerate() {
+                    table.assign_cell(|| "tag", self.table_tag, offset, || Ok(tag))?;
+                    table.assign_cell(|| "u", self.table_u, offset, || Ok(u))?;
+                    table.assign_cell(|| "v", self.table_v, offset, || Ok(v))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+pub struct AdjacencyChip<F: FieldExt> {
+    config: AdjacencyConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> AdjacencyChip<F> {
+    pub fn construct(config: AdjacencyConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> AdjacencyConfig {
+        let q_lookup = meta.complex_selector();
+        let table_tag = meta.lookup_table_column();
+        let table_u = meta.lookup_table_column();
+        let table_v = meta.lookup_table_column();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let u = meta.query_advice(advice[0], Rotation::cur());
+            let v = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![
+                (q.clone(), table_tag),
+                (q.clone() * u, table_u),
+                (q * v, table_v),
+            ]
+        });
+
+        AdjacencyConfig {
+            advice,
+            q_lookup,
+            table_tag,
+            table_u,
+            table_v,
+        }
+    }
+
+    pub fn assert_edge(
+        &self,
+        mut layouter: impl Layouter<F>,
+        u: &ACell<F>,
+        v: &ACell<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "edge",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                u.0.copy_advice(|| "u", &mut region, self.config.advice[0], 0)?;
+                v.0.copy_advice(|| "v", &mut region, self.config.advice[1], 0)?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 有向图：0 -> 1 -> 2 -> 0，外加一条无向边 3 <-> 4
+    const EDGES: [(u64, u64); 5] = [(0, 1), (1, 2), (2, 0), (3, 4), (4, 3)];
+
+    #[derive(Clone, Default)]
+    struct Adjacency;
+
+    impl TestGadget<Fp> for Adjacency {
+        type Config = AdjacencyConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> AdjacencyConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            AdjacencyChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: AdjacencyConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let edges: Vec<_> = EDGES
+                .iter()
+                .map(|(u, v)| (Fp::from(*u), Fp::from(*v)))
+                .collect();
+            config.load(layouter.namespace(|| "table"), &edges)?;
+            AdjacencyChip::construct(config).assert_edge(layouter, &inputs[0], &inputs[1])?;
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn adjacency_accepts_edges() {
+        for (u, v) in EDGES {
+            assert_eq!(run(5, Adjacency, &[Fp::from(u), Fp::from(v)], &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn adjacency_rejects_non_edges() {
+        // (1, 0) 是反方向；(0, 0) 是table里面 tag = 0 的那一行
+        for (u, v) in [(1u64, 0u64), (0, 2), (0, 0), (5, 6)] {
+            assert!(run(5, Adjacency, &[Fp::from(u), Fp::from(v)], &[]).is_err());
+        }
+    }
+}
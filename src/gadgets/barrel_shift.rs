@@ -0,0 +1,257 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    div::{DivChip, DivConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    mux::{MuxChip, MuxConfig},
+    pow2, Boolean,
+};
+use crate::ACell;
+
+// 桶形移位器：out = (x << s) mod 2^bits，s 是一个变量cell
+// 把 s 拆成 stages 个bit（s_i），第 i 级根据 s_i 决定要不要再左移 2^i 位：
+//   shifted = (cur * 2^(2^i)) mod 2^bits
+//   cur = s_i ? shifted : cur
+// 每一级都 mod 2^bits（DivChip 的余数），所以 s >= bits 的时候结果自然就是 0
+// s = 0 的时候每一级都选 cur，out 就是 x
+// x 需要 < 2^bits，s 需要 < 2^stages（stages 是能表示 bits - 1 的最少位数），bits <= 63
+// shift_right 是一样的结构，只是每一级换成除以 2^(2^i)（DivChip 的商），out = x >> s
+#[derive(Debug, Clone)]
+pub struct BarrelShiftConfig {
+    pub decompose: DecomposeConfig,
+    pub mul_const: MulConstConfig,
+    pub div: DivConfig,
+    pub mux: MuxConfig,
+}
+
+pub struct BarrelShiftChip<F: FieldExt> {
+    config: BarrelShiftConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BarrelShiftChip<F> {
+    pub fn construct(config: BarrelShiftConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> BarrelShiftConfig {
+        BarrelShiftConfig {
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            div: DivChip::configure(meta, advice, fixed),
+            mux: MuxChip::configure(meta, advice),
+        }
+    }
+
+    // 检查 x < 2^bits，并且把 s 拆成 stages 个bit
+    fn shift_bits(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        s: &ACell<F>,
+        bits: usize,
+    ) -> Result<Vec<Boolean<F>>, Error> {
+        if bits == 0 || bits > 63 {
+            return Err(Error::Synthesis);
+        }
+        let stages = ((usize::BITS - (bits - 1).leading_zeros()) as usize).max(1);
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "x < 2^bits"), x, bits)?;
+        decompose.decompose(layouter.namespace(|| "s bits"), s, stages)
+    }
+
+    pub fn shift_left(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        s: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let s_bits = self.shift_bits(layouter.namespace(|| "decompose"), x, s, bits)?;
+
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let div = DivChip::construct(self.config.div.clone());
+        let mux = MuxChip::construct(self.config.mux.clone());
+
+        let mut cur = x.clone();
+        for (i, s_i) in s_bits.iter().enumerate() {
+            let scaled = mul_const.mul_const(
+                layouter.namespace(|| format!("stage {}: << 2^{}", i, i)),
+                &cur,
+                pow2(1 << i),
+            )?;
+            let (_, shifted) = div.div_rem(
+                layouter.namespace(|| format!("stage {}: mod 2^bits", i)),
+                &scaled,
+                1 << bits,
+            )?;
+            cur = mux.mux(
+                layouter.namespace(|| format!("stage {}", i)),
+                s_i,
+                &shifted,
+                &cur,
+            )?;
+        }
+
+        Ok(cur)
+    }
+
+    pub fn shift_right(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        s: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let s_bits = self.shift_bits(layouter.namespace(|| "decompose"), x, s, bits)?;
+
+        let div = DivChip::construct(self.config.div.clone());
+        let mux = MuxChip::construct(self.config.mux.clone());
+
+        let mut cur = x.clone();
+        for (i, s_i) in s_bits.iter().enumerate() {
+            let shifted = div.div(
+                layouter.namespace(|| format!("stage {}: >> 2^{}", i, i)),
+                &cur,
+                1 << (1 << i),
+            )?;
+            cur = mux.mux(
+                layouter.namespace(|| format!("stage {}", i)),
+                s_i,
+                &shifted,
+                &cur,
+            )?;
+        }
+
+        Ok(cur)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [x, s]
+    #[derive(Clone, Default)]
+    struct BarrelShift {
+        bits: usize,
+        right: bool,
+    }
+
+    impl TestGadget<Fp> for BarrelShift {
+        type Config = BarrelShiftConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BarrelShiftConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            BarrelShiftChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: BarrelShiftConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = BarrelShiftChip::construct(config);
+            let out = if self.right {
+                chip.shift_right(layouter, &inputs[0], &inputs[1], self.bits)?
+            } else {
+                chip.shift_left(layouter, &inputs[0], &inputs[1], self.bits)?
+            };
+            Ok(vec![out])
+        }
+    }
+
+    fn native(x: u64, s: u64, bits: usize, right: bool) -> u64 {
+        match (right, s >= 64) {
+            (_, true) => 0,
+            (true, false) => x >> s,
+            (false, false) => (x << s) & ((1 << bits) - 1),
+        }
+    }
+
+    fn inputs(x: u64, s: u64) -> Vec<Fp> {
+        vec![Fp::from(x), Fp::from(s)]
+    }
+
+    #[test]
+    fn barrel_shift_matches_native() {
+        // bits = 5 的时候 s 有 3 个bit，所以 s = 5, 6, 7 都 >= bits，结果是 0
+        for bits in [8usize, 5] {
+            let stages = 64 - (bits as u64 - 1).leading_zeros();
+            for right in [false, true] {
+                for x in [0u64, 1, 0b10110, (1 << bits) - 1] {
+                    for s in 0..(1u64 << stages) {
+                        let expected = native(x, s, bits, right);
+                        assert_eq!(
+                            run(
+                                10,
+                                BarrelShift { bits, right },
+                                &inputs(x, s),
+                                &[Fp::from(expected)]
+                            ),
+                            Ok(()),
+                            "x = {}, s = {}, bits = {}, right = {}",
+                            x,
+                            s,
+                            bits,
+                            right
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn barrel_shift_rejects_wrong_output() {
+        let g = BarrelShift {
+            bits: 8,
+            right: false,
+        };
+        // 没有 mod 2^bits
+        assert!(run(10, g.clone(), &inputs(0xff, 4), &[Fp::from(0xff0)]).is_err());
+        // s = 0 的时候就是 x
+        assert!(run(10, g, &inputs(3, 0), &[Fp::from(6)]).is_err());
+        let g = BarrelShift {
+            bits: 8,
+            right: true,
+        };
+        assert!(run(10, g, &inputs(0x80, 7), &[Fp::zero()]).is_err());
+    }
+
+    #[test]
+    fn barrel_shift_rejects_out_of_range_inputs() {
+        let g = BarrelShift {
+            bits: 8,
+            right: false,
+        };
+        // x 超过 2^bits，s 超过 2^stages
+        assert!(run(10, g.clone(), &inputs(256, 0), &[Fp::from(256)]).is_err());
+        assert!(run(10, g, &inputs(1, 8), &[Fp::zero()]).is_err());
+        let g = BarrelShift {
+            bits: 0,
+            right: false,
+        };
+        assert!(synthesis_fails(10, g, &inputs(0, 0), &[Fp::zero()]));
+    }
+}
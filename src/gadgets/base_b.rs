@@ -0,0 +1,294 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// 把 x 拆成 num_digits 个 base 进制的digit，同时也就证明了 x < base^num_digits
+// 和 DecomposeChip 一样从最高位开始累加：acc = base * acc_prev + digit，最后一行的 acc 就是 x
+// digit < base 和 ModChip 里面的做法一样：同时range check digit 和 slack = base - 1 - digit
+//
+//  digit     |  acc  | slack | base(fixed) | q_first | q_step
+//  d_{n-1}   | d_n-1 |  ...  |    base     |    1    |   0
+//  ...       |  ...  |  ...  |    base     |    0    |   1
+//  d_0       |   x   |  ...  |    base     |    0    |   1
+//
+// 注意：base^num_digits 要比field小很多，否则拆分就不唯一了
+#[derive(Debug, Clone)]
+pub struct BaseBConfig {
+    pub advice: [Column<Advice>; 3],
+    pub base: Column<Fixed>,
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct BaseBChip<F: FieldExt> {
+    config: BaseBConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BaseBChip<F> {
+    pub fn construct(config: BaseBConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        base: Column<Fixed>,
+    ) -> BaseBConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("base b first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let digit = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let slack = meta.query_advice(advice[2], Rotation::cur());
+            let base = meta.query_fixed(base, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_first.clone() * (digit.clone() + slack + one - base),
+                q_first * (acc - digit),
+            ]
+        });
+
+        // 第一行没有 acc_prev，所以和上面分成两个gate
+        meta.create_gate("base b step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let digit = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let slack = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[1], Rotation::prev());
+            let base = meta.query_fixed(base, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_step.clone() * (digit.clone() + slack + one - base.clone()),
+                q_step * (acc - (acc_prev * base + digit)),
+            ]
+        });
+
+        BaseBConfig {
+            advice,
+            base,
+            q_first,
+            q_step,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    // 返回的digits是 little-endian 的，也就是 digits[0] 是最低位
+    pub fn decompose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        base: u64,
+        num_digits: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        if base < 2 || num_digits == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        let (digits, slacks) = layouter.assign_region(
+            || "base b",
+            |mut region| {
+                let x_val = x.0.value().map(to_u128);
+                let mut acc_val = Some(F::zero());
+                let mut digits = Vec::with_capacity(num_digits);
+                let mut slacks = Vec::with_capacity(num_digits);
+
+                for row in 0..num_digits {
+                    // 第 row 行放的是第 i 位（从最高位往下）
+                    let i = (num_digits - 1 - row) as u32;
+                    let digit_val = x_val.map(|x| {
+                        (base as u128)
+                            .checked_pow(i)
+                            .map_or(0, |p| (x / p) % base as u128) as u64
+                    });
+                    acc_val = acc_val
+                        .zip(digit_val)
+                        .map(|(acc, d)| acc * F::from(base) + F::from(d));
+
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+                    region.assign_fixed(|| "base", self.config.base, row, || Ok(F::from(base)))?;
+
+                    let digit = region
+                        .assign_advice(
+                            || format!("digit {}", i),
+                            self.config.advice[0],
+                            row,
+                            || digit_val.map(F::from).ok_or(Error::Synthesis),
+                        )
+                        .map(ACell)?;
+                    let slack = region
+                        .assign_advice(
+                            || "base - 1 - digit",
+                            self.config.advice[2],
+                            row,
+                            || {
+                                digit_val
+                                    .map(|d| F::from(base - 1 - d))
+                                    .ok_or(Error::Synthesis)
+                            },
+                        )
+                        .map(ACell)?;
+
+                    if row == num_digits - 1 {
+                        x.0.copy_advice(|| "x", &mut region, self.config.advice[1], row)?;
+                    } else {
+                        region.assign_advice(
+                            || "acc",
+                            self.config.advice[1],
+                            row,
+                            || acc_val.ok_or(Error::Synthesis),
+                        )?;
+                    }
+
+                    digits.push(digit);
+                    slacks.push(slack);
+                }
+
+                digits.reverse();
+                slacks.reverse();
+                Ok((digits, slacks))
+            },
+        )?;
+
+        // digit 和 base - 1 - digit 都落在 [0, 2^digit_bits) 里面，才能保证 digit <= base - 1
+        let digit_bits = 64 - (base - 1).leading_zeros() as usize;
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        for (i, (digit, slack)) in digits.iter().zip(slacks.iter()).enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("range check digit {}", i)),
+                digit,
+                digit_bits,
+            )?;
+            decompose.decompose(
+                layouter.namespace(|| format!("range check slack {}", i)),
+                slack,
+                digit_bits,
+            )?;
+        }
+
+        Ok(digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct BaseB {
+        base: u64,
+        num_digits: usize,
+    }
+
+    impl TestGadget<Fp> for BaseB {
+        type Config = BaseBConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BaseBConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let base = meta.fixed_column();
+            BaseBChip::configure(meta, advice, base)
+        }
+
+        fn synthesize(
+            &self,
+            config: BaseBConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            BaseBChip::construct(config).decompose(layouter, &inputs[0], self.base, self.num_digits)
+        }
+    }
+
+    fn digits(mut x: u64, base: u64, num_digits: usize) -> Vec<Fp> {
+        (0..num_digits)
+            .map(|_| {
+                let d = x % base;
+                x /= base;
+                Fp::from(d)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn base_b_matches_native() {
+        for (x, base, num_digits) in [
+            (0u64, 10u64, 3usize),
+            (7, 10, 1),
+            (999, 10, 3),
+            (12345, 10, 5),
+            (0b1011, 2, 4),
+            (255, 16, 2),
+            (100, 3, 5),
+        ] {
+            let g = BaseB { base, num_digits };
+            assert_eq!(
+                run(7, g, &[Fp::from(x)], &digits(x, base, num_digits)),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn base_b_rejects_wrong_digits() {
+        let g = BaseB {
+            base: 10,
+            num_digits: 3,
+        };
+        let mut expected = digits(123, 10, 3);
+        expected.swap(0, 2);
+        assert!(run(7, g, &[Fp::from(123)], &expected).is_err());
+    }
+
+    #[test]
+    fn base_b_rejects_too_many_digits() {
+        // 1000 放不进 3 个十进制digit
+        let g = BaseB {
+            base: 10,
+            num_digits: 3,
+        };
+        assert!(run(7, g, &[Fp::from(1000)], &digits(1000, 10, 3)).is_err());
+    }
+
+    #[test]
+    fn base_b_rejects_bad_parameters() {
+        let g = BaseB {
+            base: 1,
+            num_digits: 3,
+        };
+        assert!(synthesis_fails(7, g, &[Fp::from(1)], &[]));
+        let g = BaseB {
+            base: 10,
+            num_digits: 0,
+        };
+        assert!(synthesis_fails(7, g, &[Fp::from(1)], &[]));
+    }
+}
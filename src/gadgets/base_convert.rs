@@ -0,0 +1,155 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::recompose::{RecomposeChip, RecomposeConfig};
+use crate::ACell;
+
+// 证明两组digit（base b1 和 base b2，都是 little-endian）表示的是同一个数
+// 两边分别用 RecomposeChip 还原成field element，然后约束相等
+// 两边的长度可以不一样，高位多出来的 0 不影响结果
+#[derive(Debug, Clone)]
+pub struct BaseConvertConfig {
+    pub recompose: RecomposeConfig,
+}
+
+pub struct BaseConvertChip<F: FieldExt> {
+    config: BaseConvertConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BaseConvertChip<F> {
+    pub fn construct(config: BaseConvertConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        base: Column<Fixed>,
+    ) -> BaseConvertConfig {
+        BaseConvertConfig {
+            recompose: RecomposeChip::configure(meta, advice, base),
+        }
+    }
+
+    pub fn assert_same_value(
+        &self,
+        mut layouter: impl Layouter<F>,
+        digits_b1: &[ACell<F>],
+        b1: u64,
+        digits_b2: &[ACell<F>],
+        b2: u64,
+    ) -> Result<(), Error> {
+        let recompose = RecomposeChip::construct(self.config.recompose.clone());
+        let x1 = recompose.recompose(layouter.namespace(|| "base b1"), digits_b1, b1)?;
+        let x2 = recompose.recompose(layouter.namespace(|| "base b2"), digits_b2, b2)?;
+
+        layouter.assign_region(
+            || "same value",
+            |mut region| region.constrain_equal(x1.0.cell(), x2.0.cell()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入的前 len1 个是 base b1 的digits，剩下的是 base b2 的digits
+    #[derive(Clone, Default)]
+    struct BaseConvert {
+        b1: u64,
+        len1: usize,
+        b2: u64,
+    }
+
+    impl TestGadget<Fp> for BaseConvert {
+        type Config = BaseConvertConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BaseConvertConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let base = meta.fixed_column();
+            BaseConvertChip::configure(meta, advice, base)
+        }
+
+        fn synthesize(
+            &self,
+            config: BaseConvertConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (d1, d2) = inputs.split_at(self.len1);
+            BaseConvertChip::construct(config)
+                .assert_same_value(layouter, d1, self.b1, d2, self.b2)?;
+            Ok(vec![])
+        }
+    }
+
+    // little-endian，不够 len 位的话高位补 0
+    fn digits(mut x: u64, base: u64, len: usize) -> Vec<u64> {
+        (0..len)
+            .map(|_| {
+                let d = x % base;
+                x /= base;
+                d
+            })
+            .collect()
+    }
+
+    fn inputs(d1: &[u64], d2: &[u64]) -> Vec<Fp> {
+        d1.iter().chain(d2).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn base_convert_matches_native() {
+        for x in [0u64, 1, 9, 10, 255, 1000, 4095] {
+            for (b1, len1, b2, len2) in [
+                (2, 12, 10, 4),
+                (10, 4, 16, 3),
+                (16, 3, 2, 12),
+                (10, 6, 16, 5),
+            ] {
+                let (d1, d2) = (digits(x, b1, len1), digits(x, b2, len2));
+                let gadget = BaseConvert { b1, len1, b2 };
+                assert_eq!(
+                    run(9, gadget, &inputs(&d1, &d2), &[]),
+                    Ok(()),
+                    "x = {} in base {} / {}",
+                    x,
+                    b1,
+                    b2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn base_convert_rejects_different_values() {
+        // 0b1010 = 10，十进制写的是 11
+        let gadget = BaseConvert {
+            b1: 2,
+            len1: 4,
+            b2: 10,
+        };
+        assert!(run(9, gadget, &inputs(&[0, 1, 0, 1], &[1, 1]), &[]).is_err());
+
+        // 值是对的（2 == 2），但是 2 不是一个binary digit
+        let gadget = BaseConvert {
+            b1: 2,
+            len1: 2,
+            b2: 10,
+        };
+        assert!(run(9, gadget, &inputs(&[2, 0], &[2]), &[]).is_err());
+    }
+}
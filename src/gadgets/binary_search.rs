@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    less_than::{LessThanChip, LessThanConfig},
+};
+use crate::ACell;
+
+// 证明 index 就是 target 在sorted数组里面应该插入的位置：sorted[index - 1] < target <= sorted[index]
+// 假设 sorted 已经被证明是sorted的（比如用 SortedChip），那么 lt_i = (sorted[i] < target) 一定是
+// 前面一段 1、后面一段 0，所以插入位置就是 1 的个数：index = Σ lt_i
+// index 是witness cell（不是电路形状里面的常数），只要约束它等于这个count就行
+// * target 比所有元素都小的时候 index = 0
+// * target 比所有元素都大的时候 index = sorted.len()
+#[derive(Debug, Clone)]
+pub struct BinarySearchConfig {
+    pub less_than: LessThanConfig,
+    pub add: AddConfig,
+    pub bits: usize,
+}
+
+pub struct BinarySearchChip<F: FieldExt> {
+    config: BinarySearchConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BinarySearchChip<F> {
+    pub fn construct(config: BinarySearchConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // 所有value（包括 target）都需要 < 2^bits
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> BinarySearchConfig {
+        BinarySearchConfig {
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            add: AddChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    pub fn assert_search_position(
+        &self,
+        mut layouter: impl Layouter<F>,
+        sorted: &[ACell<F>],
+        target: &ACell<F>,
+        index: &ACell<F>,
+    ) -> Result<(), Error> {
+        if sorted.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let add = AddChip::construct(self.config.add.clone());
+
+        let mut count: Option<ACell<F>> = None;
+        for (i, value) in sorted.iter().enumerate() {
+            let lt = less_than.less_than(
+                layouter.namespace(|| format!("sorted[{}] < target", i)),
+                value,
+                target,
+                self.config.bits,
+            )?;
+            count = Some(match count {
+                None => lt.0,
+                Some(acc) => add.add(layouter.namespace(|| format!("count {}", i)), &acc, &lt.0)?,
+            });
+        }
+        let count = count.unwrap();
+
+        layouter.assign_region(
+            || "index == count",
+            |mut region| region.constrain_equal(index.0.cell(), count.0.cell()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 sorted..., target, index
+    #[derive(Clone, Default)]
+    struct BinarySearch;
+
+    impl TestGadget<Fp> for BinarySearch {
+        type Config = BinarySearchConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BinarySearchConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            BinarySearchChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: BinarySearchConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (sorted, rest) = inputs.split_at(inputs.len() - 2);
+            BinarySearchChip::construct(config)
+                .assert_search_position(layouter, sorted, &rest[0], &rest[1])?;
+            Ok(vec![])
+        }
+    }
+
+    const SORTED: [u64; 5] = [3, 7, 7, 20, 200];
+
+    fn inputs(target: u64, index: u64) -> Vec<Fp> {
+        SORTED
+            .iter()
+            .chain([target, index].iter())
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    #[test]
+    fn binary_search_matches_native() {
+        // interior / 第一个位置 / 最后一个位置 / 重复元素
+        for target in [0u64, 3, 4, 7, 8, 20, 199, 200, 201, 255] {
+            let index = SORTED.partition_point(|v| *v < target) as u64;
+            assert_eq!(run(7, BinarySearch, &inputs(target, index), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn binary_search_rejects_wrong_index() {
+        // target = 8 应该在 index 3
+        for index in [0u64, 2, 4, 5] {
+            assert!(run(7, BinarySearch, &inputs(8, index), &[]).is_err());
+        }
+        // target 比所有的都大，index 不能停在最后一个元素上
+        assert!(run(7, BinarySearch, &inputs(255, 4), &[]).is_err());
+        // target == sorted[0] 的时候 index 是 0，不是 1
+        assert!(run(7, BinarySearch, &inputs(3, 1), &[]).is_err());
+    }
+
+    #[test]
+    fn binary_search_rejects_empty_array() {
+        assert!(synthesis_fails(
+            7,
+            BinarySearch,
+            &[Fp::from(1), Fp::zero()],
+            &[]
+        ));
+    }
+}
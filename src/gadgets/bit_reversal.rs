@@ -0,0 +1,171 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use crate::ACell;
+
+// 证明 output 是 input 的 bit-reversal permutation（FFT 里面常用的重排）：
+// 长度是 2^k，output[rev(i)] == input[i]，rev 是把 i 的 k 个bit倒过来
+// 和 TransposeChip 一样不需要custom gate，每一行放一对，然后copy constraint
+//
+//  input[i] | output[rev(i)]
+//
+#[derive(Debug, Clone)]
+pub struct BitReversalConfig {
+    pub advice: [Column<Advice>; 2],
+}
+
+pub struct BitReversalChip<F: FieldExt> {
+    config: BitReversalConfig,
+    _marker: PhantomData<F>,
+}
+
+// 把 i 的低 k 位倒过来
+pub fn reverse_bits(i: usize, k: usize) -> usize {
+    (0..k).fold(0, |acc, bit| (acc << 1) | ((i >> bit) & 1))
+}
+
+impl<F: FieldExt> BitReversalChip<F> {
+    pub fn construct(config: BitReversalConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> BitReversalConfig {
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        BitReversalConfig { advice }
+    }
+
+    pub fn assert_bit_reversal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        input: &[ACell<F>],
+        output: &[ACell<F>],
+        k: usize,
+    ) -> Result<(), Error> {
+        let n = 1 << k;
+        if input.len() != n || output.len() != n {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "bit reversal",
+            |mut region| {
+                for (i, x) in input.iter().enumerate() {
+                    let a =
+                        x.0.copy_advice(|| "input[i]", &mut region, self.config.advice[0], i)?;
+                    let b = output[reverse_bits(i, k)].0.copy_advice(
+                        || "output[rev(i)]",
+                        &mut region,
+                        self.config.advice[1],
+                        i,
+                    )?;
+                    region.constrain_equal(a.cell(), b.cell())?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入的前一半是 input，后一半是 output
+    #[derive(Clone, Default)]
+    struct BitReversal {
+        k: usize,
+    }
+
+    impl TestGadget<Fp> for BitReversal {
+        type Config = BitReversalConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BitReversalConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            BitReversalChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: BitReversalConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (input, output) = inputs.split_at(inputs.len() / 2);
+            BitReversalChip::construct(config)
+                .assert_bit_reversal(layouter, input, output, self.k)?;
+            Ok(vec![])
+        }
+    }
+
+    // 电路外面的 bit-reversal：output[rev(i)] = input[i]
+    fn native_bit_reversal(input: &[u64], k: usize) -> Vec<u64> {
+        let mut output = vec![0; input.len()];
+        for (i, x) in input.iter().enumerate() {
+            output[reverse_bits(i, k)] = *x;
+        }
+        output
+    }
+
+    fn inputs(input: &[u64], output: &[u64]) -> Vec<Fp> {
+        input.iter().chain(output).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn reverse_bits_matches_native() {
+        assert_eq!(reverse_bits(0b001, 3), 0b100);
+        assert_eq!(reverse_bits(0b110, 3), 0b011);
+        assert_eq!(reverse_bits(0, 0), 0);
+        for i in 0..256 {
+            assert_eq!(reverse_bits(i, 8), (i as u8).reverse_bits() as usize);
+        }
+    }
+
+    #[test]
+    fn bit_reversal_matches_native() {
+        for k in 0..=4 {
+            let input: Vec<u64> = (0..1 << k).map(|i| 100 + i).collect();
+            let output = native_bit_reversal(&input, k);
+            assert_eq!(
+                run(6, BitReversal { k }, &inputs(&input, &output), &[]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn bit_reversal_rejects_wrong_order() {
+        let input = [10u64, 11, 12, 13];
+        // 这是原样输出，不是 [10, 12, 11, 13]
+        assert!(run(6, BitReversal { k: 2 }, &inputs(&input, &input), &[]).is_err());
+        assert!(run(
+            6,
+            BitReversal { k: 2 },
+            &inputs(&input, &[10, 12, 11, 14]),
+            &[]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn bit_reversal_rejects_wrong_length() {
+        let input = [10u64, 11, 12, 13];
+        assert!(synthesis_fails(
+            6,
+            BitReversal { k: 3 },
+            &inputs(&input, &input),
+            &[]
+        ));
+    }
+}
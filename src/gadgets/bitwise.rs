@@ -0,0 +1,302 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// 按位运算 AND / XOR / OR，a, b 都需要 < 2^bits
+// 先用 DecomposeChip 把 a, b 拆成bits，再像 DecomposeChip 一样从最高位开始累加 a_i * b_i，得到 a & b
+// 另外两个直接用 a & b 算出来：
+//   a ^ b = a + b - 2 * (a & b)
+//   a | b = a + b - (a & b)
+//
+//  a_i     |  b_i    | acc (a & b 的前缀) | q_first | q_step
+//  ...
+//
+//  a       |  b      | and                | q_xor / q_or
+//  out     |         |                    |
+//
+#[derive(Debug, Clone)]
+pub struct BitwiseConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub q_xor: Selector,
+    pub q_or: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct BitwiseChip<F: FieldExt> {
+    config: BitwiseConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BitwiseChip<F> {
+    pub fn construct(config: BitwiseConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> BitwiseConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+        let q_xor = meta.selector();
+        let q_or = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("bitwise and first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![q_first * (acc - a * b)]
+        });
+
+        // 第一行没有 acc_prev，所以单独一个gate
+        meta.create_gate("bitwise and step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[2], Rotation::prev());
+            let two = Expression::Constant(F::from(2));
+
+            vec![q_step * (acc - (acc_prev * two + a * b))]
+        });
+
+        meta.create_gate("bitwise xor / or", |meta| {
+            let q_xor = meta.query_selector(q_xor);
+            let q_or = meta.query_selector(q_or);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let and = meta.query_advice(advice[2], Rotation::cur());
+            let out = meta.query_advice(advice[0], Rotation::next());
+            let two = Expression::Constant(F::from(2));
+
+            vec![
+                q_xor * (a.clone() + b.clone() - two * and.clone() - out.clone()),
+                q_or * (a + b - and - out),
+            ]
+        });
+
+        BitwiseConfig {
+            advice,
+            q_first,
+            q_step,
+            q_xor,
+            q_or,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn and(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let a_bits = decompose.decompose(layouter.namespace(|| "a bits"), a, bits)?;
+        let b_bits = decompose.decompose(layouter.namespace(|| "b bits"), b, bits)?;
+
+        layouter.assign_region(
+            || "and",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+                let mut acc = None;
+
+                // 从最高位开始
+                for (row, (a_bit, b_bit)) in
+                    a_bits.iter().rev().zip(b_bits.iter().rev()).enumerate()
+                {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    let (a_bit, b_bit) = (&a_bit.0, &b_bit.0);
+                    a_bit
+                        .0
+                        .copy_advice(|| "a_i", &mut region, self.config.advice[0], row)?;
+                    b_bit
+                        .0
+                        .copy_advice(|| "b_i", &mut region, self.config.advice[1], row)?;
+
+                    acc_val = acc_val
+                        .zip(a_bit.0.value())
+                        .zip(b_bit.0.value())
+                        .map(|((acc, a), b)| acc.double() + *a * *b);
+                    acc = Some(region.assign_advice(
+                        || "acc",
+                        self.config.advice[2],
+                        row,
+                        || acc_val.ok_or(Error::Synthesis),
+                    )?);
+                }
+
+                Ok(ACell(acc.unwrap()))
+            },
+        )
+    }
+
+    pub fn xor(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        self.combine(layouter, a, b, bits, true)
+    }
+
+    pub fn or(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        self.combine(layouter, a, b, bits, false)
+    }
+
+    // 先算 a & b，再用 q_xor 或者 q_or 把结果组合出来
+    fn combine(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+        is_xor: bool,
+    ) -> Result<ACell<F>, Error> {
+        let and = self.and(layouter.namespace(|| "a & b"), a, b, bits)?;
+
+        layouter.assign_region(
+            || if is_xor { "xor" } else { "or" },
+            |mut region| {
+                if is_xor {
+                    self.config.q_xor.enable(&mut region, 0)?;
+                } else {
+                    self.config.q_or.enable(&mut region, 0)?;
+                }
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+                and.0
+                    .copy_advice(|| "a & b", &mut region, self.config.advice[2], 0)?;
+
+                let out_val = a.0.value().zip(b.0.value()).map(|(a, b)| {
+                    let (a, b) = (to_u128(a), to_u128(b));
+                    F::from_u128(if is_xor { a ^ b } else { a | b })
+                });
+
+                region
+                    .assign_advice(
+                        || "out",
+                        self.config.advice[0],
+                        1,
+                        || out_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Copy, Default)]
+    enum Op {
+        #[default]
+        And,
+        Xor,
+        Or,
+    }
+
+    #[derive(Clone, Default)]
+    struct Bitwise {
+        op: Op,
+    }
+
+    impl TestGadget<Fp> for Bitwise {
+        type Config = BitwiseConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BitwiseConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            BitwiseChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: BitwiseConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = BitwiseChip::construct(config);
+            let (a, b) = (&inputs[0], &inputs[1]);
+            let out = match self.op {
+                Op::And => chip.and(layouter, a, b, 8)?,
+                Op::Xor => chip.xor(layouter, a, b, 8)?,
+                Op::Or => chip.or(layouter, a, b, 8)?,
+            };
+            Ok(vec![out])
+        }
+    }
+
+    #[test]
+    fn bitwise_matches_native() {
+        for (a, b) in [
+            (0u64, 0u64),
+            (0b1100, 0b1010),
+            (255, 0),
+            (255, 255),
+            (0xa5, 0x5a),
+        ] {
+            for (op, expected) in [(Op::And, a & b), (Op::Xor, a ^ b), (Op::Or, a | b)] {
+                assert_eq!(
+                    run(
+                        6,
+                        Bitwise { op },
+                        &[Fp::from(a), Fp::from(b)],
+                        &[Fp::from(expected)]
+                    ),
+                    Ok(())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bitwise_rejects_wrong_output() {
+        let inputs = [Fp::from(0b1100), Fp::from(0b1010)];
+        assert!(run(6, Bitwise { op: Op::And }, &inputs, &[Fp::from(0b0110)]).is_err());
+        assert!(run(6, Bitwise { op: Op::Xor }, &inputs, &[Fp::from(0b1110)]).is_err());
+        assert!(run(6, Bitwise { op: Op::Or }, &inputs, &[Fp::from(0b1000)]).is_err());
+    }
+
+    #[test]
+    fn bitwise_rejects_input_wider_than_bits() {
+        let inputs = [Fp::from(256), Fp::from(1)];
+        assert!(run(6, Bitwise { op: Op::Xor }, &inputs, &[Fp::from(257)]).is_err());
+    }
+}
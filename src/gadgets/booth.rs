@@ -0,0 +1,293 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// radix-4 Booth 编码：由相邻的三个乘数bit (b_{i+1}, b_i, b_{i-1}) 得到一个 {-2, -1, 0, 1, 2} 里面的digit
+//   digit = -2 * b_{i+1} + b_i + b_{i-1}
+// 这里直接用一张 8 行的lookup table来做，顺便也保证了三个输入都是bit
+// 负数在field里面就是 p - 2, p - 1；(0, 0, 0) -> 0 本来就是table里面的一行，所以不需要tag列
+//
+//  b_{i+1} | b_i | b_{i-1} | q_lookup
+//  digit   |     |         |
+//
+#[derive(Debug, Clone)]
+pub struct BoothConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_lookup: Selector,
+    pub table_bits: [TableColumn; 3],
+    pub table_digit: TableColumn,
+}
+
+pub struct BoothChip<F: FieldExt> {
+    config: BoothConfig,
+    _marker: PhantomData<F>,
+}
+
+// 按 (b_{i+1}, b_i, b_{i-1}) 查 Booth digit
+fn booth_digit(bits: [u64; 3]) -> i64 {
+    -2 * bits[0] as i64 + bits[1] as i64 + bits[2] as i64
+}
+
+fn signed<F: FieldExt>(v: i64) -> F {
+    if v < 0 {
+        -F::from(v.unsigned_abs())
+    } else {
+        F::from(v as u64)
+    }
+}
+
+impl<F: FieldExt> BoothChip<F> {
+    pub fn construct(config: BoothConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> BoothConfig {
+        let q_lookup = meta.complex_selector();
+        let table_bits = [
+            meta.lookup_table_column(),
+            meta.lookup_table_column(),
+            meta.lookup_table_column(),
+        ];
+        let table_digit = meta.lookup_table_column();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let digit = meta.query_advice(advice[0], Rotation::next());
+
+            let mut lookups: Vec<_> = advice
+                .iter()
+                .zip(table_bits)
+                .map(|(column, table)| {
+                    (
+                        q.clone() * meta.query_advice(*column, Rotation::cur()),
+                        table,
+                    )
+                })
+                .collect();
+            lookups.push((q * digit, table_digit));
+            lookups
+        });
+
+        BoothConfig {
+            advice,
+            q_lookup,
+            table_bits,
+            table_digit,
+        }
+    }
+
+    pub fn load(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "booth table",
+            |mut table| {
+                for offset in 0..8 {
+                    let bits = [(offset >> 2) & 1, (offset >> 1) & 1, offset & 1];
+                    for (bit, column) in bits.iter().zip(self.config.table_bits) {
+                        table.assign_cell(
+                            || "bit",
+                            column,
+                            offset as usize,
+                            || Ok(F::from(*bit)),
+                        )?;
+                    }
+                    table.assign_cell(
+                        || "digit",
+                        self.config.table_digit,
+                        offset as usize,
+                        || Ok(signed::<F>(booth_digit(bits))),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    // bits = [b_{i+1}, b_i, b_{i-1}]
+    pub fn booth_digit(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bits: &[ACell<F>; 3],
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "booth digit",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                let mut bit_vals = Some([0u64; 3]);
+                for (i, (bit, column)) in bits.iter().zip(self.config.advice).enumerate() {
+                    bit.0.copy_advice(|| "bit", &mut region, column, 0)?;
+                    bit_vals = bit_vals.zip(bit.0.value()).map(|(mut vals, v)| {
+                        vals[i] = (*v == F::one()) as u64;
+                        vals
+                    });
+                }
+
+                region
+                    .assign_advice(
+                        || "digit",
+                        self.config.advice[0],
+                        1,
+                        || {
+                            bit_vals
+                                .map(|bits| signed::<F>(booth_digit(bits)))
+                                .ok_or(Error::Synthesis)
+                        },
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [b_{i+1}, b_i, b_{i-1}]
+    #[derive(Clone, Default)]
+    struct Booth;
+
+    impl TestGadget<Fp> for Booth {
+        type Config = BoothConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BoothConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            BoothChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: BoothConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = BoothChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+            let bits = [inputs[0].clone(), inputs[1].clone(), inputs[2].clone()];
+            let digit = chip.booth_digit(layouter.namespace(|| "digit"), &bits)?;
+            Ok(vec![digit])
+        }
+    }
+
+    // 不走 booth_digit，直接把伪造的 digit 填进去，看lookup能不能拦住
+    #[derive(Clone, Default)]
+    struct ForgedBooth {
+        digit: i64,
+    }
+
+    impl TestGadget<Fp> for ForgedBooth {
+        type Config = BoothConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BoothConfig {
+            Booth::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: BoothConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            BoothChip::construct(config.clone()).load(layouter.namespace(|| "table"))?;
+            layouter.assign_region(
+                || "forged booth digit",
+                |mut region| {
+                    config.q_lookup.enable(&mut region, 0)?;
+                    for (input, column) in inputs.iter().zip(config.advice) {
+                        input.0.copy_advice(|| "bit", &mut region, column, 0)?;
+                    }
+                    region.assign_advice(
+                        || "digit",
+                        config.advice[0],
+                        1,
+                        || Ok(signed::<Fp>(self.digit)),
+                    )?;
+                    Ok(vec![])
+                },
+            )
+        }
+    }
+
+    // Booth 编码表，按 (b_{i+1}, b_i, b_{i-1}) 排
+    const TABLE: [([u64; 3], i64); 8] = [
+        ([0, 0, 0], 0),
+        ([0, 0, 1], 1),
+        ([0, 1, 0], 1),
+        ([0, 1, 1], 2),
+        ([1, 0, 0], -2),
+        ([1, 0, 1], -1),
+        ([1, 1, 0], -1),
+        ([1, 1, 1], 0),
+    ];
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn booth_digit_matches_recoding_table() {
+        for (bits, digit) in TABLE {
+            assert_eq!(booth_digit(bits), digit, "{:?}", bits);
+            assert_eq!(
+                run(4, Booth, &fp(&bits), &[signed::<Fp>(digit)]),
+                Ok(()),
+                "{:?}",
+                bits
+            );
+        }
+    }
+
+    #[test]
+    fn booth_negative_digits_are_field_negations() {
+        // -1 就是 p - 1，-2 就是 p - 2
+        let p_minus_1 = Fp::zero() - Fp::one();
+        let p_minus_2 = Fp::zero() - Fp::from(2);
+        assert_eq!(signed::<Fp>(-1), p_minus_1);
+        assert_eq!(signed::<Fp>(-2), p_minus_2);
+
+        assert_eq!(run(4, Booth, &fp(&[1, 0, 0]), &[p_minus_2]), Ok(()));
+        assert_eq!(run(4, Booth, &fp(&[1, 0, 1]), &[p_minus_1]), Ok(()));
+        assert_eq!(run(4, Booth, &fp(&[1, 1, 0]), &[p_minus_1]), Ok(()));
+    }
+
+    #[test]
+    fn booth_rejects_forged_digits() {
+        for (bits, digit) in TABLE {
+            // 符号反了，或者差了一
+            for forged in [-digit, digit + 1, digit - 1] {
+                if forged == digit {
+                    continue;
+                }
+                assert!(
+                    run(4, ForgedBooth { digit: forged }, &fp(&bits), &[]).is_err(),
+                    "{:?} -> {}",
+                    bits,
+                    forged
+                );
+            }
+            assert_eq!(run(4, ForgedBooth { digit }, &fp(&bits), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn booth_rejects_non_bit_inputs() {
+        // -2 * 1 + 2 + 0 = 0，但是 2 不是 bit，不在table里面
+        assert!(run(4, ForgedBooth { digit: 0 }, &fp(&[1, 2, 0]), &[]).is_err());
+    }
+}
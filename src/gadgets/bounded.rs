@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    mul::{MulChip, MulConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 判断 x 是否在闭区间 [lo, hi] 里面：lo <= x AND x <= hi
+// 和 IntervalOverlapChip 一样，两个 Boolean 直接相乘就是 AND
+// x == lo 或者 x == hi 也算在区间里面；lo > hi 的时候一定返回 0
+// x, lo, hi 都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct BoundedConfig {
+    pub less_than_or_equal: LessThanOrEqualConfig,
+    pub mul: MulConfig,
+}
+
+pub struct BoundedChip<F: FieldExt> {
+    config: BoundedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BoundedChip<F> {
+    pub fn construct(config: BoundedConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> BoundedConfig {
+        BoundedConfig {
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+            mul: MulChip::configure(meta, advice),
+        }
+    }
+
+    pub fn is_bounded(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        lo: &ACell<F>,
+        hi: &ACell<F>,
+        bits: usize,
+    ) -> Result<Boolean<F>, Error> {
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+        let above =
+            less_than_or_equal.less_than_or_equal(layouter.namespace(|| "lo <= x"), lo, x, bits)?;
+        let below =
+            less_than_or_equal.less_than_or_equal(layouter.namespace(|| "x <= hi"), x, hi, bits)?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        mul.mul(layouter.namespace(|| "and"), &above.0, &below.0)
+            .map(Boolean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [x, lo, hi]，输出 lo <= x <= hi
+    #[derive(Clone, Default)]
+    struct Bounded;
+
+    impl TestGadget<Fp> for Bounded {
+        type Config = BoundedConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BoundedConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            BoundedChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: BoundedConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let inside = BoundedChip::construct(config)
+                .is_bounded(layouter, &inputs[0], &inputs[1], &inputs[2], 8)?;
+            Ok(vec![inside.0])
+        }
+    }
+
+    fn check(x: u64, lo: u64, hi: u64, inside: bool) -> bool {
+        run(
+            7,
+            Bounded,
+            &[Fp::from(x), Fp::from(lo), Fp::from(hi)],
+            &[Fp::from(inside as u64)],
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn bounded_matches_native() {
+        for (lo, hi) in [(10, 20), (0, 255), (5, 5), (20, 10)] {
+            for x in [0, 4, 5, 6, 9, 10, 15, 20, 21, 255] {
+                let inside = lo <= x && x <= hi;
+                assert!(check(x, lo, hi, inside), "{} in [{}, {}]", x, lo, hi);
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_rejects_wrong_answer() {
+        // 边界是闭的
+        assert!(!check(10, 10, 20, false));
+        assert!(!check(20, 10, 20, false));
+        assert!(!check(21, 10, 20, true));
+    }
+}
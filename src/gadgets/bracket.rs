@@ -0,0 +1,178 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    prefix_sum::{PrefixSumChip, PrefixSumConfig},
+};
+use crate::ACell;
+
+// 括号匹配：steps 里面每一个都是 +1（左括号）或者 -1（右括号）
+// * 每一步都是 ±1：(s - 1) * (s + 1) = 0
+// * 所有前缀和都 >= 0：负数在field里面是一个很大的数，range check到 bits 位就过不了
+// * 最后的前缀和 == 0
+// 前缀和最大也就是 steps.len()，所以 bits 直接由长度决定
+//
+//  step | q_step
+//
+#[derive(Debug, Clone)]
+pub struct BracketConfig {
+    pub advice: Column<Advice>,
+    pub q_step: Selector,
+    pub prefix_sum: PrefixSumConfig,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct BracketChip<F: FieldExt> {
+    config: BracketConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BracketChip<F> {
+    pub fn construct(config: BracketConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 用来放最后的前缀和要等于的常数 0
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        fixed: Column<Fixed>,
+    ) -> BracketConfig {
+        let q_step = meta.selector();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_constant(fixed);
+
+        meta.create_gate("bracket step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let step = meta.query_advice(advice[0], Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![q_step * (step.clone() - one.clone()) * (step + one)]
+        });
+
+        BracketConfig {
+            advice: advice[0],
+            q_step,
+            prefix_sum: PrefixSumChip::configure(meta, advice),
+            decompose: DecomposeChip::configure(meta, advice),
+        }
+    }
+
+    // 空的序列也是合法的
+    pub fn assert_balanced(
+        &self,
+        mut layouter: impl Layouter<F>,
+        steps: &[ACell<F>],
+    ) -> Result<(), Error> {
+        if steps.is_empty() {
+            return Ok(());
+        }
+
+        layouter.assign_region(
+            || "steps are ±1",
+            |mut region| {
+                for (row, step) in steps.iter().enumerate() {
+                    self.config.q_step.enable(&mut region, row)?;
+                    step.0
+                        .copy_advice(|| "step", &mut region, self.config.advice, row)?;
+                }
+                Ok(())
+            },
+        )?;
+
+        let prefix_sum = PrefixSumChip::construct(self.config.prefix_sum.clone());
+        let depths = prefix_sum.prefix_sum(layouter.namespace(|| "depths"), steps)?;
+
+        let bits = (usize::BITS - steps.len().leading_zeros()) as usize;
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        for (i, depth) in depths.iter().enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("depth {} >= 0", i)),
+                depth,
+                bits,
+            )?;
+        }
+
+        let last = depths.last().unwrap();
+        layouter.assign_region(
+            || "ends at depth 0",
+            |mut region| region.constrain_constant(last.0.cell(), F::zero()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Bracket;
+
+    impl TestGadget<Fp> for Bracket {
+        type Config = BracketConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BracketConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            let fixed = meta.fixed_column();
+            BracketChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: BracketConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            BracketChip::construct(config).assert_balanced(layouter, inputs)?;
+            Ok(vec![])
+        }
+    }
+
+    // '(' 是 +1，')' 是 -1
+    fn steps(s: &str) -> Vec<Fp> {
+        s.chars()
+            .map(|c| if c == '(' { Fp::one() } else { -Fp::one() })
+            .collect()
+    }
+
+    fn balanced(s: &str) -> bool {
+        let mut depth = 0i64;
+        for c in s.chars() {
+            depth += if c == '(' { 1 } else { -1 };
+            if depth < 0 {
+                return false;
+            }
+        }
+        depth == 0
+    }
+
+    #[test]
+    fn bracket_matches_native() {
+        for s in [
+            "", "()", "(())()", "((()))", "(", ")", ")(", "())(", "(()", "())",
+        ] {
+            assert_eq!(
+                run(6, Bracket, &steps(s), &[]).is_ok(),
+                balanced(s),
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn bracket_rejects_steps_other_than_plus_minus_one() {
+        // 2 - 1 - 1 = 0，前缀和也都 >= 0，但是 2 不是合法的一步
+        let inputs = [Fp::from(2), -Fp::one(), -Fp::one()];
+        assert!(run(6, Bracket, &inputs, &[]).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::minmax::{MinMaxChip, MinMaxConfig};
+use crate::ACell;
+
+// 冒泡排序的一趟：从左往右对相邻的两个做 compare-and-swap
+// 前面换过来的大数会一直往右带，所以每一步比较的是 carry（目前为止的最大值）和 input[i]：
+//   (min, max) = min_max(carry, input[i])
+//   output[i - 1] = min，carry = max
+// 最后 carry 就是 output 的最后一个，也就是最大值
+// 已经排好序的时候每一步都不换；只有一个元素的时候原样返回
+// 所有value都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct BubblePassConfig {
+    pub min_max: MinMaxConfig,
+    pub bits: usize,
+}
+
+pub struct BubblePassChip<F: FieldExt> {
+    config: BubblePassConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BubblePassChip<F> {
+    pub fn construct(config: BubblePassConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> BubblePassConfig {
+        BubblePassConfig {
+            min_max: MinMaxChip::configure(meta, advice, fixed),
+            bits,
+        }
+    }
+
+    pub fn bubble_pass(
+        &self,
+        mut layouter: impl Layouter<F>,
+        input: &[ACell<F>],
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let (first, rest) = match input.split_first() {
+            Some(split) => split,
+            None => return Ok(vec![]),
+        };
+
+        let min_max = MinMaxChip::construct(self.config.min_max.clone());
+        let mut output = Vec::with_capacity(input.len());
+        let mut carry = first.clone();
+        for (i, value) in rest.iter().enumerate() {
+            let (min, max) = min_max.min_max(
+                layouter.namespace(|| format!("compare and swap {}", i)),
+                &carry,
+                value,
+                self.config.bits,
+            )?;
+            output.push(min);
+            carry = max;
+        }
+        output.push(carry);
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct BubblePass;
+
+    impl TestGadget<Fp> for BubblePass {
+        type Config = BubblePassConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BubblePassConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            BubblePassChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: BubblePassConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            BubblePassChip::construct(config).bubble_pass(layouter, inputs)
+        }
+    }
+
+    fn bubble_pass(values: &[u64]) -> Vec<u64> {
+        let mut values = values.to_vec();
+        for i in 1..values.len() {
+            if values[i - 1] > values[i] {
+                values.swap(i - 1, i);
+            }
+        }
+        values
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn bubble_pass_matches_native() {
+        for values in [
+            vec![],
+            vec![42u64],
+            vec![1, 2, 3, 4],
+            vec![4, 3, 2, 1],
+            vec![5, 1, 4, 2, 8],
+            vec![7, 7, 3, 7],
+            vec![255, 0, 255, 0],
+        ] {
+            assert_eq!(
+                run(8, BubblePass, &fp(&values), &fp(&bubble_pass(&values))),
+                Ok(()),
+                "{:?}",
+                values
+            );
+        }
+    }
+
+    #[test]
+    fn bubble_pass_rejects_wrong_output() {
+        let values = [5u64, 1, 4, 2, 8];
+        // 完全排好序（不止一趟），原样输出，或者最大值没有被带到最后
+        for wrong in [[1u64, 2, 4, 5, 8], [5, 1, 4, 2, 8], [1, 5, 2, 4, 8]] {
+            assert!(run(8, BubblePass, &fp(&values), &fp(&wrong)).is_err());
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::to_u128;
+use crate::ACell;
+
+// byte 之间的 XOR，用一张 (a, b, a ^ b) 的lookup table来做
+// table 一共 256 * 256 = 2^16 行，电路至少需要 k = 17
+// 顺便也保证了 a 和 b 都是byte（不在table里面的值lookup不过）
+//
+//  a | b | c | q_lookup
+//
+#[derive(Debug, Clone)]
+pub struct ByteXorConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_lookup: Selector,
+    pub table_a: TableColumn,
+    pub table_b: TableColumn,
+    pub table_c: TableColumn,
+}
+
+pub struct ByteXorChip<F: FieldExt> {
+    config: ByteXorConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ByteXorChip<F> {
+    pub fn construct(config: ByteXorConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> ByteXorConfig {
+        let q_lookup = meta.complex_selector();
+        let table_a = meta.lookup_table_column();
+        let table_b = meta.lookup_table_column();
+        let table_c = meta.lookup_table_column();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![
+                (q.clone() * a, table_a),
+                (q.clone() * b, table_b),
+                (q * c, table_c),
+            ]
+        });
+
+        ByteXorConfig {
+            advice,
+            q_lookup,
+            table_a,
+            table_b,
+            table_c,
+        }
+    }
+
+    pub fn load(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "byte xor table",
+            |mut table| {
+                let mut offset = 0;
+                for a in 0..256u64 {
+                    for b in 0..256u64 {
+                        table.assign_cell(
+                            || "a",
+                            self.config.table_a,
+                            offset,
+                            || Ok(F::from(a)),
+                        )?;
+                        table.assign_cell(
+                            || "b",
+                            self.config.table_b,
+                            offset,
+                            || Ok(F::from(b)),
+                        )?;
+                        table.assign_cell(
+                            || "a ^ b",
+                            self.config.table_c,
+                            offset,
+                            || Ok(F::from(a ^ b)),
+                        )?;
+                        offset += 1;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn xor(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "byte xor",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .map(|(a, b)| F::from_u128(to_u128(a) ^ to_u128(b)));
+
+                region
+                    .assign_advice(
+                        || "a ^ b",
+                        self.config.advice[2],
+                        0,
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+
+    // 断言 a ^ b == c，c 直接copy进来，不需要再多一个copy constraint
+    pub fn assert_xor(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        c: &ACell<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assert byte xor",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+                c.0.copy_advice(|| "c", &mut region, self.config.advice[2], 0)?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是若干对 (a, b)，输出每一对的 a ^ b
+    #[derive(Clone, Default)]
+    struct ByteXor;
+
+    impl TestGadget<Fp> for ByteXor {
+        type Config = ByteXorConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ByteXorConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            ByteXorChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: ByteXorConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = ByteXorChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+            inputs
+                .chunks(2)
+                .map(|pair| chip.xor(layouter.namespace(|| "xor"), &pair[0], &pair[1]))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn xor_matches_native() {
+        let pairs = [
+            (0u64, 0u64),
+            (0xff, 0x0f),
+            (0xa5, 0x5a),
+            (0x12, 0x34),
+            (0xff, 0xff),
+        ];
+        let inputs: Vec<_> = pairs
+            .iter()
+            .flat_map(|(a, b)| [Fp::from(*a), Fp::from(*b)])
+            .collect();
+        let expected: Vec<_> = pairs.iter().map(|(a, b)| Fp::from(a ^ b)).collect();
+        assert_eq!(run(17, ByteXor, &inputs, &expected), Ok(()));
+    }
+
+    #[test]
+    fn xor_rejects_wrong_output() {
+        assert!(run(
+            17,
+            ByteXor,
+            &[Fp::from(0xa5), Fp::from(0x5a)],
+            &[Fp::from(0xfe)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn xor_rejects_non_byte_operand() {
+        assert!(run(
+            17,
+            ByteXor,
+            &[Fp::from(0x100), Fp::from(1)],
+            &[Fp::from(0x101)]
+        )
+        .is_err());
+    }
+}
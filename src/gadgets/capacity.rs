@@ -0,0 +1,158 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    masked_sum::{MaskedSumChip, MaskedSumConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 背包容量约束：Σ mask_i * weight_i <= capacity
+// 用 MaskedSumChip 算出被选中的总重量，再用 LessThanOrEqualChip 和 capacity 比较
+// 每个weight都会被range check到 bits 位，不然prover可以用一个"负数"weight（p - w）把总重量抵消掉
+// 总重量和 capacity 也需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct CapacityConfig {
+    pub masked_sum: MaskedSumConfig,
+    pub less_than_or_equal: LessThanOrEqualConfig,
+    pub decompose: DecomposeConfig,
+    pub bits: usize,
+}
+
+pub struct CapacityChip<F: FieldExt> {
+    config: CapacityConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CapacityChip<F> {
+    pub fn construct(config: CapacityConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> CapacityConfig {
+        CapacityConfig {
+            masked_sum: MaskedSumChip::configure(meta, advice),
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            bits,
+        }
+    }
+
+    pub fn assert_within_capacity(
+        &self,
+        mut layouter: impl Layouter<F>,
+        masks: &[Boolean<F>],
+        weights: &[ACell<F>],
+        capacity: &ACell<F>,
+    ) -> Result<(), Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        for (i, weight) in weights.iter().enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("range check weight {}", i)),
+                weight,
+                self.config.bits,
+            )?;
+        }
+
+        let masked_sum = MaskedSumChip::construct(self.config.masked_sum.clone());
+        let total = masked_sum.masked_sum(layouter.namespace(|| "total weight"), masks, weights)?;
+
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+        less_than_or_equal.assert_less_than_or_equal(
+            layouter.namespace(|| "total <= capacity"),
+            &total,
+            capacity,
+            self.config.bits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 masks ++ weights ++ [capacity]
+    #[derive(Clone, Default)]
+    struct Capacity;
+
+    impl TestGadget<Fp> for Capacity {
+        type Config = CapacityConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CapacityConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            CapacityChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: CapacityConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (capacity, rest) = inputs.split_last().unwrap();
+            let (masks, weights) = rest.split_at(rest.len() / 2);
+            let masks: Vec<_> = masks.iter().cloned().map(Boolean).collect();
+            CapacityChip::construct(config)
+                .assert_within_capacity(layouter, &masks, weights, capacity)?;
+            Ok(vec![])
+        }
+    }
+
+    fn check(masks: &[u64], weights: &[Fp], capacity: u64) -> bool {
+        let inputs: Vec<Fp> = masks
+            .iter()
+            .map(|m| Fp::from(*m))
+            .chain(weights.iter().cloned())
+            .chain([Fp::from(capacity)])
+            .collect();
+        run(7, Capacity, &inputs, &[]).is_ok()
+    }
+
+    fn fp(v: &[u64]) -> Vec<Fp> {
+        v.iter().map(|x| Fp::from(*x)).collect()
+    }
+
+    #[test]
+    fn capacity_accepts_exact_fit() {
+        // 10 + 30 = 40
+        assert!(check(&[1, 0, 1], &fp(&[10, 20, 30]), 40));
+        assert!(check(&[1, 1, 1], &fp(&[10, 20, 30]), 255));
+    }
+
+    #[test]
+    fn capacity_rejects_one_over() {
+        assert!(!check(&[1, 0, 1], &fp(&[10, 20, 30]), 39));
+    }
+
+    #[test]
+    fn capacity_accepts_empty_selection() {
+        assert!(check(&[0, 0, 0], &fp(&[10, 20, 30]), 0));
+    }
+
+    #[test]
+    fn capacity_rejects_negative_weight() {
+        // 50 + (p - 45) = 5 <= 10，不做range check的话就会通过
+        let weights = [Fp::from(50), -Fp::from(45)];
+        assert!(!check(&[1, 1], &weights, 10));
+    }
+}
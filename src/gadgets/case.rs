@@ -0,0 +1,220 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::to_u128;
+use crate::ACell;
+
+// ASCII 大小写转换，用lookup来做
+// fixed table 里面放 256 行 (byte, upper(byte), lower(byte))，非字母的byte映射到自己
+// 转换的时候只需要约束 (in, out) 这一对出现在table里面
+//
+//  in | out | q_upper | q_lower
+//
+#[derive(Debug, Clone)]
+pub struct CaseConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_upper: Selector,
+    pub q_lower: Selector,
+    pub table_byte: TableColumn,
+    pub table_upper: TableColumn,
+    pub table_lower: TableColumn,
+}
+
+pub struct CaseChip<F: FieldExt> {
+    config: CaseConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CaseChip<F> {
+    pub fn construct(config: CaseConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> CaseConfig {
+        // 用在lookup里面的selector必须是complex selector
+        let q_upper = meta.complex_selector();
+        let q_lower = meta.complex_selector();
+        let table_byte = meta.lookup_table_column();
+        let table_upper = meta.lookup_table_column();
+        let table_lower = meta.lookup_table_column();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        // selector关掉的时候lookup的是 (0, 0)，table里面本来就有这一行
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_upper);
+            let input = meta.query_advice(advice[0], Rotation::cur());
+            let output = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![(q.clone() * input, table_byte), (q * output, table_upper)]
+        });
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lower);
+            let input = meta.query_advice(advice[0], Rotation::cur());
+            let output = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![(q.clone() * input, table_byte), (q * output, table_lower)]
+        });
+
+        CaseConfig {
+            advice,
+            q_upper,
+            q_lower,
+            table_byte,
+            table_upper,
+            table_lower,
+        }
+    }
+
+    // table只需要load一次
+    pub fn load(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "ascii case table",
+            |mut table| {
+                for byte in 0..=255u8 {
+                    let offset = byte as usize;
+                    table.assign_cell(
+                        || "byte",
+                        self.config.table_byte,
+                        offset,
+                        || Ok(F::from(byte as u64)),
+                    )?;
+                    table.assign_cell(
+                        || "upper",
+                        self.config.table_upper,
+                        offset,
+                        || Ok(F::from(byte.to_ascii_uppercase() as u64)),
+                    )?;
+                    table.assign_cell(
+                        || "lower",
+                        self.config.table_lower,
+                        offset,
+                        || Ok(F::from(byte.to_ascii_lowercase() as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn to_upper(&self, layouter: impl Layouter<F>, byte: &ACell<F>) -> Result<ACell<F>, Error> {
+        self.convert(layouter, byte, self.config.q_upper, u8::to_ascii_uppercase)
+    }
+
+    pub fn to_lower(&self, layouter: impl Layouter<F>, byte: &ACell<F>) -> Result<ACell<F>, Error> {
+        self.convert(layouter, byte, self.config.q_lower, u8::to_ascii_lowercase)
+    }
+
+    fn convert(
+        &self,
+        mut layouter: impl Layouter<F>,
+        byte: &ACell<F>,
+        selector: Selector,
+        f: fn(&u8) -> u8,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "convert case",
+            |mut region| {
+                selector.enable(&mut region, 0)?;
+
+                byte.0
+                    .copy_advice(|| "in", &mut region, self.config.advice[0], 0)?;
+
+                // 超出一个byte的输入在table里面找不到，lookup会失败，这里随便给一个值就行
+                let out_val = byte.0.value().map(|b| {
+                    let b = to_u128(b);
+                    if b < 256 {
+                        F::from(f(&(b as u8)) as u64)
+                    } else {
+                        F::zero()
+                    }
+                });
+
+                region
+                    .assign_advice(
+                        || "out",
+                        self.config.advice[1],
+                        0,
+                        || out_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Case;
+
+    impl TestGadget<Fp> for Case {
+        type Config = CaseConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CaseConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            CaseChip::configure(meta, advice)
+        }
+
+        // 每个输入都输出 (upper, lower)
+        fn synthesize(
+            &self,
+            config: CaseConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = CaseChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+
+            let mut out = vec![];
+            for byte in inputs {
+                out.push(chip.to_upper(layouter.namespace(|| "upper"), byte)?);
+                out.push(chip.to_lower(layouter.namespace(|| "lower"), byte)?);
+            }
+            Ok(out)
+        }
+    }
+
+    fn expected(bytes: &[u8]) -> Vec<Fp> {
+        bytes
+            .iter()
+            .flat_map(|b| {
+                [
+                    Fp::from(b.to_ascii_uppercase() as u64),
+                    Fp::from(b.to_ascii_lowercase() as u64),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn case_matches_native() {
+        let bytes = b"aZ09@[`{ \xff";
+        let inputs: Vec<_> = bytes.iter().map(|b| Fp::from(*b as u64)).collect();
+        assert_eq!(run(9, Case, &inputs, &expected(bytes)), Ok(()));
+    }
+
+    #[test]
+    fn case_rejects_wrong_conversion() {
+        // 'a' 的大写不是 'b'
+        let mut out = expected(b"a");
+        out[0] = Fp::from(b'b' as u64);
+        assert!(run(9, Case, &[Fp::from(b'a' as u64)], &out).is_err());
+    }
+
+    #[test]
+    fn case_rejects_non_byte_input() {
+        assert!(run(9, Case, &[Fp::from(300)], &[Fp::zero(), Fp::zero()]).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    div::{DivChip, DivConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+};
+use crate::ACell;
+
+// Catalan 数的递推：C_{n+1} = C_n * 2(2n + 1) / (n + 2)
+// n 是常数，所以 2(2n + 1) 直接用 MulConstChip 乘上去，再用 DivChip 除以 n + 2
+// 这个除法一定是整除的，所以约束余数 == 0（C_n 给错了的话一般就除不尽）
+// n = 0 的时候 C_1 = C_0 * 2 / 2 = 1
+// 注意：C_n * 2(2n + 1) / (n + 2) 需要在 DivChip 的 QUOTIENT_BITS 以内
+#[derive(Debug, Clone)]
+pub struct CatalanConfig {
+    pub mul_const: MulConstConfig,
+    pub div: DivConfig,
+}
+
+pub struct CatalanChip<F: FieldExt> {
+    config: CatalanConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CatalanChip<F> {
+    pub fn construct(config: CatalanConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 同时用来放常数和 DivChip 的除数，还有余数要等于的 0
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> CatalanConfig {
+        meta.enable_constant(fixed);
+
+        CatalanConfig {
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            div: DivChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn catalan_step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        c_n: &ACell<F>,
+        n: u64,
+    ) -> Result<ACell<F>, Error> {
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let numerator = mul_const.mul_const(
+            layouter.namespace(|| "C_n * 2(2n + 1)"),
+            c_n,
+            F::from(2 * (2 * n + 1)),
+        )?;
+
+        let div = DivChip::construct(self.config.div.clone());
+        let (q, r) = div.div_rem(layouter.namespace(|| "/ (n + 2)"), &numerator, n + 2)?;
+
+        layouter.assign_region(
+            || "exact division",
+            |mut region| region.constrain_constant(r.0.cell(), F::zero()),
+        )?;
+
+        Ok(q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [C_start]，从 n = start 开始连续走 steps 步，输出 C_{start+1}, ..., C_{start+steps}
+    #[derive(Clone, Default)]
+    struct Catalan {
+        start: u64,
+        steps: u64,
+    }
+
+    impl TestGadget<Fp> for Catalan {
+        type Config = CatalanConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CatalanConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            CatalanChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: CatalanConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = CatalanChip::construct(config);
+            let mut c = inputs[0].clone();
+            let mut outputs = vec![];
+            for n in self.start..self.start + self.steps {
+                c = chip.catalan_step(layouter.namespace(|| format!("C_{}", n + 1)), &c, n)?;
+                outputs.push(c.clone());
+            }
+            Ok(outputs)
+        }
+    }
+
+    // C_n = binom(2n, n) / (n + 1)
+    fn native_catalan(n: u64) -> u64 {
+        let binom = (0..n).fold(1u64, |acc, i| acc * (2 * n - i) / (i + 1));
+        binom / (n + 1)
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn catalan_matches_native() {
+        let expected: Vec<_> = (1..=12).map(native_catalan).collect();
+        assert_eq!(&expected[..5], &[1, 2, 5, 14, 42]);
+        assert_eq!(
+            run(
+                10,
+                Catalan {
+                    start: 0,
+                    steps: 12
+                },
+                &fp(&[1]),
+                &fp(&expected)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn catalan_first_step_gives_one() {
+        assert_eq!(
+            run(10, Catalan { start: 0, steps: 1 }, &fp(&[1]), &fp(&[1])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn catalan_rejects_wrong_input() {
+        // C_3 = 5；给 6 的话 6 * 14 / 5 除不尽
+        assert_eq!(
+            run(10, Catalan { start: 3, steps: 1 }, &fp(&[5]), &fp(&[14])),
+            Ok(())
+        );
+        assert!(run(10, Catalan { start: 3, steps: 1 }, &fp(&[6]), &fp(&[16])).is_err());
+        assert!(run(10, Catalan { start: 3, steps: 1 }, &fp(&[5]), &fp(&[15])).is_err());
+    }
+}
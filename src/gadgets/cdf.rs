@@ -0,0 +1,238 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// 从预先算好的表里面读 CDF：y == cdf(x)
+// 所有 (x, cdf(x)) 都放进lookup table，然后lookup (1, x, y)
+// 和 AdjacencyChip 一样多一个tag列，selector关掉的时候查的 (0, 0, 0) 不会被当成 cdf(0) = 0
+// x 不在table里面的时候witness生成直接返回 Error::Synthesis（硬填一个 y 也lookup不过）
+//
+//  x | y | q_lookup
+//
+#[derive(Debug, Clone)]
+pub struct CdfConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_lookup: Selector,
+    pub table_tag: TableColumn,
+    pub table_x: TableColumn,
+    pub table_cdf: TableColumn,
+}
+
+impl CdfConfig {
+    pub fn load<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        pairs: &[(F, F)],
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || "cdf table",
+            |mut table| {
+                let rows = std::iter::once((F::zero(), F::zero(), F::zero()))
+                    .chain(pairs.iter().map(|(x, y)| (F::one(), *x, *y)));
+                for (offset, (tag, x, y)) in rows.enumerate() {
+                    table.assign_cell(|| "tag", self.table_tag, offset, || Ok(tag))?;
+                    table.assign_cell(|| "x", self.table_x, offset, || Ok(x))?;
+                    table.assign_cell(|| "cdf(x)", self.table_cdf, offset, || Ok(y))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+// table的内容witness生成的时候也要用到，所以chip里面也存一份
+pub struct CdfChip<F: FieldExt> {
+    config: CdfConfig,
+    pairs: Vec<(F, F)>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CdfChip<F> {
+    pub fn construct(config: CdfConfig, pairs: &[(F, F)]) -> Self {
+        Self {
+            config,
+            pairs: pairs.to_vec(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> CdfConfig {
+        let q_lookup = meta.complex_selector();
+        let table_tag = meta.lookup_table_column();
+        let table_x = meta.lookup_table_column();
+        let table_cdf = meta.lookup_table_column();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let y = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![
+                (q.clone(), table_tag),
+                (q.clone() * x, table_x),
+                (q * y, table_cdf),
+            ]
+        });
+
+        CdfConfig {
+            advice,
+            q_lookup,
+            table_tag,
+            table_x,
+            table_cdf,
+        }
+    }
+
+    pub fn cdf(&self, mut layouter: impl Layouter<F>, x: &ACell<F>) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "cdf",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                x.0.copy_advice(|| "x", &mut region, self.config.advice[0], 0)?;
+
+                let y_val = x.0.value().map(|x| {
+                    self.pairs
+                        .iter()
+                        .find(|(table_x, _)| table_x == x)
+                        .map(|(_, y)| *y)
+                });
+
+                region
+                    .assign_advice(
+                        || "cdf(x)",
+                        self.config.advice[1],
+                        0,
+                        || y_val.flatten().ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 两个骰子点数之和的分布，CDF 用 "出现次数的前缀和" 表示（分母 36 省掉）
+    fn native_cdf() -> Vec<(u64, u64)> {
+        let mut counts = [0u64; 13];
+        for a in 1..=6 {
+            for b in 1..=6 {
+                counts[a + b] += 1;
+            }
+        }
+        let mut acc = 0;
+        (2..=12)
+            .map(|x| {
+                acc += counts[x];
+                (x as u64, acc)
+            })
+            .collect()
+    }
+
+    fn pairs() -> Vec<(Fp, Fp)> {
+        native_cdf()
+            .into_iter()
+            .map(|(x, y)| (Fp::from(x), Fp::from(y)))
+            .collect()
+    }
+
+    // 输入是 xs，输出是每个 x 的 cdf(x)
+    #[derive(Clone, Default)]
+    struct Cdf;
+
+    impl TestGadget<Fp> for Cdf {
+        type Config = CdfConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CdfConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            CdfChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: CdfConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let pairs = pairs();
+            config.load(layouter.namespace(|| "table"), &pairs)?;
+            let chip = CdfChip::construct(config, &pairs);
+            inputs
+                .iter()
+                .map(|x| chip.cdf(layouter.namespace(|| "cdf"), x))
+                .collect()
+        }
+    }
+
+    // 绕过witness生成，直接给 x = 7 填一个错的 cdf
+    #[derive(Clone, Default)]
+    struct ForgedCdf;
+
+    impl TestGadget<Fp> for ForgedCdf {
+        type Config = CdfConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CdfConfig {
+            Cdf::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: CdfConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            config.load(layouter.namespace(|| "table"), &pairs())?;
+            let y = layouter.assign_region(
+                || "forged cdf",
+                |mut region| {
+                    config.q_lookup.enable(&mut region, 0)?;
+                    inputs[0]
+                        .0
+                        .copy_advice(|| "x", &mut region, config.advice[0], 0)?;
+                    region
+                        .assign_advice(|| "cdf(x)", config.advice[1], 0, || Ok(Fp::from(20)))
+                        .map(ACell)
+                },
+            )?;
+            Ok(vec![y])
+        }
+    }
+
+    #[test]
+    fn cdf_matches_native() {
+        let table = native_cdf();
+        assert_eq!(table.last(), Some(&(12, 36)));
+
+        let xs = [2u64, 7, 12, 6, 8];
+        let inputs: Vec<_> = xs.iter().map(|x| Fp::from(*x)).collect();
+        let expected: Vec<_> = xs
+            .iter()
+            .map(|x| Fp::from(table.iter().find(|(tx, _)| tx == x).unwrap().1))
+            .collect();
+        assert_eq!(run(5, Cdf, &inputs, &expected), Ok(()));
+    }
+
+    #[test]
+    fn cdf_rejects_out_of_table_input() {
+        assert!(synthesis_fails(5, Cdf, &[Fp::from(13)], &[Fp::zero()]));
+        assert!(synthesis_fails(5, Cdf, &[Fp::zero()], &[Fp::zero()]));
+    }
+
+    #[test]
+    fn cdf_rejects_forged_value() {
+        // cdf(7) = 21
+        assert_eq!(run(5, Cdf, &[Fp::from(7)], &[Fp::from(21)]), Ok(()));
+        assert!(run(5, ForgedCdf, &[Fp::from(7)], &[Fp::from(20)]).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    dot_product::{DotProductChip, DotProductConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+};
+use crate::ACell;
+
+// 找零：Σ count_i * denomination_i == target
+// 用 DotProductChip 算总额，再用 IsEqualChip 和 target 比较
+// 注意 count 是不是非负的小整数这里不管，需要的话调用方自己range check
+#[derive(Debug, Clone)]
+pub struct ChangeMakingConfig {
+    pub dot_product: DotProductConfig,
+    pub is_equal: IsEqualConfig,
+}
+
+pub struct ChangeMakingChip<F: FieldExt> {
+    config: ChangeMakingConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ChangeMakingChip<F> {
+    pub fn construct(config: ChangeMakingConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> ChangeMakingConfig {
+        ChangeMakingConfig {
+            dot_product: DotProductChip::configure(meta, advice),
+            is_equal: IsEqualChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_change(
+        &self,
+        mut layouter: impl Layouter<F>,
+        counts: &[ACell<F>],
+        denominations: &[ACell<F>],
+        target: &ACell<F>,
+    ) -> Result<(), Error> {
+        let dot_product = DotProductChip::construct(self.config.dot_product.clone());
+        let total = dot_product.dot_product(
+            layouter.namespace(|| "Σ count * denomination"),
+            counts,
+            denominations,
+        )?;
+
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        is_equal.assert_equal(layouter.namespace(|| "total == target"), &total, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 counts...，denominations...，最后一个是 target
+    #[derive(Clone, Default)]
+    struct Change;
+
+    impl TestGadget<Fp> for Change {
+        type Config = ChangeMakingConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ChangeMakingConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            ChangeMakingChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: ChangeMakingConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (target, rest) = inputs.split_last().unwrap();
+            let (counts, denominations) = rest.split_at(rest.len() / 2);
+            ChangeMakingChip::construct(config).assert_change(
+                layouter,
+                counts,
+                denominations,
+                target,
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    const COINS: [u64; 4] = [1, 5, 10, 25];
+
+    fn inputs(counts: &[u64], target: u64) -> Vec<Fp> {
+        counts
+            .iter()
+            .chain(&COINS)
+            .chain([&target])
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    #[test]
+    fn change_matches_native() {
+        for counts in [[3u64, 1, 2, 1], [0, 0, 0, 4], [0, 0, 0, 0], [99, 0, 0, 0]] {
+            let target = counts.iter().zip(&COINS).map(|(c, d)| c * d).sum();
+            assert_eq!(run(5, Change, &inputs(&counts, target), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn change_rejects_wrong_total() {
+        // 少给了一分钱
+        assert!(run(5, Change, &inputs(&[2, 1, 2, 1], 53), &[]).is_err());
+        // target = 0 只有全是 0 的 counts 才行
+        assert!(run(5, Change, &inputs(&[1, 0, 0, 0], 0), &[]).is_err());
+        assert!(run(5, Change, &inputs(&[0, 0, 0, 0], 1), &[]).is_err());
+    }
+}
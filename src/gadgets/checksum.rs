@@ -0,0 +1,222 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::modulo::{ModChip, ModConfig};
+use crate::ACell;
+
+// 一个简单的多项式checksum（Horner）：
+//   acc_0 = 0
+//   acc_{i+1} = (acc_i * base + byte_i) mod modulus
+// 每一步都用 ModChip 做一次reduction，所以 acc 一直都 < modulus
+//
+//  acc | byte | sum | base(fixed) | selector
+//
+// 注意：这里不会去range check每一个byte，调用方需要自己保证它们是byte
+#[derive(Debug, Clone)]
+pub struct ChecksumConfig {
+    pub advice: [Column<Advice>; 3],
+    pub base: Column<Fixed>,
+    pub selector: Selector,
+    pub modulo: ModConfig,
+}
+
+pub struct ChecksumChip<F: FieldExt> {
+    config: ChecksumConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ChecksumChip<F> {
+    pub fn construct(config: ChecksumConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 同时用来放 base、ModChip 的 modulus，还有空输入时的常数 0
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ChecksumConfig {
+        let selector = meta.selector();
+        meta.enable_constant(fixed);
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("checksum step", |meta| {
+            let s = meta.query_selector(selector);
+            let acc = meta.query_advice(advice[0], Rotation::cur());
+            let byte = meta.query_advice(advice[1], Rotation::cur());
+            let sum = meta.query_advice(advice[2], Rotation::cur());
+            let base = meta.query_fixed(fixed, Rotation::cur());
+
+            vec![s * (acc * base + byte - sum)]
+        });
+
+        ChecksumConfig {
+            advice,
+            base: fixed,
+            selector,
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    // 空的输入 checksum 就是 acc_0 = 0
+    pub fn checksum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[ACell<F>],
+        base: u64,
+        modulus: u64,
+    ) -> Result<ACell<F>, Error> {
+        let modulo = ModChip::construct(self.config.modulo.clone());
+
+        let (first, rest) = match bytes.split_first() {
+            Some(split) => split,
+            None => {
+                return layouter.assign_region(
+                    || "empty checksum",
+                    |mut region| {
+                        region
+                            .assign_advice_from_constant(
+                                || "0",
+                                self.config.advice[0],
+                                0,
+                                F::zero(),
+                            )
+                            .map(ACell)
+                    },
+                )
+            }
+        };
+        // acc_1 = (0 * base + byte_0) mod modulus
+        let mut acc = modulo.modulo(layouter.namespace(|| "reduce byte 0"), first, modulus)?;
+
+        for (i, byte) in rest.iter().enumerate() {
+            let sum = layouter.assign_region(
+                || "checksum step",
+                |mut region| {
+                    self.config.selector.enable(&mut region, 0)?;
+
+                    acc.0
+                        .copy_advice(|| "acc", &mut region, self.config.advice[0], 0)?;
+                    byte.0
+                        .copy_advice(|| "byte", &mut region, self.config.advice[1], 0)?;
+                    region.assign_fixed(|| "base", self.config.base, 0, || Ok(F::from(base)))?;
+
+                    let sum_val = acc
+                        .0
+                        .value()
+                        .zip(byte.0.value())
+                        .map(|(acc, byte)| *acc * F::from(base) + *byte);
+
+                    region
+                        .assign_advice(
+                            || "acc * base + byte",
+                            self.config.advice[2],
+                            0,
+                            || sum_val.ok_or(Error::Synthesis),
+                        )
+                        .map(ACell)
+                },
+            )?;
+
+            acc = modulo.modulo(
+                layouter.namespace(|| format!("reduce byte {}", i + 1)),
+                &sum,
+                modulus,
+            )?;
+        }
+
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Checksum {
+        base: u64,
+        modulus: u64,
+    }
+
+    impl TestGadget<Fp> for Checksum {
+        type Config = ChecksumConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ChecksumConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ChecksumChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ChecksumConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let sum = ChecksumChip::construct(config).checksum(
+                layouter,
+                inputs,
+                self.base,
+                self.modulus,
+            )?;
+            Ok(vec![sum])
+        }
+    }
+
+    fn native(bytes: &[u8], base: u64, modulus: u64) -> u64 {
+        bytes
+            .iter()
+            .fold(0, |acc, b| (acc * base + *b as u64) % modulus)
+    }
+
+    fn fp(bytes: &[u8]) -> Vec<Fp> {
+        bytes.iter().map(|b| Fp::from(*b as u64)).collect()
+    }
+
+    #[test]
+    fn checksum_matches_native() {
+        let g = Checksum {
+            base: 256,
+            modulus: 65521,
+        };
+        for bytes in [&b"a"[..], b"hello", b"\xff\xff\xff\x00"] {
+            let expected = Fp::from(native(bytes, g.base, g.modulus));
+            assert_eq!(run(10, g.clone(), &fp(bytes), &[expected]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn checksum_of_empty_input_is_zero() {
+        let g = Checksum {
+            base: 256,
+            modulus: 65521,
+        };
+        assert_eq!(run(4, g.clone(), &[], &[Fp::zero()]), Ok(()));
+        assert!(run(4, g, &[], &[Fp::one()]).is_err());
+    }
+
+    #[test]
+    fn checksum_rejects_wrong_sum() {
+        let g = Checksum {
+            base: 256,
+            modulus: 65521,
+        };
+        let wrong = Fp::from(native(b"hello", g.base, g.modulus) + 1);
+        assert!(run(10, g, &fp(b"hello"), &[wrong]).is_err());
+    }
+}
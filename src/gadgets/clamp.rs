@@ -0,0 +1,121 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    minmax::{MinMaxChip, MinMaxConfig},
+};
+use crate::ACell;
+
+// clamp(x, max) = min(x, max)，也就是把 x 饱和到 [0, max] 里面
+// 在field里面没有负数，所以"下界 0"是靠 range 假设来保证的：
+// * x 和 max 都必须 < 2^bits（bits 在 configure 的时候给定），这个chip本身不会去range check x
+// * 如果 x 是一个"负数"（比如 p - 1），比较的结果就没有意义了，调用方需要自己先做range check
+#[derive(Debug, Clone)]
+pub struct ClampConfig {
+    pub min_max: MinMaxConfig,
+    pub constant: ConstantConfig,
+    pub bits: usize,
+}
+
+pub struct ClampChip<F: FieldExt> {
+    config: ClampConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ClampChip<F> {
+    pub fn construct(config: ClampConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 同时用来放 less_than 的 2^bits 和 max 这个常数
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> ClampConfig {
+        ClampConfig {
+            min_max: MinMaxChip::configure(meta, advice, fixed),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            bits,
+        }
+    }
+
+    pub fn clamp(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        max: F,
+    ) -> Result<ACell<F>, Error> {
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let max = constant.load_constant(layouter.namespace(|| "max"), max)?;
+
+        // x > max 的时候返回 max，否则原样返回 x
+        let min_max = MinMaxChip::construct(self.config.min_max.clone());
+        min_max.min(
+            layouter.namespace(|| "min(x, max)"),
+            x,
+            &max,
+            self.config.bits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Clamp {
+        max: u64,
+    }
+
+    impl TestGadget<Fp> for Clamp {
+        type Config = ClampConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ClampConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ClampChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: ClampConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let out =
+                ClampChip::construct(config).clamp(layouter, &inputs[0], Fp::from(self.max))?;
+            Ok(vec![out])
+        }
+    }
+
+    #[test]
+    fn clamp_matches_native() {
+        for x in [0u64, 50, 99, 100, 101, 255] {
+            let expected = Fp::from(x.min(100));
+            assert_eq!(
+                run(5, Clamp { max: 100 }, &[Fp::from(x)], &[expected]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn clamp_rejects_unsaturated_output() {
+        assert!(run(5, Clamp { max: 100 }, &[Fp::from(150)], &[Fp::from(150)]).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    div::{DivChip, DivConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    mux::{MuxChip, MuxConfig},
+    parity::{ParityChip, ParityConfig},
+};
+use crate::ACell;
+
+// Collatz 的一步：n 是偶数的时候 next = n / 2，奇数的时候 next = 3n + 1
+// 两个分支都算出来，再用 parity bit 去 mux：next = odd ? 3n + 1 : n / 2
+// （奇数的时候 n / 2 是向下取整的，反正不会被选中）
+// n 需要 < 2^bits，用于 parity 的range check
+#[derive(Debug, Clone)]
+pub struct CollatzStepConfig {
+    pub parity: ParityConfig,
+    pub div: DivConfig,
+    pub mul_const: MulConstConfig,
+    pub add: AddConfig,
+    pub constant: ConstantConfig,
+    pub mux: MuxConfig,
+    pub bits: usize,
+}
+
+pub struct CollatzStepChip<F: FieldExt> {
+    config: CollatzStepConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CollatzStepChip<F> {
+    pub fn construct(config: CollatzStepConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> CollatzStepConfig {
+        CollatzStepConfig {
+            parity: ParityChip::configure(meta, [advice[0], advice[1]]),
+            div: DivChip::configure(meta, advice, fixed),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            add: AddChip::configure(meta, advice),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            mux: MuxChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    pub fn step(&self, mut layouter: impl Layouter<F>, n: &ACell<F>) -> Result<ACell<F>, Error> {
+        let parity = ParityChip::construct(self.config.parity.clone());
+        let odd = parity.parity(layouter.namespace(|| "parity"), n, self.config.bits)?;
+
+        let div = DivChip::construct(self.config.div.clone());
+        let half = div.div(layouter.namespace(|| "n / 2"), n, 2)?;
+
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let triple = mul_const.mul_const(layouter.namespace(|| "3n"), n, F::from(3))?;
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let one = constant.load_constant(layouter.namespace(|| "one"), F::one())?;
+        let add = AddChip::construct(self.config.add.clone());
+        let triple_plus_one = add.add(layouter.namespace(|| "3n + 1"), &triple, &one)?;
+
+        let mux = MuxChip::construct(self.config.mux.clone());
+        mux.mux(layouter.namespace(|| "next"), &odd, &triple_plus_one, &half)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 从 n 开始连续走 steps 步，每一步的结果都expose出来
+    #[derive(Clone, Default)]
+    struct Collatz {
+        steps: usize,
+    }
+
+    impl TestGadget<Fp> for Collatz {
+        type Config = CollatzStepConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CollatzStepConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            CollatzStepChip::configure(meta, advice, fixed, 16)
+        }
+
+        fn synthesize(
+            &self,
+            config: CollatzStepConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = CollatzStepChip::construct(config);
+            let mut n = inputs[0].clone();
+            let mut out = Vec::with_capacity(self.steps);
+            for i in 0..self.steps {
+                n = chip.step(layouter.namespace(|| format!("step {}", i)), &n)?;
+                out.push(n.clone());
+            }
+            Ok(out)
+        }
+    }
+
+    fn collatz(n: u64) -> u64 {
+        if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        }
+    }
+
+    fn trajectory(mut n: u64, steps: usize) -> Vec<Fp> {
+        (0..steps)
+            .map(|_| {
+                n = collatz(n);
+                Fp::from(n)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collatz_matches_native() {
+        // 1 -> 4 -> 2 -> 1
+        for (n, steps) in [(1u64, 3usize), (6, 4), (27, 5), (2, 1)] {
+            assert_eq!(
+                run(10, Collatz { steps }, &[Fp::from(n)], &trajectory(n, steps)),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn collatz_rejects_wrong_step() {
+        // 奇数不能除以 2，偶数不能 3n + 1
+        assert!(run(10, Collatz { steps: 1 }, &[Fp::from(7)], &[Fp::from(3)]).is_err());
+        assert!(run(10, Collatz { steps: 1 }, &[Fp::from(8)], &[Fp::from(25)]).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::poseidon::{PoseidonChip, PoseidonConfig};
+use crate::ACell;
+
+// 承诺：commitment = hash(value, randomness)
+// 不是真正的 Pedersen（没有用椭圆曲线），但是性质类似：
+// randomness 保证 hiding，hash 的抗碰撞保证 binding
+// 打开承诺就是在电路里面重新算一遍hash，然后和 commitment 做copy constraint
+#[derive(Debug, Clone)]
+pub struct PedersenLikeConfig {
+    pub poseidon: PoseidonConfig,
+}
+
+pub struct PedersenLikeChip<F: FieldExt> {
+    config: PedersenLikeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PedersenLikeChip<F> {
+    pub fn construct(config: PedersenLikeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        round_constants: [Column<Fixed>; 3],
+    ) -> PedersenLikeConfig {
+        PedersenLikeConfig {
+            poseidon: PoseidonChip::configure(meta, advice, round_constants),
+        }
+    }
+
+    pub fn commit(
+        &self,
+        layouter: impl Layouter<F>,
+        value: &ACell<F>,
+        randomness: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let poseidon = PoseidonChip::construct(self.config.poseidon.clone());
+        poseidon.hash(layouter, value, randomness)
+    }
+
+    pub fn verify_open(
+        &self,
+        mut layouter: impl Layouter<F>,
+        commitment: &ACell<F>,
+        value: &ACell<F>,
+        randomness: &ACell<F>,
+    ) -> Result<(), Error> {
+        let recomputed = self.commit(layouter.namespace(|| "recompute"), value, randomness)?;
+
+        layouter.assign_region(
+            || "open",
+            |mut region| region.constrain_equal(recomputed.0.cell(), commitment.0.cell()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::{
+        poseidon::hash,
+        testing::{run, TestGadget},
+    };
+
+    // open = false 的时候输入是 value, randomness，输出 commitment
+    // open = true 的时候输入是 commitment, value, randomness
+    #[derive(Clone, Default)]
+    struct PedersenLike {
+        open: bool,
+    }
+
+    impl TestGadget<Fp> for PedersenLike {
+        type Config = PedersenLikeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PedersenLikeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let round_constants = [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ];
+            PedersenLikeChip::configure(meta, advice, round_constants)
+        }
+
+        fn synthesize(
+            &self,
+            config: PedersenLikeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = PedersenLikeChip::construct(config);
+            if self.open {
+                chip.verify_open(layouter, &inputs[0], &inputs[1], &inputs[2])?;
+                return Ok(vec![]);
+            }
+            Ok(vec![chip.commit(layouter, &inputs[0], &inputs[1])?])
+        }
+    }
+
+    const OPEN: PedersenLike = PedersenLike { open: true };
+
+    #[test]
+    fn commit_matches_native() {
+        let (value, randomness) = (Fp::from(42), Fp::from(0xdead_beef));
+        assert_eq!(
+            run(
+                5,
+                PedersenLike::default(),
+                &[value, randomness],
+                &[hash(value, randomness)]
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn verify_open_accepts_correct_opening() {
+        let (value, randomness) = (Fp::from(42), Fp::from(0xdead_beef));
+        let commitment = hash(value, randomness);
+        assert_eq!(run(5, OPEN, &[commitment, value, randomness], &[]), Ok(()));
+    }
+
+    #[test]
+    fn verify_open_rejects_wrong_opening() {
+        let (value, randomness) = (Fp::from(42), Fp::from(0xdead_beef));
+        let commitment = hash(value, randomness);
+        // randomness 错了
+        assert!(run(5, OPEN, &[commitment, value, randomness + Fp::one()], &[]).is_err());
+        // value 错了
+        assert!(run(5, OPEN, &[commitment, Fp::from(43), randomness], &[]).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    lookup_range::{LookupRangeCheckChip, LookupRangeCheckConfig},
+};
+use crate::ACell;
+
+// 累加器的更新：new_commit = old_commit + delta，其中 delta < 2^delta_bits
+// delta 用 LookupRangeCheckChip 做range check，所以电路里面要先 load 一次range table
+// delta = 0 和 delta = 2^delta_bits - 1 都可以，再大就lookup不过
+#[derive(Debug, Clone)]
+pub struct CommitUpdateConfig {
+    pub lookup_range: LookupRangeCheckConfig,
+    pub add: AddConfig,
+}
+
+pub struct CommitUpdateChip<F: FieldExt> {
+    config: CommitUpdateConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CommitUpdateChip<F> {
+    pub fn construct(config: CommitUpdateConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> CommitUpdateConfig {
+        CommitUpdateConfig {
+            lookup_range: LookupRangeCheckChip::configure(meta, [advice[0], advice[1]], fixed),
+            add: AddChip::configure(meta, advice),
+        }
+    }
+
+    pub fn load(&self, layouter: impl Layouter<F>) -> Result<(), Error> {
+        LookupRangeCheckChip::construct(self.config.lookup_range.clone()).load(layouter)
+    }
+
+    pub fn update(
+        &self,
+        mut layouter: impl Layouter<F>,
+        old_commit: &ACell<F>,
+        delta: &ACell<F>,
+        delta_bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let lookup_range = LookupRangeCheckChip::construct(self.config.lookup_range.clone());
+        lookup_range.range_check(layouter.namespace(|| "delta range"), delta, delta_bits)?;
+
+        let add = AddChip::construct(self.config.add.clone());
+        add.add(layouter.namespace(|| "old + delta"), old_commit, delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const DELTA_BITS: usize = 12;
+
+    // 输入是 [old_commit, delta]
+    #[derive(Clone, Default)]
+    struct CommitUpdate;
+
+    impl TestGadget<Fp> for CommitUpdate {
+        type Config = CommitUpdateConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CommitUpdateConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            CommitUpdateChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: CommitUpdateConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = CommitUpdateChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+            let new = chip.update(
+                layouter.namespace(|| "update"),
+                &inputs[0],
+                &inputs[1],
+                DELTA_BITS,
+            )?;
+            Ok(vec![new])
+        }
+    }
+
+    #[test]
+    fn commit_update_matches_native() {
+        let old = Fp::from(0xdead_beef);
+        // 0，最大的 delta，以及中间的
+        for delta in [0u64, 1, 1000, (1 << DELTA_BITS) - 1] {
+            assert_eq!(
+                run(
+                    9,
+                    CommitUpdate,
+                    &[old, Fp::from(delta)],
+                    &[old + Fp::from(delta)]
+                ),
+                Ok(()),
+                "delta = {}",
+                delta
+            );
+        }
+    }
+
+    #[test]
+    fn commit_update_rejects_wrong_commit() {
+        let old = Fp::from(100);
+        assert!(run(9, CommitUpdate, &[old, Fp::from(5)], &[Fp::from(104)]).is_err());
+    }
+
+    #[test]
+    fn commit_update_rejects_out_of_range_delta() {
+        let old = Fp::from(100);
+        let delta = Fp::from(1 << DELTA_BITS);
+        assert!(run(9, CommitUpdate, &[old, delta], &[old + delta]).is_err());
+        // 负的 delta 也是超出范围的
+        assert!(run(9, CommitUpdate, &[old, -Fp::one()], &[old - Fp::one()]).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use crate::ACell;
+
+// 把一个常数放进advice column里面
+// 用的是 assign_advice_from_constant：floor planner 会把常数放到 fixed column 里面，
+// 再用copy constraint把它和advice cell绑在一起，所以prover没办法改这个值
+#[derive(Debug, Clone)]
+pub struct ConstantConfig {
+    pub advice: Column<Advice>,
+    pub fixed: Column<Fixed>,
+}
+
+pub struct ConstantChip<F: FieldExt> {
+    config: ConstantConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ConstantChip<F> {
+    pub fn construct(config: ConstantConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        fixed: Column<Fixed>,
+    ) -> ConstantConfig {
+        meta.enable_equality(advice);
+        meta.enable_constant(fixed);
+
+        ConstantConfig { advice, fixed }
+    }
+
+    pub fn load_constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: F,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "load constant",
+            |mut region| {
+                region
+                    .assign_advice_from_constant(|| "constant", self.config.advice, 0, value)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Constant {
+        value: u64,
+    }
+
+    impl TestGadget<Fp> for Constant {
+        type Config = ConstantConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ConstantConfig {
+            let advice = meta.advice_column();
+            let fixed = meta.fixed_column();
+            ConstantChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ConstantConfig,
+            layouter: impl Layouter<Fp>,
+            _inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let c =
+                ConstantChip::construct(config).load_constant(layouter, Fp::from(self.value))?;
+            Ok(vec![c])
+        }
+    }
+
+    #[test]
+    fn constant_is_loaded() {
+        assert_eq!(run(4, Constant { value: 42 }, &[], &[Fp::from(42)]), Ok(()));
+    }
+
+    #[test]
+    fn constant_rejects_other_value() {
+        assert!(run(4, Constant { value: 42 }, &[], &[Fp::from(43)]).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    mul::{MulChip, MulConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// x, u, w 都 range check 到 64 bits，这样下面的等式两边都 < 2^128，不会在field里面溢出
+pub const COPRIME_BITS: usize = 64;
+
+// 证明 gcd(x, m) == 1，m 是常数
+// Bézout：存在整数 u, v 使得 u * x + v * m == 1。这里取 0 <= u < m，此时 v <= 0，
+// 写成 w = -v 就是 u * x == w * m + 1，全部都是非负整数
+// 注意在field里面 x 只要不是 0 就一定有逆，所以必须把 x, u, w 都range check住，
+// 让这个等式在整数上成立，才真正说明 gcd(x, m) == 1
+// x 和 m 有公因子的时候不存在这样的 u, w，witness生成会直接返回 Error::Synthesis
+#[derive(Debug, Clone)]
+pub struct CoprimeConfig {
+    pub advice: [Column<Advice>; 2],
+    pub constant: ConstantConfig,
+    pub mul: MulConfig,
+    pub add: AddConfig,
+    pub is_equal: IsEqualConfig,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct CoprimeChip<F: FieldExt> {
+    config: CoprimeConfig,
+    _marker: PhantomData<F>,
+}
+
+// 返回 u 使得 u * x ≡ 1 (mod m)，0 <= u < m；不互素的时候返回 None
+fn mod_inverse(x: u128, m: u128) -> Option<u128> {
+    let (mut old_r, mut r) = (x % m, m);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q as i128 * s);
+    }
+    (old_r == 1).then(|| old_s.rem_euclid(m as i128) as u128)
+}
+
+impl<F: FieldExt> CoprimeChip<F> {
+    pub fn construct(config: CoprimeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> CoprimeConfig {
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        CoprimeConfig {
+            advice: [advice[0], advice[1]],
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            mul: MulChip::configure(meta, advice),
+            add: AddChip::configure(meta, advice),
+            is_equal: IsEqualChip::configure(meta, advice),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn assert_coprime(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        m: u64,
+    ) -> Result<(), Error> {
+        // 任何数都和 1 互素
+        if m == 1 {
+            return Ok(());
+        }
+        if m == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        let (u, w) = layouter.assign_region(
+            || "witness bezout",
+            |mut region| {
+                let m = m as u128;
+                let uw_val = x.0.value().map(|x| {
+                    let x = to_u128(x);
+                    mod_inverse(x, m).and_then(|u| u.checked_mul(x).map(|ux| (u, (ux - 1) / m)))
+                });
+                let uw_val = uw_val.flatten();
+
+                let u = region
+                    .assign_advice(
+                        || "u",
+                        self.config.advice[0],
+                        0,
+                        || uw_val.map(|(u, _)| F::from_u128(u)).ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+                let w = region
+                    .assign_advice(
+                        || "w",
+                        self.config.advice[1],
+                        0,
+                        || uw_val.map(|(_, w)| F::from_u128(w)).ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                Ok((u, w))
+            },
+        )?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check x"), x, COPRIME_BITS)?;
+        decompose.decompose(layouter.namespace(|| "range check u"), &u, COPRIME_BITS)?;
+        decompose.decompose(layouter.namespace(|| "range check w"), &w, COPRIME_BITS)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let m_cell = constant.load_constant(layouter.namespace(|| "m"), F::from(m))?;
+        let one = constant.load_constant(layouter.namespace(|| "one"), F::one())?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let ux = mul.mul(layouter.namespace(|| "u * x"), &u, x)?;
+        let wm = mul.mul(layouter.namespace(|| "w * m"), &w, &m_cell)?;
+        let add = AddChip::construct(self.config.add.clone());
+        let rhs = add.add(layouter.namespace(|| "w * m + 1"), &wm, &one)?;
+
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        is_equal.assert_equal(layouter.namespace(|| "u * x == w * m + 1"), &ux, &rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Coprime {
+        m: u64,
+    }
+
+    impl TestGadget<Fp> for Coprime {
+        type Config = CoprimeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CoprimeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            CoprimeChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: CoprimeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            CoprimeChip::construct(config).assert_coprime(layouter, &inputs[0], self.m)?;
+            Ok(vec![])
+        }
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn mod_inverse_matches_native() {
+        for (x, m) in [(3u128, 7u128), (10, 7), (1, 2), (12, 35), (6, 9)] {
+            match mod_inverse(x, m) {
+                Some(u) => assert_eq!(u * x % m, 1),
+                None => assert_ne!(gcd(x as u64, m as u64), 1),
+            }
+        }
+    }
+
+    #[test]
+    fn coprime_accepts_coprime_inputs() {
+        for (x, m) in [
+            (3u64, 7u64),
+            (10, 7),
+            (1, 2),
+            (12, 35),
+            (u64::MAX, 1 << 32),
+            (5, 1),
+        ] {
+            assert_eq!(gcd(x, m), 1);
+            assert_eq!(run(8, Coprime { m }, &[Fp::from(x)], &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn coprime_rejects_common_factor() {
+        for (x, m) in [(6u64, 9u64), (0, 7), (14, 35), (7, 7)] {
+            assert_ne!(gcd(x, m), 1);
+            assert!(synthesis_fails(8, Coprime { m }, &[Fp::from(x)], &[]));
+        }
+    }
+
+    #[test]
+    fn coprime_rejects_x_wider_than_64_bits() {
+        // 2^64 + 1 是奇数，和 2 互素，但是超出了 COPRIME_BITS
+        let x = Fp::from_u128((1 << 64) + 1);
+        assert!(run(8, Coprime { m: 2 }, &[x], &[]).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    barrel_shift::{BarrelShiftChip, BarrelShiftConfig},
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    mux::{MuxChip, MuxConfig},
+    sub::{SubChip, SubConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// CORDIC 的一步旋转（第 shift 次迭代，旋转角度是 ±atan(2^-shift)）：
+//   sign = 1: x' = x - (y >> shift), y' = y + (x >> shift)
+//   sign = 0: x' = x + (y >> shift), y' = y - (x >> shift)
+// 旋转的过程中 x, y 会变成负数，所以这里的 x, y 都是有符号数，用 offset 编码：v 存成 u = v + 2^(bits-1)，
+// u 在 [0, 2^bits) 里面，也就是 v 在 [-2^(bits-1), 2^(bits-1)) 里面
+// 右移是算术右移（向下取整），因为 2^(bits-1) 能被 2^shift 整除：
+//   v >> shift = (u >> shift) - 2^(bits-1-shift)
+// u >> shift 用 BarrelShiftChip::shift_right（顺便range check了输入），减掉常数之后就是有符号的移位结果，
+// 再和 u 加减，得到的还是 offset 编码（offset 只算了一次）
+// 两个分支都算出来，再用 MuxChip 按 sign 选；x', y' 也用 DecomposeChip range check到 bits 位，
+// 溢出（|v| >= 2^(bits-1)）的时候约束过不去
+// bits <= 63，shift < bits
+#[derive(Debug, Clone)]
+pub struct CordicStepConfig {
+    pub constant: ConstantConfig,
+    pub barrel_shift: BarrelShiftConfig,
+    pub decompose: DecomposeConfig,
+    pub add: AddConfig,
+    pub sub: SubConfig,
+    pub mux: MuxConfig,
+    pub bits: usize,
+}
+
+pub struct CordicStepChip<F: FieldExt> {
+    config: CordicStepConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CordicStepChip<F> {
+    pub fn construct(config: CordicStepConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> CordicStepConfig {
+        CordicStepConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            barrel_shift: BarrelShiftChip::configure(meta, advice, fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            add: AddChip::configure(meta, advice),
+            sub: SubChip::configure(meta, advice),
+            mux: MuxChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    // 有符号数 v 的 offset 编码 v + 2^(bits-1)
+    pub fn encode(v: i64, bits: usize) -> F {
+        let u = v + (1i64 << (bits - 1));
+        if u < 0 {
+            -F::from(u.unsigned_abs())
+        } else {
+            F::from(u as u64)
+        }
+    }
+
+    pub fn step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        y: &ACell<F>,
+        sign: &Boolean<F>,
+        shift: usize,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let bits = self.config.bits;
+        if bits == 0 || bits > 63 || shift >= bits {
+            return Err(Error::Synthesis);
+        }
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let s = constant.load_constant(layouter.namespace(|| "shift"), F::from(shift as u64))?;
+        let offset = constant.load_constant(
+            layouter.namespace(|| "2^(bits-1-shift)"),
+            F::from(1u64 << (bits - 1 - shift)),
+        )?;
+
+        let barrel_shift = BarrelShiftChip::construct(self.config.barrel_shift.clone());
+        let sub = SubChip::construct(self.config.sub.clone());
+        let add = AddChip::construct(self.config.add.clone());
+
+        let x_shifted =
+            barrel_shift.shift_right(layouter.namespace(|| "x_u >> shift"), x, &s, bits)?;
+        let x_shifted = sub.sub(layouter.namespace(|| "x >> shift"), &x_shifted, &offset)?;
+        let y_shifted =
+            barrel_shift.shift_right(layouter.namespace(|| "y_u >> shift"), y, &s, bits)?;
+        let y_shifted = sub.sub(layouter.namespace(|| "y >> shift"), &y_shifted, &offset)?;
+
+        let x_minus = sub.sub(layouter.namespace(|| "x - (y >> shift)"), x, &y_shifted)?;
+        let x_plus = add.add(layouter.namespace(|| "x + (y >> shift)"), x, &y_shifted)?;
+        let y_plus = add.add(layouter.namespace(|| "y + (x >> shift)"), y, &x_shifted)?;
+        let y_minus = sub.sub(layouter.namespace(|| "y - (x >> shift)"), y, &x_shifted)?;
+
+        let mux = MuxChip::construct(self.config.mux.clone());
+        let new_x = mux.mux(layouter.namespace(|| "x'"), sign, &x_minus, &x_plus)?;
+        let new_y = mux.mux(layouter.namespace(|| "y'"), sign, &y_plus, &y_minus)?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check x'"), &new_x, bits)?;
+        decompose.decompose(layouter.namespace(|| "range check y'"), &new_y, bits)?;
+
+        Ok((new_x, new_y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const BITS: usize = 16;
+
+    // 输入是 [x, y, sign_0, sign_1, ...]（x, y 是 offset 编码），第 i 步用 sign_i 和 shift = i，
+    // 输出每一步之后的 (x, y)
+    #[derive(Clone, Default)]
+    struct Cordic;
+
+    impl TestGadget<Fp> for Cordic {
+        type Config = CordicStepConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CordicStepConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            CordicStepChip::configure(meta, advice, fixed, BITS)
+        }
+
+        fn synthesize(
+            &self,
+            config: CordicStepConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = CordicStepChip::construct(config);
+            let (mut x, mut y) = (inputs[0].clone(), inputs[1].clone());
+            let mut outputs = vec![];
+            for (shift, sign) in inputs[2..].iter().enumerate() {
+                (x, y) = chip.step(
+                    layouter.namespace(|| format!("step {}", shift)),
+                    &x,
+                    &y,
+                    &Boolean(sign.clone()),
+                    shift,
+                )?;
+                outputs.extend([x.clone(), y.clone()]);
+            }
+            Ok(outputs)
+        }
+    }
+
+    fn encode(v: i64) -> Fp {
+        CordicStepChip::<Fp>::encode(v, BITS)
+    }
+
+    // i64 的 >> 就是算术右移
+    fn native_step(x: i64, y: i64, sign: bool, shift: usize) -> (i64, i64) {
+        if sign {
+            (x - (y >> shift), y + (x >> shift))
+        } else {
+            (x + (y >> shift), y - (x >> shift))
+        }
+    }
+
+    fn native_steps(x: i64, y: i64, signs: &[bool]) -> Vec<(i64, i64)> {
+        let mut xy = (x, y);
+        signs
+            .iter()
+            .enumerate()
+            .map(|(shift, sign)| {
+                xy = native_step(xy.0, xy.1, *sign, shift);
+                xy
+            })
+            .collect()
+    }
+
+    fn inputs(x: i64, y: i64, signs: &[bool]) -> Vec<Fp> {
+        [encode(x), encode(y)]
+            .into_iter()
+            .chain(signs.iter().map(|s| Fp::from(*s as u64)))
+            .collect()
+    }
+
+    fn encoded(steps: &[(i64, i64)]) -> Vec<Fp> {
+        steps
+            .iter()
+            .flat_map(|(x, y)| [encode(*x), encode(*y)])
+            .collect()
+    }
+
+    #[test]
+    fn cordic_encoding() {
+        assert_eq!(encode(0), Fp::from(1 << (BITS - 1)));
+        assert_eq!(encode(-(1 << (BITS - 1))), Fp::zero());
+        assert_eq!(encode(-1) + Fp::one(), encode(0));
+    }
+
+    #[test]
+    fn cordic_rotation_crosses_zero() {
+        // 旋转模式：从 (2^12 / K, 0) 开始转 1°，每一步的方向由剩下的角度 z 的符号决定（K 是 CORDIC 的增益）
+        // 第一步转了 45°，之后 z = 1 - 45 + 26.57 + 14.04 = -3.4°，这几步 y 都是负数，最后才转回 1° 附近
+        let one = 4096.0;
+        let gain: f64 = (0..8).map(|i| (1.0 + 4f64.powi(-i)).sqrt()).product();
+        let (x0, y0) = ((one / gain).round() as i64, 0);
+
+        let mut z = 1f64.to_radians();
+        let signs: Vec<_> = (0..8)
+            .map(|i| {
+                let sign = z >= 0.0;
+                let angle = 2f64.powi(-i).atan();
+                z -= if sign { angle } else { -angle };
+                sign
+            })
+            .collect();
+
+        let steps = native_steps(x0, y0, &signs);
+        assert!(steps.iter().any(|(_, y)| *y < 0), "{:?}", steps);
+        let (x, y) = *steps.last().unwrap();
+        assert!((x as f64 - one * 1f64.to_radians().cos()).abs() < 64.0);
+        assert!((y as f64 - one * 1f64.to_radians().sin()).abs() < 64.0);
+
+        assert_eq!(
+            run(13, Cordic, &inputs(x0, y0, &signs), &encoded(&steps)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn cordic_sign_zero_and_one() {
+        // shift = 0：sign = 1 是 (x - y, y + x)，sign = 0 是 (x + y, y - x)
+        assert_eq!(native_step(300, 100, true, 0), (200, 400));
+        assert_eq!(native_step(300, 100, false, 0), (400, -200));
+        assert_eq!(
+            run(
+                10,
+                Cordic,
+                &inputs(300, 100, &[true]),
+                &encoded(&[(200, 400)])
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            run(
+                10,
+                Cordic,
+                &inputs(300, 100, &[false]),
+                &encoded(&[(400, -200)])
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn cordic_arithmetic_shift_of_negative() {
+        // (3, -2) -> (1, -5)，第二步 -5 >> 1 = -3（向下取整），不是 -2
+        let signs = [false, true];
+        let steps = native_steps(3, -2, &signs);
+        assert_eq!(steps, [(1, -5), (4, -5)]);
+        assert_eq!(
+            run(11, Cordic, &inputs(3, -2, &signs), &encoded(&steps)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn cordic_rejects_wrong_branch_and_overflow() {
+        // sign = 1 却给了 sign = 0 的结果
+        let expected = encoded(&native_steps(4000, 4000, &[false]));
+        assert!(run(10, Cordic, &inputs(4000, 4000, &[true]), &expected).is_err());
+        // 负数右移的时候向 0 取整（而不是向下取整）
+        let signs = [false, true];
+        let truncated: Vec<_> = signs
+            .iter()
+            .enumerate()
+            .scan((3i64, -2i64), |(x, y), (shift, sign)| {
+                let d = 1i64 << shift;
+                (*x, *y) = if *sign {
+                    (*x - *y / d, *y + *x / d)
+                } else {
+                    (*x + *y / d, *y - *x / d)
+                };
+                Some((*x, *y))
+            })
+            .collect();
+        assert_eq!(truncated, [(1, -5), (3, -5)]);
+        assert!(run(11, Cordic, &inputs(3, -2, &signs), &encoded(&truncated)).is_err());
+        // x' = 2^15 超出了有符号的范围
+        let max = (1 << (BITS - 1)) - 1;
+        assert!(run(
+            10,
+            Cordic,
+            &inputs(max, 1, &[false]),
+            &encoded(&[(max + 1, 1 - max)])
+        )
+        .is_err());
+    }
+}
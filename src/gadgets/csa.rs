@@ -0,0 +1,286 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+use crate::ACell;
+
+// carry-save adder 里面的 3:2 compressor（也就是一个全加器）
+//   sum = a ^ b ^ c，carry = majority(a, b, c)
+// 所有的都是boolean的时候，这两个式子等价于 a + b + c = sum + 2 * carry：
+// 左边在 [0, 3] 里面，右边的拆法是唯一的，sum 就是奇偶位，carry 就是"至少两个是 1"
+//
+//  a   |   b   | c | selector
+//  sum | carry |   |
+//
+#[derive(Debug, Clone)]
+pub struct CsaConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+pub struct CsaChip<F: FieldExt> {
+    config: CsaConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CsaChip<F> {
+    pub fn construct(config: CsaConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> CsaConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("3:2 compressor", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+            let sum = meta.query_advice(advice[0], Rotation::next());
+            let carry = meta.query_advice(advice[1], Rotation::next());
+            let one = Expression::Constant(F::one());
+            let two = Expression::Constant(F::from(2));
+
+            let bool_check = |v: Expression<F>| s.clone() * v.clone() * (one.clone() - v);
+
+            vec![
+                bool_check(a.clone()),
+                bool_check(b.clone()),
+                bool_check(c.clone()),
+                bool_check(sum.clone()),
+                bool_check(carry.clone()),
+                s.clone() * (a + b + c - sum - carry * two),
+            ]
+        });
+
+        CsaConfig { advice, selector }
+    }
+
+    // 返回 (sum, carry)
+    pub fn compress(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        c: &ACell<F>,
+    ) -> Result<(Boolean<F>, Boolean<F>), Error> {
+        layouter.assign_region(
+            || "3:2 compressor",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+                c.0.copy_advice(|| "c", &mut region, self.config.advice[2], 0)?;
+
+                let total =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .zip(c.0.value())
+                        .map(|((a, b), c)| [a, b, c].iter().filter(|v| ***v == F::one()).count());
+
+                let sum = region
+                    .assign_advice(
+                        || "sum",
+                        self.config.advice[0],
+                        1,
+                        || {
+                            total
+                                .map(|t| F::from((t % 2) as u64))
+                                .ok_or(Error::Synthesis)
+                        },
+                    )
+                    .map(ACell)?;
+                let carry = region
+                    .assign_advice(
+                        || "carry",
+                        self.config.advice[1],
+                        1,
+                        || {
+                            total
+                                .map(|t| F::from((t >= 2) as u64))
+                                .ok_or(Error::Synthesis)
+                        },
+                    )
+                    .map(ACell)?;
+
+                Ok((Boolean(sum), Boolean(carry)))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [a, b, c]，输出 [sum, carry]
+    #[derive(Clone, Default)]
+    struct Csa;
+
+    impl TestGadget<Fp> for Csa {
+        type Config = CsaConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CsaConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            CsaChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: CsaConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (sum, carry) = CsaChip::construct(config)
+                .compress(layouter, &inputs[0], &inputs[1], &inputs[2])?;
+            Ok(vec![sum.0, carry.0])
+        }
+    }
+
+    // 不走 compress，直接把伪造的 sum / carry 填进gate里面，看gate本身能不能拦住
+    #[derive(Clone, Default)]
+    struct ForgedCsa {
+        sum: i64,
+        carry: i64,
+    }
+
+    fn signed(v: i64) -> Fp {
+        if v < 0 {
+            -Fp::from(v.unsigned_abs())
+        } else {
+            Fp::from(v as u64)
+        }
+    }
+
+    impl TestGadget<Fp> for ForgedCsa {
+        type Config = CsaConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> CsaConfig {
+            Csa::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: CsaConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            layouter.assign_region(
+                || "forged 3:2 compressor",
+                |mut region| {
+                    config.selector.enable(&mut region, 0)?;
+                    for (input, column) in inputs.iter().zip(config.advice) {
+                        input.0.copy_advice(|| "input", &mut region, column, 0)?;
+                    }
+                    region.assign_advice(|| "sum", config.advice[0], 1, || Ok(signed(self.sum)))?;
+                    region.assign_advice(
+                        || "carry",
+                        config.advice[1],
+                        1,
+                        || Ok(signed(self.carry)),
+                    )?;
+                    Ok(vec![])
+                },
+            )
+        }
+    }
+
+    fn all_inputs() -> impl Iterator<Item = [u64; 3]> {
+        (0..8u64).map(|i| [(i >> 2) & 1, (i >> 1) & 1, i & 1])
+    }
+
+    fn native(bits: [u64; 3]) -> (u64, u64) {
+        (
+            bits[0] ^ bits[1] ^ bits[2],
+            (bits[0] & bits[1]) | (bits[1] & bits[2]) | (bits[0] & bits[2]),
+        )
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn csa_matches_native_exhaustively() {
+        for bits in all_inputs() {
+            let (sum, carry) = native(bits);
+            assert_eq!(
+                run(4, Csa, &fp(&bits), &fp(&[sum, carry])),
+                Ok(()),
+                "{:?}",
+                bits
+            );
+
+            // 直接填正确的值，gate 当然也是满足的
+            let honest = ForgedCsa {
+                sum: sum as i64,
+                carry: carry as i64,
+            };
+            assert_eq!(run(4, honest, &fp(&bits), &[]), Ok(()), "{:?}", bits);
+        }
+    }
+
+    #[test]
+    fn csa_rejects_forged_outputs() {
+        for bits in all_inputs() {
+            let (sum, carry) = native(bits);
+            let total = (bits[0] + bits[1] + bits[2]) as i64;
+
+            // 另外三种 boolean 的 (sum, carry)
+            for (s, c) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+                if (s, c) == (sum, carry) {
+                    continue;
+                }
+                let forged = ForgedCsa {
+                    sum: s as i64,
+                    carry: c as i64,
+                };
+                assert!(
+                    run(4, forged, &fp(&bits), &[]).is_err(),
+                    "{:?} -> ({}, {})",
+                    bits,
+                    s,
+                    c
+                );
+            }
+
+            // sum + 2 * carry 还是对的，但是 sum / carry 不是 boolean
+            for (s, c) in [(total, 0), (total - 2, 1), (total + 2, -1)] {
+                if (s, c) == (sum as i64, carry as i64) {
+                    continue;
+                }
+                let forged = ForgedCsa { sum: s, carry: c };
+                assert!(
+                    run(4, forged, &fp(&bits), &[]).is_err(),
+                    "{:?} -> ({}, {})",
+                    bits,
+                    s,
+                    c
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn csa_rejects_non_boolean_inputs() {
+        // 2 + 0 + 0 = 0 + 2 * 1，但是 a 不是 bit
+        let forged = ForgedCsa { sum: 0, carry: 1 };
+        assert!(run(4, forged, &fp(&[2, 0, 0]), &[]).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    permutation_check::{PermutationCheckChip, PermutationCheckConfig},
+    recompose::{RecomposeChip, RecomposeConfig},
+};
+use crate::ACell;
+
+// de Bruijn 序列 B(2, k)：所有长度为 k 的窗口两两不同
+// 每个窗口 bits[i..i + k] 当作一个 k 位的二进制数（bits[i] 是最高位），用 RecomposeChip 算出它的值，
+// RecomposeChip 同时也保证了每个bit都 < 2
+// 窗口一共有 2^k 个，它们的值是 0..2^k 的一个permutation（grand product，gamma 的要求见 PermutationCheckChip），
+// 也就等价于两两不同
+// 支持两种长度：
+// * 循环序列，长度 2^k，窗口可以绕回开头
+// * 线性序列，长度 2^k + k - 1
+// k = 1 的时候两种长度是一样的（"01" 或 "10"）
+// 电路的大小是 O(k * 2^k)，这里限制 k <= 16
+#[derive(Debug, Clone)]
+pub struct DeBruijnConfig {
+    pub constant: ConstantConfig,
+    pub recompose: RecomposeConfig,
+    pub permutation_check: PermutationCheckConfig,
+}
+
+pub struct DeBruijnChip<F: FieldExt> {
+    config: DeBruijnConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DeBruijnChip<F> {
+    pub fn construct(config: DeBruijnConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> DeBruijnConfig {
+        DeBruijnConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            recompose: RecomposeChip::configure(meta, advice, fixed),
+            permutation_check: PermutationCheckChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_de_bruijn(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bits: &[ACell<F>],
+        k: usize,
+        gamma: &ACell<F>,
+    ) -> Result<(), Error> {
+        if k == 0 || k > 16 {
+            return Err(Error::Synthesis);
+        }
+        let num_windows = 1usize << k;
+        if bits.len() != num_windows && bits.len() != num_windows + k - 1 {
+            return Err(Error::Synthesis);
+        }
+
+        let recompose = RecomposeChip::construct(self.config.recompose.clone());
+        let mut windows = Vec::with_capacity(num_windows);
+        for i in 0..num_windows {
+            // RecomposeChip 要 little-endian，所以窗口倒过来放
+            let window: Vec<_> = (0..k)
+                .rev()
+                .map(|j| bits[(i + j) % bits.len()].clone())
+                .collect();
+            windows.push(recompose.recompose(
+                layouter.namespace(|| format!("window {}", i)),
+                &window,
+                2,
+            )?);
+        }
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let expected = (0..num_windows)
+            .map(|v| {
+                constant.load_constant(
+                    layouter.namespace(|| format!("value {}", v)),
+                    F::from(v as u64),
+                )
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let permutation_check =
+            PermutationCheckChip::construct(self.config.permutation_check.clone());
+        permutation_check.assert_permutation(
+            layouter.namespace(|| "windows are distinct"),
+            &windows,
+            &expected,
+            gamma,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [gamma, bits...]，没有输出
+    #[derive(Clone, Default)]
+    struct DeBruijn {
+        k: usize,
+    }
+
+    impl TestGadget<Fp> for DeBruijn {
+        type Config = DeBruijnConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DeBruijnConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            DeBruijnChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: DeBruijnConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (gamma, bits) = inputs.split_first().unwrap();
+            DeBruijnChip::construct(config).assert_de_bruijn(layouter, bits, self.k, gamma)?;
+            Ok(vec![])
+        }
+    }
+
+    // 标准的 FKM 构造（把长度整除 k 的 Lyndon word 按字典序拼起来），得到循环的 B(2, k)
+    fn native_de_bruijn(k: usize) -> Vec<u64> {
+        fn db(t: usize, p: usize, k: usize, a: &mut Vec<u64>, seq: &mut Vec<u64>) {
+            if t > k {
+                if k.is_multiple_of(p) {
+                    seq.extend_from_slice(&a[1..=p]);
+                }
+            } else {
+                a[t] = a[t - p];
+                db(t + 1, p, k, a, seq);
+                for j in a[t - p] + 1..2 {
+                    a[t] = j;
+                    db(t + 1, t, k, a, seq);
+                }
+            }
+        }
+
+        let mut a = vec![0; k + 1];
+        let mut seq = vec![];
+        db(1, 1, k, &mut a, &mut seq);
+        seq
+    }
+
+    fn windows_distinct(bits: &[u64], k: usize) -> bool {
+        let mut seen = vec![false; 1 << k];
+        (0..1 << k).all(|i| {
+            let v = (0..k).fold(0, |acc, j| 2 * acc + bits[(i + j) % bits.len()]) as usize;
+            !std::mem::replace(&mut seen[v], true)
+        })
+    }
+
+    fn inputs(bits: &[u64]) -> Vec<Fp> {
+        std::iter::once(Fp::from(0x1234_5678_9abc))
+            .chain(bits.iter().map(|b| Fp::from(*b)))
+            .collect()
+    }
+
+    #[test]
+    fn de_bruijn_accepts_native_sequences() {
+        for k in 1..=3 {
+            let cyclic = native_de_bruijn(k);
+            assert_eq!(cyclic.len(), 1 << k);
+            assert!(windows_distinct(&cyclic, k));
+
+            let linear: Vec<_> = cyclic.iter().chain(&cyclic[..k - 1]).copied().collect();
+            for bits in [cyclic.clone(), linear] {
+                assert_eq!(
+                    run(8, DeBruijn { k }, &inputs(&bits), &[]),
+                    Ok(()),
+                    "{:?}",
+                    bits
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn de_bruijn_matches_native_for_every_sequence() {
+        // k = 1 和 k = 2 的时候把所有可能的序列（循环和线性两种长度）都试一遍
+        for (k, len) in [(1, 2), (2, 4), (2, 5)] {
+            for v in 0u64..1 << len {
+                let bits: Vec<_> = (0..len).map(|i| (v >> i) & 1).collect();
+                assert_eq!(
+                    run(8, DeBruijn { k }, &inputs(&bits), &[]).is_ok(),
+                    windows_distinct(&bits, k),
+                    "k = {}, {:?}",
+                    k,
+                    bits
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn de_bruijn_rejects_non_bits() {
+        // 最后一个窗口是 2 * 0 + 3 = 3，窗口的值正好是 0..4 的permutation，只有bit的range check能挡住
+        assert!(run(8, DeBruijn { k: 2 }, &inputs(&[0, 0, 1, 1, 0]), &[]).is_ok());
+        assert!(run(8, DeBruijn { k: 2 }, &inputs(&[0, 0, 1, 0, 3]), &[]).is_err());
+    }
+
+    #[test]
+    fn de_bruijn_rejects_bad_length() {
+        assert!(synthesis_fails(
+            8,
+            DeBruijn { k: 2 },
+            &inputs(&[0, 0, 1]),
+            &[]
+        ));
+        assert!(synthesis_fails(8, DeBruijn { k: 0 }, &inputs(&[0]), &[]));
+    }
+}
@@ -0,0 +1,215 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{to_u128, Boolean};
+use crate::ACell;
+
+// 把 x 拆成 bits 个bit，同时也就证明了 x < 2^bits（可以当作range check来用）
+// 从最高位开始累加（Horner）：acc = 2 * acc_prev + bit，最后一行的 acc 就是 x 本身
+//
+//   bit     |   acc   | q_first | q_step
+//  b_{n-1}  | b_{n-1} |    1    |   0
+//  b_{n-2}  |   ...   |    0    |   1
+//   b_0     |    x    |    0    |   1
+//
+#[derive(Debug, Clone)]
+pub struct DecomposeConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_first: Selector,
+    pub q_step: Selector,
+}
+
+pub struct DecomposeChip<F: FieldExt> {
+    config: DecomposeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DecomposeChip<F> {
+    pub fn construct(config: DecomposeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> DecomposeConfig {
+        let col_bit = advice[0];
+        let col_acc = advice[1];
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        meta.enable_equality(col_bit);
+        meta.enable_equality(col_acc);
+
+        // 第一行没有 acc_prev，所以拆成两个gate，不然 q_first 那一行也会去query上一行
+        meta.create_gate("decompose first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let bit = meta.query_advice(col_bit, Rotation::cur());
+            let acc = meta.query_advice(col_acc, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                // bit 必须是 0 或 1
+                q_first.clone() * bit.clone() * (one - bit.clone()),
+                // 第一行：acc = bit
+                q_first * (acc - bit),
+            ]
+        });
+
+        meta.create_gate("decompose step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let bit = meta.query_advice(col_bit, Rotation::cur());
+            let acc = meta.query_advice(col_acc, Rotation::cur());
+            let acc_prev = meta.query_advice(col_acc, Rotation::prev());
+            let one = Expression::Constant(F::one());
+            let two = Expression::Constant(F::from(2));
+
+            vec![
+                q_step.clone() * bit.clone() * (one - bit.clone()),
+                // 其余行：acc = 2 * acc_prev + bit
+                q_step * (acc - (acc_prev * two + bit)),
+            ]
+        });
+
+        DecomposeConfig {
+            advice: [col_bit, col_acc],
+            q_first,
+            q_step,
+        }
+    }
+
+    // 返回的bits是 little-endian 的，也就是 bits[0] 是最低位
+    pub fn decompose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bits: usize,
+    ) -> Result<Vec<Boolean<F>>, Error> {
+        if bits == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "decompose",
+            |mut region| {
+                let x_val = x.0.value().map(to_u128);
+                let mut acc_val = Some(F::zero());
+                let mut out = Vec::with_capacity(bits);
+
+                for row in 0..bits {
+                    // 第 row 行放的是第 i 位（从最高位往下）
+                    let i = bits - 1 - row;
+                    let bit_val = x_val.map(|x| {
+                        if i < 128 {
+                            F::from(((x >> i) & 1) as u64)
+                        } else {
+                            F::zero()
+                        }
+                    });
+                    acc_val = acc_val.zip(bit_val).map(|(acc, bit)| acc.double() + bit);
+
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    let bit_cell = region.assign_advice(
+                        || format!("bit {}", i),
+                        self.config.advice[0],
+                        row,
+                        || bit_val.ok_or(Error::Synthesis),
+                    )?;
+
+                    if row == bits - 1 {
+                        // 最后一行的acc就是x，直接copy过来，这样gate就把bits和x绑定在一起了
+                        x.0.copy_advice(|| "x", &mut region, self.config.advice[1], row)?;
+                    } else {
+                        region.assign_advice(
+                            || "acc",
+                            self.config.advice[1],
+                            row,
+                            || acc_val.ok_or(Error::Synthesis),
+                        )?;
+                    }
+
+                    out.push(Boolean(ACell(bit_cell)));
+                }
+
+                out.reverse();
+                Ok(out)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Decompose {
+        bits: usize,
+    }
+
+    impl TestGadget<Fp> for Decompose {
+        type Config = DecomposeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DecomposeConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            DecomposeChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: DecomposeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let bits =
+                DecomposeChip::construct(config).decompose(layouter, &inputs[0], self.bits)?;
+            Ok(bits.into_iter().map(|b| b.0).collect())
+        }
+    }
+
+    fn bits_of(x: u64, n: usize) -> Vec<Fp> {
+        (0..n).map(|i| Fp::from((x >> i) & 1)).collect()
+    }
+
+    #[test]
+    fn decompose_matches_native_bits() {
+        for (x, n) in [
+            (0u64, 1),
+            (1, 1),
+            (0b1011, 4),
+            (200, 8),
+            (255, 8),
+            (u64::MAX, 64),
+        ] {
+            assert_eq!(
+                run(8, Decompose { bits: n }, &[Fp::from(x)], &bits_of(x, n)),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn decompose_rejects_out_of_range_value() {
+        // 256 放不进 8 bits：算出来的bits重组不回 x
+        assert!(run(6, Decompose { bits: 8 }, &[Fp::from(256)], &bits_of(256, 8)).is_err());
+    }
+
+    #[test]
+    fn decompose_rejects_wrong_bits() {
+        let mut bits = bits_of(0b1011, 4);
+        bits[2] = Fp::one();
+        assert!(run(5, Decompose { bits: 4 }, &[Fp::from(0b1011)], &bits).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    mux::{MuxChip, MuxConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 删除下标 k 处的元素（k 是一个cell），后面的元素都往前挪一位，和 InsertAtChip 反过来
+// 对 input 的每个位置 j：
+//   eq_j = (j == k)
+//   removed_j = eq_0 + ... + eq_j（k <= j，也就是这个位置已经要从后面取了）
+//   output[j] = removed_j ? input[j + 1] : input[j]（j < n - 1）
+// 最后断言 eq_0 + ... + eq_{n-1} == 1，也就是 0 <= k < n，这样 removed_j 也一定是 0 或 1
+// 只有一个元素的时候 output 是空的，但还是要断言 k == 0
+#[derive(Debug, Clone)]
+pub struct DeleteAtConfig {
+    pub constant: ConstantConfig,
+    pub is_equal: IsEqualConfig,
+    pub add: AddConfig,
+    pub mux: MuxConfig,
+}
+
+pub struct DeleteAtChip<F: FieldExt> {
+    config: DeleteAtConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DeleteAtChip<F> {
+    pub fn construct(config: DeleteAtConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> DeleteAtConfig {
+        DeleteAtConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            is_equal: IsEqualChip::configure(meta, advice),
+            add: AddChip::configure(meta, advice),
+            mux: MuxChip::configure(meta, advice),
+        }
+    }
+
+    pub fn delete_at(
+        &self,
+        mut layouter: impl Layouter<F>,
+        input: &[ACell<F>],
+        k: &ACell<F>,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        if input.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        let add = AddChip::construct(self.config.add.clone());
+        let mux = MuxChip::construct(self.config.mux.clone());
+
+        let n = input.len();
+        let mut output = Vec::with_capacity(n - 1);
+        let mut removed: Option<ACell<F>> = None;
+
+        for j in 0..n {
+            let j_cell = constant.load_constant(
+                layouter.namespace(|| format!("j = {}", j)),
+                F::from(j as u64),
+            )?;
+            let eq = is_equal.is_equal(layouter.namespace(|| format!("k == {}", j)), k, &j_cell)?;
+
+            let current = match removed {
+                Some(removed) => add.add(
+                    layouter.namespace(|| format!("removed {}", j)),
+                    &removed,
+                    &eq.0,
+                )?,
+                None => eq.0,
+            };
+
+            if j + 1 < n {
+                output.push(mux.mux(
+                    layouter.namespace(|| format!("output {}", j)),
+                    &Boolean(current.clone()),
+                    &input[j + 1],
+                    &input[j],
+                )?);
+            }
+            removed = Some(current);
+        }
+
+        let total = removed.unwrap();
+        layouter.assign_region(
+            || "0 <= k < n",
+            |mut region| region.constrain_constant(total.0.cell(), F::one()),
+        )?;
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 input...，最后一个是 k
+    #[derive(Clone, Default)]
+    struct DeleteAt;
+
+    impl TestGadget<Fp> for DeleteAt {
+        type Config = DeleteAtConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DeleteAtConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            DeleteAtChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: DeleteAtConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (k, input) = inputs.split_last().unwrap();
+            DeleteAtChip::construct(config).delete_at(layouter, input, k)
+        }
+    }
+
+    fn inputs(input: &[u64], k: u64) -> Vec<Fp> {
+        input.iter().chain([&k]).map(|v| Fp::from(*v)).collect()
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn delete_at_matches_native() {
+        let input = [10u64, 20, 30, 40];
+        // 第一个，中间，最后一个
+        for k in 0..input.len() {
+            let mut expected = input.to_vec();
+            expected.remove(k);
+            assert_eq!(
+                run(7, DeleteAt, &inputs(&input, k as u64), &fp(&expected)),
+                Ok(()),
+                "k = {}",
+                k
+            );
+        }
+        // 只有一个元素，删完就空了
+        assert_eq!(run(7, DeleteAt, &inputs(&[5], 0), &[]), Ok(()));
+    }
+
+    #[test]
+    fn delete_at_rejects_wrong_output() {
+        let input = [10u64, 20, 30, 40];
+        assert!(run(7, DeleteAt, &inputs(&input, 1), &fp(&[10, 20, 40])).is_err());
+        assert!(run(7, DeleteAt, &inputs(&input, 1), &fp(&[20, 30, 40])).is_err());
+    }
+
+    #[test]
+    fn delete_at_rejects_out_of_range_index() {
+        // k = n 的时候没有元素被删掉，output 就是 input 的前 n - 1 个
+        let input = [10u64, 20, 30];
+        assert!(run(7, DeleteAt, &inputs(&input, 3), &fp(&[10, 20])).is_err());
+        assert!(run(7, DeleteAt, &inputs(&[5], 1), &[]).is_err());
+        assert!(synthesis_fails(7, DeleteAt, &inputs(&[], 0), &[]));
+    }
+}
@@ -0,0 +1,219 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use crate::ACell;
+
+// 证明 d 是方阵 M 的对角线：d[i] == M[i][i]
+// 和 TransposeChip 一样不需要custom gate，每一行放一对 M[i][i] / d[i]，加一个copy constraint
+// M 不是方阵，或者 d 的长度和 M 不一样的时候返回 Error::Synthesis
+// 没有现成的 d 的时候用 diagonal()，d[i] 是新witness出来的cell，同样和 M[i][i] 做copy constraint
+//
+//  M[i][i] | d[i]
+//
+#[derive(Debug, Clone)]
+pub struct DiagonalConfig {
+    pub advice: [Column<Advice>; 2],
+}
+
+pub struct DiagonalChip<F: FieldExt> {
+    config: DiagonalConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DiagonalChip<F> {
+    pub fn construct(config: DiagonalConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> DiagonalConfig {
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        DiagonalConfig { advice }
+    }
+
+    pub fn diagonal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        m: &[Vec<ACell<F>>],
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let size = m.len();
+        if m.iter().any(|row| row.len() != size) {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "diagonal",
+            |mut region| {
+                m.iter()
+                    .enumerate()
+                    .map(|(i, row)| {
+                        let m_ii = row[i].0.copy_advice(
+                            || "m[i][i]",
+                            &mut region,
+                            self.config.advice[0],
+                            i,
+                        )?;
+                        let d_i = region.assign_advice(
+                            || "d[i]",
+                            self.config.advice[1],
+                            i,
+                            || m_ii.value().copied().ok_or(Error::Synthesis),
+                        )?;
+                        region.constrain_equal(m_ii.cell(), d_i.cell())?;
+                        Ok(ACell(d_i))
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    pub fn assert_diagonal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        m: &[Vec<ACell<F>>],
+        d: &[ACell<F>],
+    ) -> Result<(), Error> {
+        let size = m.len();
+        if m.iter().any(|row| row.len() != size) || d.len() != size {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "diagonal",
+            |mut region| {
+                for (i, (row, d_i)) in m.iter().zip(d).enumerate() {
+                    let m_ii = row[i].0.copy_advice(
+                        || "m[i][i]",
+                        &mut region,
+                        self.config.advice[0],
+                        i,
+                    )?;
+                    let d_i =
+                        d_i.0
+                            .copy_advice(|| "d[i]", &mut region, self.config.advice[1], i)?;
+                    region.constrain_equal(m_ii.cell(), d_i.cell())?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [M 按行展开 (rows * cols 个), d (d_len 个)]，没有输出
+    #[derive(Clone, Default)]
+    struct Diagonal {
+        rows: usize,
+        cols: usize,
+    }
+
+    impl TestGadget<Fp> for Diagonal {
+        type Config = DiagonalConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DiagonalConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            DiagonalChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: DiagonalConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (m, d) = inputs.split_at(self.rows * self.cols);
+            let m: Vec<_> = m.chunks(self.cols).map(|row| row.to_vec()).collect();
+            DiagonalChip::construct(config).assert_diagonal(layouter, &m, d)?;
+            Ok(vec![])
+        }
+    }
+
+    fn inputs(m: &[&[u64]], d: &[u64]) -> Vec<Fp> {
+        m.iter()
+            .flat_map(|row| row.iter())
+            .chain(d)
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    fn native_diagonal(m: &[&[u64]]) -> Vec<u64> {
+        m.iter().enumerate().map(|(i, row)| row[i]).collect()
+    }
+
+    #[test]
+    fn diagonal_matches_native() {
+        let matrices: [&[&[u64]]; 3] = [
+            &[&[7]],
+            &[&[1, 2], &[3, 4]],
+            &[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]],
+        ];
+        for m in matrices {
+            let size = m.len();
+            let d = native_diagonal(m);
+            assert_eq!(
+                run(
+                    5,
+                    Diagonal {
+                        rows: size,
+                        cols: size
+                    },
+                    &inputs(m, &d),
+                    &[]
+                ),
+                Ok(()),
+                "{:?}",
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn diagonal_rejects_corrupted_entry() {
+        let m: &[&[u64]] = &[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]];
+        let g = Diagonal { rows: 3, cols: 3 };
+        // 把非对角线上的元素当成对角线
+        assert!(run(5, g.clone(), &inputs(m, &[1, 5, 8]), &[]).is_err());
+        assert!(run(5, g.clone(), &inputs(m, &[2, 5, 9]), &[]).is_err());
+        assert!(run(5, g, &inputs(m, &[1, 5, 10]), &[]).is_err());
+    }
+
+    #[test]
+    fn diagonal_rejects_bad_shapes() {
+        // 不是方阵
+        let m: &[&[u64]] = &[&[1, 2, 3], &[4, 5, 6]];
+        assert!(synthesis_fails(
+            5,
+            Diagonal { rows: 2, cols: 3 },
+            &inputs(m, &[1, 5]),
+            &[]
+        ));
+        // d 的长度不对
+        let m: &[&[u64]] = &[&[1, 2], &[3, 4]];
+        assert!(synthesis_fails(
+            5,
+            Diagonal { rows: 2, cols: 2 },
+            &inputs(m, &[1]),
+            &[]
+        ));
+        assert!(synthesis_fails(
+            5,
+            Diagonal { rows: 2, cols: 2 },
+            &inputs(m, &[1, 4, 0]),
+            &[]
+        ));
+    }
+}
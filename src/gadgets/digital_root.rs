@@ -0,0 +1,182 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    base_b::{BaseBChip, BaseBConfig},
+};
+use crate::ACell;
+
+// digital root：不断地把十进制的各位数字加起来，直到只剩一位
+// 电路里面不能写while，所以固定做 max_iters 轮 “拆成十进制digits再求和”，
+// 最后再把结果拆成 1 个十进制digit，也就是断言它已经 < 10
+// 已经是一位数的时候，digit sum 就是它自己，多做几轮也没关系
+//
+// num_digits 是每一轮拆分用的digit个数，x 需要 < 10^num_digits（digit sum 只会更小）
+#[derive(Debug, Clone)]
+pub struct DigitalRootConfig {
+    pub add: AddConfig,
+    pub base_b: BaseBConfig,
+    pub num_digits: usize,
+}
+
+pub struct DigitalRootChip<F: FieldExt> {
+    config: DigitalRootConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DigitalRootChip<F> {
+    pub fn construct(config: DigitalRootConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        num_digits: usize,
+    ) -> DigitalRootConfig {
+        DigitalRootConfig {
+            add: AddChip::configure(meta, advice),
+            base_b: BaseBChip::configure(meta, advice, fixed),
+            num_digits,
+        }
+    }
+
+    pub fn digital_root(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        max_iters: usize,
+    ) -> Result<ACell<F>, Error> {
+        let base_b = BaseBChip::construct(self.config.base_b.clone());
+        let add = AddChip::construct(self.config.add.clone());
+
+        let mut current = x.clone();
+        for iter in 0..max_iters {
+            let digits = base_b.decompose(
+                layouter.namespace(|| format!("digits {}", iter)),
+                &current,
+                10,
+                self.config.num_digits,
+            )?;
+
+            let mut sum = digits[0].clone();
+            for digit in &digits[1..] {
+                sum = add.add(layouter.namespace(|| "digit sum"), &sum, digit)?;
+            }
+            current = sum;
+        }
+
+        // 最后必须只剩一位
+        base_b.decompose(layouter.namespace(|| "single digit"), &current, 10, 1)?;
+
+        Ok(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct DigitalRoot {
+        max_iters: usize,
+    }
+
+    impl TestGadget<Fp> for DigitalRoot {
+        type Config = DigitalRootConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DigitalRootConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            DigitalRootChip::configure(meta, advice, fixed, 4)
+        }
+
+        fn synthesize(
+            &self,
+            config: DigitalRootConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![DigitalRootChip::construct(config).digital_root(
+                layouter,
+                &inputs[0],
+                self.max_iters,
+            )?])
+        }
+    }
+
+    fn digital_root(mut x: u64) -> u64 {
+        while x >= 10 {
+            let mut sum = 0;
+            while x > 0 {
+                sum += x % 10;
+                x /= 10;
+            }
+            x = sum;
+        }
+        x
+    }
+
+    #[test]
+    fn digital_root_matches_native() {
+        // 4 位数最多要 3 轮：9999 -> 36 -> 9
+        for x in [0u64, 7, 9, 10, 38, 99, 1234, 9999] {
+            assert_eq!(
+                run(
+                    8,
+                    DigitalRoot { max_iters: 3 },
+                    &[Fp::from(x)],
+                    &[Fp::from(digital_root(x))]
+                ),
+                Ok(())
+            );
+        }
+        // 一位数不需要迭代
+        assert_eq!(
+            run(
+                8,
+                DigitalRoot { max_iters: 0 },
+                &[Fp::from(5)],
+                &[Fp::from(5)]
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    // 1 + 2 + 3 + 4 = 10，还要再做一轮才是 1
+    fn digital_root_rejects_wrong_root() {
+        assert!(run(
+            8,
+            DigitalRoot { max_iters: 3 },
+            &[Fp::from(1234)],
+            &[Fp::from(10)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn digital_root_rejects_too_few_iterations() {
+        // 9999 -> 36 一轮之后还不是一位数
+        assert!(run(
+            8,
+            DigitalRoot { max_iters: 1 },
+            &[Fp::from(9999)],
+            &[Fp::from(36)]
+        )
+        .is_err());
+    }
+}
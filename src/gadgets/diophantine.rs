@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+};
+use crate::ACell;
+
+// 证明 (x, y) 是 a * x + b * y == c 的一个解，a, b, c 都是常数
+// 注意这里是在field里面成立，不是整数上；需要整数解的话要自己再range check x, y
+#[derive(Debug, Clone)]
+pub struct DiophantineConfig {
+    pub mul_const: MulConstConfig,
+    pub add: AddConfig,
+    pub constant: ConstantConfig,
+    pub is_equal: IsEqualConfig,
+}
+
+pub struct DiophantineChip<F: FieldExt> {
+    config: DiophantineConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DiophantineChip<F> {
+    pub fn construct(config: DiophantineConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> DiophantineConfig {
+        DiophantineConfig {
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            add: AddChip::configure(meta, advice),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            is_equal: IsEqualChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_solution(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        y: &ACell<F>,
+        a: F,
+        b: F,
+        c: F,
+    ) -> Result<(), Error> {
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let ax = mul_const.mul_const(layouter.namespace(|| "a * x"), x, a)?;
+        let by = mul_const.mul_const(layouter.namespace(|| "b * y"), y, b)?;
+
+        let add = AddChip::construct(self.config.add.clone());
+        let sum = add.add(layouter.namespace(|| "a * x + b * y"), &ax, &by)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let c = constant.load_constant(layouter.namespace(|| "c"), c)?;
+
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        is_equal.assert_equal(layouter.namespace(|| "a * x + b * y == c"), &sum, &c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Diophantine {
+        a: u64,
+        b: u64,
+        c: u64,
+    }
+
+    impl TestGadget<Fp> for Diophantine {
+        type Config = DiophantineConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DiophantineConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            DiophantineChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: DiophantineConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            DiophantineChip::construct(config).assert_solution(
+                layouter,
+                &inputs[0],
+                &inputs[1],
+                Fp::from(self.a),
+                Fp::from(self.b),
+                Fp::from(self.c),
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn diophantine_matches_native() {
+        for (a, b, c) in [(3u64, 5u64, 22u64), (0, 5, 20), (3, 0, 9), (0, 0, 0)] {
+            for x in 0..6u64 {
+                for y in 0..6u64 {
+                    let g = Diophantine { a, b, c };
+                    assert_eq!(
+                        run(5, g, &[Fp::from(x), Fp::from(y)], &[]).is_ok(),
+                        a * x + b * y == c,
+                        "{} * {} + {} * {} == {}",
+                        a,
+                        x,
+                        b,
+                        y,
+                        c
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn diophantine_accepts_field_solutions() {
+        // 3x + 5y = 1 在整数上有 x = 2, y = -1，在field里面 -1 就是 p - 1
+        let g = Diophantine { a: 3, b: 5, c: 1 };
+        assert_eq!(run(5, g, &[Fp::from(2), -Fp::one()], &[]), Ok(()));
+    }
+}
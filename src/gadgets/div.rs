@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::modulo::{ModChip, ModConfig};
+use crate::ACell;
+
+// 除以一个常数 d：x = q * d + r，0 <= r < d
+// 约束和 ModChip 完全一样，只是这里关心的是商 q，所以直接复用 ModChip
+// q 会被range check到 QUOTIENT_BITS 位
+#[derive(Debug, Clone)]
+pub struct DivConfig {
+    pub modulo: ModConfig,
+}
+
+pub struct DivChip<F: FieldExt> {
+    config: DivConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DivChip<F> {
+    pub fn construct(config: DivConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> DivConfig {
+        DivConfig {
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    // 返回 (q, r)
+    pub fn div_rem(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        d: u64,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        modulo.div_rem(layouter, x, d)
+    }
+
+    pub fn div(&self, layouter: impl Layouter<F>, x: &ACell<F>, d: u64) -> Result<ACell<F>, Error> {
+        self.div_rem(layouter, x, d).map(|(q, _)| q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Div {
+        d: u64,
+    }
+
+    impl TestGadget<Fp> for Div {
+        type Config = DivConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DivConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            DivChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: DivConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (q, r) = DivChip::construct(config).div_rem(layouter, &inputs[0], self.d)?;
+            Ok(vec![q, r])
+        }
+    }
+
+    #[test]
+    fn div_matches_native() {
+        for (x, d) in [
+            (0u64, 7u64),
+            (6, 7),
+            (7, 7),
+            (100, 7),
+            (u64::MAX, 2),
+            (5, 1),
+        ] {
+            assert_eq!(
+                run(
+                    8,
+                    Div { d },
+                    &[Fp::from(x)],
+                    &[Fp::from(x / d), Fp::from(x % d)]
+                ),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn div_rejects_wrong_quotient() {
+        assert!(run(
+            8,
+            Div { d: 7 },
+            &[Fp::from(100)],
+            &[Fp::from(15), Fp::from(2)]
+        )
+        .is_err());
+        assert!(run(
+            8,
+            Div { d: 7 },
+            &[Fp::from(100)],
+            &[Fp::from(13), Fp::from(9)]
+        )
+        .is_err());
+    }
+}
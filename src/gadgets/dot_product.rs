@@ -0,0 +1,189 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// 内积：out = Σ a_i * b_i
+// 和 MaskedSumChip 一样是一个running sum，只是 a 不需要是boolean
+//
+//   a   |  b  | acc                    | q_first | q_step
+//  a_0  | b_0 | a_0 * b_0              |    1    |   0
+//  a_1  | b_1 | acc_prev + a_1 * b_1   |    0    |   1
+//
+#[derive(Debug, Clone)]
+pub struct DotProductConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_first: Selector,
+    pub q_step: Selector,
+}
+
+pub struct DotProductChip<F: FieldExt> {
+    config: DotProductConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DotProductChip<F> {
+    pub fn construct(config: DotProductConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> DotProductConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("dot product first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![q_first * (acc - a * b)]
+        });
+
+        meta.create_gate("dot product step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[2], Rotation::prev());
+
+            vec![q_step * (acc - (acc_prev + a * b))]
+        });
+
+        DotProductConfig {
+            advice,
+            q_first,
+            q_step,
+        }
+    }
+
+    // a 和 b 长度必须一样，而且不能是空的
+    pub fn dot_product(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[ACell<F>],
+        b: &[ACell<F>],
+    ) -> Result<ACell<F>, Error> {
+        if a.len() != b.len() || a.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "dot product",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+                let mut acc = None;
+
+                for (row, (a, b)) in a.iter().zip(b.iter()).enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    a.0.copy_advice(|| "a", &mut region, self.config.advice[0], row)?;
+                    b.0.copy_advice(|| "b", &mut region, self.config.advice[1], row)?;
+
+                    acc_val = acc_val
+                        .zip(a.0.value())
+                        .zip(b.0.value())
+                        .map(|((acc, a), b)| acc + *a * *b);
+                    acc = Some(
+                        region
+                            .assign_advice(
+                                || "acc",
+                                self.config.advice[2],
+                                row,
+                                || acc_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?,
+                    );
+                }
+
+                acc.ok_or(Error::Synthesis)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入的前一半是 a，后一半是 b
+    #[derive(Clone, Default)]
+    struct DotProduct;
+
+    impl TestGadget<Fp> for DotProduct {
+        type Config = DotProductConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DotProductConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            DotProductChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: DotProductConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (a, b) = inputs.split_at(inputs.len() / 2);
+            let out = DotProductChip::construct(config).dot_product(layouter, a, b)?;
+            Ok(vec![out])
+        }
+    }
+
+    fn inputs(a: &[u64], b: &[u64]) -> Vec<Fp> {
+        a.iter().chain(b).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn dot_product_matches_native() {
+        for (a, b) in [
+            (vec![7u64], vec![6u64]),
+            (vec![1, 2, 3], vec![4, 5, 6]),
+            (vec![0, 0, 9], vec![100, 200, 0]),
+        ] {
+            let expected: u64 = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+            assert_eq!(
+                run(5, DotProduct, &inputs(&a, &b), &[Fp::from(expected)]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn dot_product_rejects_wrong_output() {
+        assert!(run(
+            5,
+            DotProduct,
+            &inputs(&[1, 2, 3], &[4, 5, 6]),
+            &[Fp::from(31)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn dot_product_rejects_empty() {
+        assert!(synthesis_fails(5, DotProduct, &[], &[Fp::zero()]));
+    }
+}
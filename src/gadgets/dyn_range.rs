@@ -0,0 +1,198 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+use crate::ACell;
+
+// 运行时选择range check的位数：flag = 1 的时候检查 x < 2^8，flag = 0 的时候检查 x < 2^16
+// 两张lookup table，一张 8-bit 一张 16-bit，用flag去"mux"到底查哪一张：
+//   q * flag * x       在 8-bit table 里面
+//   q * (1 - flag) * x 在 16-bit table 里面
+// 没被选中的那一边查的是 0，两张table里面都有 0
+// 注意：16-bit table 有 2^16 行，电路至少需要 k = 17
+//
+//  x | flag | q_lookup
+//
+#[derive(Debug, Clone)]
+pub struct DynRangeConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_lookup: Selector,
+    pub table_8: TableColumn,
+    pub table_16: TableColumn,
+}
+
+pub struct DynRangeChip<F: FieldExt> {
+    config: DynRangeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DynRangeChip<F> {
+    pub fn construct(config: DynRangeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> DynRangeConfig {
+        let q_lookup = meta.complex_selector();
+        let table_8 = meta.lookup_table_column();
+        let table_16 = meta.lookup_table_column();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        meta.create_gate("flag is boolean", |meta| {
+            let q = meta.query_selector(q_lookup);
+            let flag = meta.query_advice(advice[1], Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![q * flag.clone() * (one - flag)]
+        });
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let flag = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![(q * flag * x, table_8)]
+        });
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let flag = meta.query_advice(advice[1], Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![(q * (one - flag) * x, table_16)]
+        });
+
+        DynRangeConfig {
+            advice,
+            q_lookup,
+            table_8,
+            table_16,
+        }
+    }
+
+    pub fn load(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "8-bit range table",
+            |mut table| {
+                for value in 0..(1u64 << 8) {
+                    table.assign_cell(
+                        || "value",
+                        self.config.table_8,
+                        value as usize,
+                        || Ok(F::from(value)),
+                    )?;
+                }
+                Ok(())
+            },
+        )?;
+
+        layouter.assign_table(
+            || "16-bit range table",
+            |mut table| {
+                for value in 0..(1u64 << 16) {
+                    table.assign_cell(
+                        || "value",
+                        self.config.table_16,
+                        value as usize,
+                        || Ok(F::from(value)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn assert_in_dyn_range(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        flag: &Boolean<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "dyn range",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                x.0.copy_advice(|| "x", &mut region, self.config.advice[0], 0)?;
+                let flag = &flag.0;
+                flag.0
+                    .copy_advice(|| "flag", &mut region, self.config.advice[1], 0)?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 (x, flag)
+    #[derive(Clone, Default)]
+    struct DynRange;
+
+    impl TestGadget<Fp> for DynRange {
+        type Config = DynRangeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DynRangeConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            DynRangeChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: DynRangeConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = DynRangeChip::construct(config);
+            chip.load(layouter.namespace(|| "tables"))?;
+            for pair in inputs.chunks(2) {
+                chip.assert_in_dyn_range(
+                    layouter.namespace(|| "dyn range"),
+                    &pair[0],
+                    &Boolean(pair[1].clone()),
+                )?;
+            }
+            Ok(vec![])
+        }
+    }
+
+    fn check(x: u64, flag: u64) -> bool {
+        run(17, DynRange, &[Fp::from(x), Fp::from(flag)], &[]).is_ok()
+    }
+
+    #[test]
+    fn dyn_range_accepts_values_in_selected_range() {
+        let inputs: Vec<Fp> = [(0u64, 1u64), (255, 1), (0, 0), (256, 0), (65535, 0)]
+            .iter()
+            .flat_map(|(x, f)| [Fp::from(*x), Fp::from(*f)])
+            .collect();
+        assert_eq!(run(17, DynRange, &inputs, &[]), Ok(()));
+    }
+
+    #[test]
+    fn dyn_range_rejects_values_outside_selected_range() {
+        assert!(!check(256, 1));
+        assert!(!check(65536, 0));
+    }
+
+    #[test]
+    fn dyn_range_rejects_non_boolean_flag() {
+        // flag = 2 的时候两边查的都是 2x 和 -x
+        assert!(!check(1, 2));
+    }
+}
@@ -0,0 +1,205 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    fixed_mul::{FixedMulChip, FixedMulConfig},
+};
+use crate::ACell;
+
+// 定点数的指数移动平均：ema_t = alpha * value_t + (1 - alpha) * ema_{t-1}，alpha = alpha_num / alpha_den
+// FixedMulChip 只能除以 2^scale，所以 alpha_den 必须是 2 的幂（scale = log2(alpha_den)）
+// 两个乘积分别向下取整再相加：
+//   ema_t = floor(alpha_num * value_t / den) + floor((den - alpha_num) * ema_{t-1} / den)
+// 和 FiboChip 一样一行接一行地往下传，ema_0 = value_0
+#[derive(Debug, Clone)]
+pub struct EmaConfig {
+    pub constant: ConstantConfig,
+    pub fixed_mul: FixedMulConfig,
+    pub add: AddConfig,
+}
+
+pub struct EmaChip<F: FieldExt> {
+    config: EmaConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> EmaChip<F> {
+    pub fn construct(config: EmaConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> EmaConfig {
+        EmaConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            fixed_mul: FixedMulChip::configure(meta, advice, fixed),
+            add: AddChip::configure(meta, advice),
+        }
+    }
+
+    pub fn ema(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+        alpha_num: u64,
+        alpha_den: u64,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        if values.is_empty() || !alpha_den.is_power_of_two() || alpha_num > alpha_den {
+            return Err(Error::Synthesis);
+        }
+        let scale = alpha_den.trailing_zeros() as usize;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let fixed_mul = FixedMulChip::construct(self.config.fixed_mul.clone());
+        let add = AddChip::construct(self.config.add.clone());
+
+        let alpha = constant.load_constant(layouter.namespace(|| "alpha"), F::from(alpha_num))?;
+        let one_minus_alpha = constant.load_constant(
+            layouter.namespace(|| "1 - alpha"),
+            F::from(alpha_den - alpha_num),
+        )?;
+
+        let mut out = Vec::with_capacity(values.len());
+        out.push(values[0].clone());
+        for (t, value) in values.iter().enumerate().skip(1) {
+            let (new_part, _) = fixed_mul.fixed_mul(
+                layouter.namespace(|| format!("alpha * value_{}", t)),
+                value,
+                &alpha,
+                scale,
+            )?;
+            let (old_part, _) = fixed_mul.fixed_mul(
+                layouter.namespace(|| format!("(1 - alpha) * ema_{}", t - 1)),
+                &out[t - 1],
+                &one_minus_alpha,
+                scale,
+            )?;
+            let ema = add.add(
+                layouter.namespace(|| format!("ema_{}", t)),
+                &new_part,
+                &old_part,
+            )?;
+            out.push(ema);
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Ema {
+        alpha_num: u64,
+        alpha_den: u64,
+    }
+
+    impl TestGadget<Fp> for Ema {
+        type Config = EmaConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> EmaConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            EmaChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: EmaConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            EmaChip::construct(config).ema(layouter, inputs, self.alpha_num, self.alpha_den)
+        }
+    }
+
+    // 电路外面的定点数 EMA，两个乘积分别向下取整
+    fn ema(values: &[u64], alpha_num: u64, alpha_den: u64) -> Vec<u64> {
+        let mut out = vec![values[0]];
+        for value in &values[1..] {
+            let prev = *out.last().unwrap();
+            out.push(alpha_num * value / alpha_den + (alpha_den - alpha_num) * prev / alpha_den);
+        }
+        out
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn ema_matches_native() {
+        let values = [1000u64, 1200, 900, 900, 5000, 0, 777];
+        // alpha = 1/4, 3/8, 0（一直是 value_0），1（就是 values 本身）
+        for (alpha_num, alpha_den) in [(1u64, 4u64), (3, 8), (0, 16), (16, 16)] {
+            let g = Ema {
+                alpha_num,
+                alpha_den,
+            };
+            assert_eq!(
+                run(
+                    10,
+                    g,
+                    &fp(&values),
+                    &fp(&ema(&values, alpha_num, alpha_den))
+                ),
+                Ok(()),
+                "alpha = {}/{}",
+                alpha_num,
+                alpha_den
+            );
+        }
+    }
+
+    #[test]
+    fn ema_first_value_seeds_average() {
+        let g = Ema {
+            alpha_num: 1,
+            alpha_den: 2,
+        };
+        assert_eq!(run(8, g.clone(), &fp(&[42]), &fp(&[42])), Ok(()));
+        assert!(run(8, g, &fp(&[42]), &fp(&[21])).is_err());
+    }
+
+    #[test]
+    fn ema_rejects_wrong_average() {
+        let g = Ema {
+            alpha_num: 1,
+            alpha_den: 4,
+        };
+        // 用精确的有理数算出来再取整：0.25 * 1003 + 0.75 * 1001 = 1001.5，而逐项取整之后是 250 + 750 = 1000
+        assert!(run(10, g, &fp(&[1001, 1003]), &fp(&[1001, 1001])).is_err());
+    }
+
+    #[test]
+    fn ema_rejects_bad_alpha() {
+        let g = Ema {
+            alpha_num: 1,
+            alpha_den: 3,
+        };
+        assert!(synthesis_fails(8, g, &fp(&[1]), &fp(&[1])));
+        let g = Ema {
+            alpha_num: 5,
+            alpha_den: 4,
+        };
+        assert!(synthesis_fails(8, g, &fp(&[1]), &fp(&[1])));
+    }
+}
@@ -0,0 +1,171 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    mux::{MuxChip, MuxConfig},
+};
+use crate::ACell;
+
+// n!，n 是witness，但是最多只能到 max_n（电路的形状由 max_n 决定）
+// 从 acc = 1 开始，对 k = 1..=max_n：
+//   acc = (k <= n) ? acc * k : acc
+// 0! = 1! = 1 都是自然成立的。另外还要断言 n <= max_n，否则结果会停在 max_n!
+// LessThanOrEqualChip 假设输入都 < 2^bits，所以 n 要先range check到 bits 位，
+// 不然 n = p - 1 这种 "负数" 会让所有的 k <= n 都不成立，证明出 (-1)! = 1
+#[derive(Debug, Clone)]
+pub struct FactorialConfig {
+    pub constant: ConstantConfig,
+    pub decompose: DecomposeConfig,
+    pub mul_const: MulConstConfig,
+    pub less_than_or_equal: LessThanOrEqualConfig,
+    pub mux: MuxConfig,
+}
+
+pub struct FactorialChip<F: FieldExt> {
+    config: FactorialConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FactorialChip<F> {
+    pub fn construct(config: FactorialConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> FactorialConfig {
+        FactorialConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+            mux: MuxChip::configure(meta, advice),
+        }
+    }
+
+    pub fn factorial(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: &ACell<F>,
+        max_n: usize,
+    ) -> Result<ACell<F>, Error> {
+        // n 和 k 都 <= max_n < 2^bits
+        let bits = ((usize::BITS - max_n.leading_zeros()) as usize).max(1);
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let mux = MuxChip::construct(self.config.mux.clone());
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check n"), n, bits)?;
+
+        let max_n_cell =
+            constant.load_constant(layouter.namespace(|| "max_n"), F::from(max_n as u64))?;
+        less_than_or_equal.assert_less_than_or_equal(
+            layouter.namespace(|| "n <= max_n"),
+            n,
+            &max_n_cell,
+            bits,
+        )?;
+
+        let mut acc = constant.load_constant(layouter.namespace(|| "0!"), F::one())?;
+        for k in 1..=max_n {
+            let k_val = F::from(k as u64);
+            let k_cell =
+                constant.load_constant(layouter.namespace(|| format!("k = {}", k)), k_val)?;
+            let active = less_than_or_equal.less_than_or_equal(
+                layouter.namespace(|| format!("{} <= n", k)),
+                &k_cell,
+                n,
+                bits,
+            )?;
+            let product =
+                mul_const.mul_const(layouter.namespace(|| format!("acc * {}", k)), &acc, k_val)?;
+            acc = mux.mux(
+                layouter.namespace(|| format!("step {}", k)),
+                &active,
+                &product,
+                &acc,
+            )?;
+        }
+
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Factorial;
+
+    impl TestGadget<Fp> for Factorial {
+        type Config = FactorialConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FactorialConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            FactorialChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: FactorialConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                FactorialChip::construct(config).factorial(layouter, &inputs[0], 6)?
+            ])
+        }
+    }
+
+    #[test]
+    fn factorial_matches_native() {
+        for n in 0..=6u64 {
+            let expected: u64 = (1..=n).product();
+            assert_eq!(
+                run(7, Factorial, &[Fp::from(n)], &[Fp::from(expected)]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn factorial_rejects_wrong_result() {
+        assert!(run(7, Factorial, &[Fp::from(4)], &[Fp::from(25)]).is_err());
+        assert!(run(7, Factorial, &[Fp::from(4)], &[Fp::from(120)]).is_err());
+    }
+
+    #[test]
+    fn factorial_rejects_n_above_max() {
+        // 结果会停在 6! = 720
+        assert!(run(7, Factorial, &[Fp::from(7)], &[Fp::from(720)]).is_err());
+    }
+
+    #[test]
+    fn factorial_rejects_negative_n() {
+        // n = p - 1：没有range check的时候所有 k <= n 都是 false，会证明出 (-1)! = 1
+        assert!(run(7, Factorial, &[-Fp::one()], &[Fp::one()]).is_err());
+    }
+}
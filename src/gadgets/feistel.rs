@@ -0,0 +1,178 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::byte_xor::{ByteXorChip, ByteXorConfig};
+use crate::ACell;
+
+// Feistel 网络的一轮：(L', R') = (R, L ^ F(R, key))，L, R, key 都是byte
+// 轮函数直接用 xor table：F(R, key) = R ^ key，两次 XOR 都走 ByteXorChip 的lookup，
+// 所以 L, R, key 也都被约束成了byte
+// L' 就是 R 本身，不需要新的cell
+// 解密：把 (L', R') 交换一下再跑一轮同样的 key，结果交换回来就是 (L, R)
+//   round(R', L') = (L', R' ^ L' ^ key) = (R, L)
+// 注意：这个轮函数是线性的，只是用来演示Feistel的结构，不要拿去做真正需要安全性的东西
+// 用之前要先调用 load 把xor table放进电路
+#[derive(Debug, Clone)]
+pub struct FeistelRoundConfig {
+    pub byte_xor: ByteXorConfig,
+}
+
+pub struct FeistelRoundChip<F: FieldExt> {
+    config: FeistelRoundConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FeistelRoundChip<F> {
+    pub fn construct(config: FeistelRoundConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> FeistelRoundConfig {
+        FeistelRoundConfig {
+            byte_xor: ByteXorChip::configure(meta, advice),
+        }
+    }
+
+    pub fn load(&self, layouter: impl Layouter<F>) -> Result<(), Error> {
+        ByteXorChip::construct(self.config.byte_xor.clone()).load(layouter)
+    }
+
+    pub fn round(
+        &self,
+        mut layouter: impl Layouter<F>,
+        l: &ACell<F>,
+        r: &ACell<F>,
+        key: &ACell<F>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let byte_xor = ByteXorChip::construct(self.config.byte_xor.clone());
+        let f = byte_xor.xor(layouter.namespace(|| "F(R, key)"), r, key)?;
+        let new_r = byte_xor.xor(layouter.namespace(|| "L ^ F(R, key)"), l, &f)?;
+
+        Ok((r.clone(), new_r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [L, R, key_0, key_1, ...]，每个key跑一轮
+    // decrypt = false 的时候输出加密之后的 (L, R)
+    // decrypt = true 的时候再交换一下、用倒序的key解密、最后交换回来，输出应该就是原来的 (L, R)
+    #[derive(Clone, Default)]
+    struct Feistel {
+        decrypt: bool,
+    }
+
+    impl TestGadget<Fp> for Feistel {
+        type Config = FeistelRoundConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FeistelRoundConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            FeistelRoundChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: FeistelRoundConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = FeistelRoundChip::construct(config);
+            chip.load(layouter.namespace(|| "xor table"))?;
+
+            let keys = &inputs[2..];
+            let (mut l, mut r) = (inputs[0].clone(), inputs[1].clone());
+            for (i, key) in keys.iter().enumerate() {
+                (l, r) = chip.round(layouter.namespace(|| format!("round {}", i)), &l, &r, key)?;
+            }
+            if self.decrypt {
+                (l, r) = (r, l);
+                for (i, key) in keys.iter().enumerate().rev() {
+                    (l, r) =
+                        chip.round(layouter.namespace(|| format!("inverse {}", i)), &l, &r, key)?;
+                }
+                (l, r) = (r, l);
+            }
+            Ok(vec![l, r])
+        }
+    }
+
+    fn native_round(l: u64, r: u64, key: u64) -> (u64, u64) {
+        (r, l ^ (r ^ key))
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn feistel_round_matches_native() {
+        let (l, r, keys) = (0x3c, 0xa5, [0x0f, 0xff, 0x00, 0x96]);
+        let (l_out, r_out) = keys
+            .iter()
+            .fold((l, r), |(l, r), &key| native_round(l, r, key));
+
+        let inputs: Vec<_> = [l, r].iter().chain(&keys).copied().collect();
+        assert_eq!(
+            run(
+                17,
+                Feistel { decrypt: false },
+                &fp(&inputs),
+                &fp(&[l_out, r_out])
+            ),
+            Ok(())
+        );
+        // 单独一轮：L' = R
+        assert_eq!(native_round(l, r, keys[0]), (0xa5, 0x3c ^ 0xa5 ^ 0x0f));
+    }
+
+    #[test]
+    fn feistel_swapped_rounds_decrypt() {
+        let inputs = [0x12, 0xfe, 0x34, 0x56, 0x78];
+        assert_eq!(
+            run(
+                17,
+                Feistel { decrypt: true },
+                &fp(&inputs),
+                &fp(&inputs[..2])
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn feistel_rejects_wrong_output_and_non_bytes() {
+        // 只跑一轮，不把 L 和 R 交换
+        let (l, r, key) = (0x12, 0xfe, 0x34);
+        assert!(run(
+            17,
+            Feistel { decrypt: false },
+            &fp(&[l, r, key]),
+            &fp(&[l ^ r ^ key, r])
+        )
+        .is_err());
+        // key 不是byte
+        assert!(run(
+            17,
+            Feistel { decrypt: false },
+            &fp(&[l, r, 0x100 + key]),
+            &fp(&[r, l ^ r ^ key])
+        )
+        .is_err());
+    }
+}
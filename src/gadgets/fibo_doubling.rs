@@ -0,0 +1,174 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    mul::{MulChip, MulConfig},
+    sub::{SubChip, SubConfig},
+};
+use crate::ACell;
+
+// Fibonacci 的 fast doubling（由 Q-matrix 的平方 Q^2k = (Q^k)^2 推出来的）：
+//   f(2k)     = f(k) * (2 * f(k + 1) - f(k))
+//   f(2k + 1) = f(k)^2 + f(k + 1)^2
+// 一次 double_step 就是从 (f(k), f(k + 1)) 走到 (f(2k), f(2k + 1))
+// 从 (f(0), f(1)) = (0, 1) 开始的话，k = 0 这一步还是 (0, 1)
+#[derive(Debug, Clone)]
+pub struct FiboDoublingConfig {
+    pub add: AddConfig,
+    pub sub: SubConfig,
+    pub mul: MulConfig,
+}
+
+pub struct FiboDoublingChip<F: FieldExt> {
+    config: FiboDoublingConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboDoublingChip<F> {
+    pub fn construct(config: FiboDoublingConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> FiboDoublingConfig {
+        FiboDoublingConfig {
+            add: AddChip::configure(meta, advice),
+            sub: SubChip::configure(meta, advice),
+            mul: MulChip::configure(meta, advice),
+        }
+    }
+
+    // 返回 (f(2k), f(2k + 1))
+    pub fn double_step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        fk: &ACell<F>,
+        fk1: &ACell<F>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let add = AddChip::construct(self.config.add.clone());
+        let sub = SubChip::construct(self.config.sub.clone());
+        let mul = MulChip::construct(self.config.mul.clone());
+
+        let twice = add.add(layouter.namespace(|| "2 * f(k + 1)"), fk1, fk1)?;
+        let diff = sub.sub(layouter.namespace(|| "2 * f(k + 1) - f(k)"), &twice, fk)?;
+        let f2k = mul.mul(layouter.namespace(|| "f(2k)"), fk, &diff)?;
+
+        let fk_sq = mul.mul(layouter.namespace(|| "f(k)^2"), fk, fk)?;
+        let fk1_sq = mul.mul(layouter.namespace(|| "f(k + 1)^2"), fk1, fk1)?;
+        let f2k1 = add.add(layouter.namespace(|| "f(2k + 1)"), &fk_sq, &fk1_sq)?;
+
+        Ok((f2k, f2k1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    use super::*;
+    use crate::{
+        gadgets::testing::{run, TestGadget},
+        util::fib_sequence,
+        MyCircuit,
+    };
+
+    // 输入是 [f(k), f(k + 1)]，连续做 steps 次 double_step
+    #[derive(Clone, Default)]
+    struct FiboDoubling {
+        steps: usize,
+    }
+
+    impl TestGadget<Fp> for FiboDoubling {
+        type Config = FiboDoublingConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FiboDoublingConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            FiboDoublingChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: FiboDoublingConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = FiboDoublingChip::construct(config);
+            let (mut fk, mut fk1) = (inputs[0].clone(), inputs[1].clone());
+            for _ in 0..self.steps {
+                (fk, fk1) = chip.double_step(layouter.namespace(|| "double"), &fk, &fk1)?;
+            }
+            Ok(vec![fk, fk1])
+        }
+    }
+
+    #[test]
+    fn double_step_matches_iterative_fibo_chip() {
+        for k in 0..=5 {
+            // 用 FiboChip 一项一项证明到 f(2k + 1)（MyCircuit 至少有 3 项），public input 就是这个数列
+            let n = (2 * k + 2).max(3);
+            let fib = fib_sequence(Fp::zero(), Fp::one(), n);
+            let circuit = MyCircuit::with_witness(Fp::zero(), Fp::one(), n);
+            let prover = MockProver::run(5, &circuit, vec![fib.clone()]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+
+            // k = 0 是 base case：(f(0), f(1)) 还是走到 (f(0), f(1))
+            assert_eq!(
+                run(
+                    4,
+                    FiboDoubling { steps: 1 },
+                    &[fib[k], fib[k + 1]],
+                    &[fib[2 * k], fib[2 * k + 1]]
+                ),
+                Ok(()),
+                "k = {}",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_doubling_matches_native() {
+        // (f(1), f(2)) -> (f(2), f(3)) -> (f(4), f(5)) -> (f(8), f(9)) -> (f(16), f(17))
+        let fib = fib_sequence(Fp::zero(), Fp::one(), 18);
+        assert_eq!(
+            run(
+                6,
+                FiboDoubling { steps: 4 },
+                &[fib[1], fib[2]],
+                &[fib[16], fib[17]]
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn double_step_rejects_wrong_output() {
+        let fib = fib_sequence(Fp::zero(), Fp::one(), 12);
+        // (f(2k), f(2k + 1)) 换了位置，或者是 (f(k + 1), f(k + 2))
+        assert!(run(
+            4,
+            FiboDoubling { steps: 1 },
+            &[fib[5], fib[6]],
+            &[fib[11], fib[10]]
+        )
+        .is_err());
+        assert!(run(
+            4,
+            FiboDoubling { steps: 1 },
+            &[fib[5], fib[6]],
+            &[fib[6], fib[7]]
+        )
+        .is_err());
+    }
+}
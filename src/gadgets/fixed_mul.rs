@@ -0,0 +1,153 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    div::{DivChip, DivConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 定点数乘法：(a * b) >> scale
+// 先用 MulChip 算 a * b，再用 DivChip 除以 2^scale，返回 (商, 余数)
+// scale = 0 的时候就是普通乘法，余数是 0
+// 注意：scale 必须 < 64，而且 (a * b) >> scale 需要在 DivChip 的 QUOTIENT_BITS 以内
+#[derive(Debug, Clone)]
+pub struct FixedMulConfig {
+    pub mul: MulConfig,
+    pub div: DivConfig,
+}
+
+pub struct FixedMulChip<F: FieldExt> {
+    config: FixedMulConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FixedMulChip<F> {
+    pub fn construct(config: FixedMulConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> FixedMulConfig {
+        FixedMulConfig {
+            mul: MulChip::configure(meta, advice),
+            div: DivChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn fixed_mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        scale: usize,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        if scale >= 64 {
+            return Err(Error::Synthesis);
+        }
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let product = mul.mul(layouter.namespace(|| "a * b"), a, b)?;
+
+        let div = DivChip::construct(self.config.div.clone());
+        div.div_rem(layouter.namespace(|| ">> scale"), &product, 1 << scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct FixedMul {
+        scale: usize,
+    }
+
+    impl TestGadget<Fp> for FixedMul {
+        type Config = FixedMulConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FixedMulConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            FixedMulChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: FixedMulConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (q, r) = FixedMulChip::construct(config)
+                .fixed_mul(layouter, &inputs[0], &inputs[1], self.scale)?;
+            Ok(vec![q, r])
+        }
+    }
+
+    // 电路外面的定点数乘法：(a * b) >> scale，余数是被移掉的低位
+    fn fixed_mul(a: u64, b: u64, scale: usize) -> [Fp; 2] {
+        let product = a as u128 * b as u128;
+        [
+            Fp::from_u128(product >> scale),
+            Fp::from_u128(product & ((1 << scale) - 1)),
+        ]
+    }
+
+    #[test]
+    fn fixed_mul_matches_native() {
+        for (a, b, scale) in [
+            // Q8：1.5 * 2.25 = 3.375
+            (384u64, 576u64, 8usize),
+            // 会被截断的低位
+            (385, 577, 8),
+            (0, 12345, 8),
+            (1 << 16, 1 << 16, 16),
+            (123_456_789, 987_654_321, 32),
+            // scale = 0 就是普通乘法
+            (7, 9, 0),
+        ] {
+            assert_eq!(
+                run(
+                    8,
+                    FixedMul { scale },
+                    &[Fp::from(a), Fp::from(b)],
+                    &fixed_mul(a, b, scale)
+                ),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_mul_rejects_wrong_result() {
+        let [q, r] = fixed_mul(385, 577, 8);
+        let g = FixedMul { scale: 8 };
+        let inputs = [Fp::from(385), Fp::from(577)];
+        assert!(run(8, g.clone(), &inputs, &[q + Fp::one(), r]).is_err());
+        assert!(run(8, g, &inputs, &[q, r + Fp::one()]).is_err());
+    }
+
+    #[test]
+    fn fixed_mul_rejects_scale_too_large() {
+        assert!(synthesis_fails(
+            8,
+            FixedMul { scale: 64 },
+            &[Fp::one(), Fp::one()],
+            &[]
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    base_b::{BaseBChip, BaseBConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+};
+use crate::ACell;
+
+// 二维下标转一维：flat = row * width + col
+// col < width 用 BaseBChip 把 col 拆成 1 个 width 进制的digit 来保证
+// （width == 1 的时候 BaseBChip 用不了，直接约束 col == 0）
+// row 不做检查，行数不是这里关心的
+#[derive(Debug, Clone)]
+pub struct FlattenIndexConfig {
+    pub base_b: BaseBConfig,
+    pub mul_const: MulConstConfig,
+    pub add: AddConfig,
+}
+
+pub struct FlattenIndexChip<F: FieldExt> {
+    config: FlattenIndexConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FlattenIndexChip<F> {
+    pub fn construct(config: FlattenIndexConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> FlattenIndexConfig {
+        meta.enable_constant(fixed);
+
+        FlattenIndexConfig {
+            base_b: BaseBChip::configure(meta, advice, fixed),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            add: AddChip::configure(meta, advice),
+        }
+    }
+
+    pub fn flatten(
+        &self,
+        mut layouter: impl Layouter<F>,
+        row: &ACell<F>,
+        col: &ACell<F>,
+        width: u64,
+    ) -> Result<ACell<F>, Error> {
+        match width {
+            0 => return Err(Error::Synthesis),
+            1 => layouter.assign_region(
+                || "col == 0",
+                |mut region| region.constrain_constant(col.0.cell(), F::zero()),
+            )?,
+            _ => {
+                let base_b = BaseBChip::construct(self.config.base_b.clone());
+                base_b.decompose(layouter.namespace(|| "col < width"), col, width, 1)?;
+            }
+        }
+
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let offset =
+            mul_const.mul_const(layouter.namespace(|| "row * width"), row, F::from(width))?;
+
+        let add = AddChip::construct(self.config.add.clone());
+        add.add(layouter.namespace(|| "row * width + col"), &offset, col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [row, col]
+    #[derive(Clone, Default)]
+    struct Flatten {
+        width: u64,
+    }
+
+    impl TestGadget<Fp> for Flatten {
+        type Config = FlattenIndexConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FlattenIndexConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            FlattenIndexChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: FlattenIndexConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let flat = FlattenIndexChip::construct(config)
+                .flatten(layouter, &inputs[0], &inputs[1], self.width)?;
+            Ok(vec![flat])
+        }
+    }
+
+    fn inputs(row: u64, col: u64) -> Vec<Fp> {
+        vec![Fp::from(row), Fp::from(col)]
+    }
+
+    #[test]
+    fn flatten_matches_native() {
+        for width in [1u64, 3, 10] {
+            for row in [0u64, 1, 7] {
+                for col in [0, width / 2, width - 1] {
+                    assert_eq!(
+                        run(
+                            6,
+                            Flatten { width },
+                            &inputs(row, col),
+                            &[Fp::from(row * width + col)]
+                        ),
+                        Ok(()),
+                        "({}, {}) with width {}",
+                        row,
+                        col,
+                        width
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flatten_rejects_out_of_bounds_column() {
+        // (1, 3) 和 (2, 0) 算出来都是 6，但是 col == width 不合法
+        assert!(run(6, Flatten { width: 3 }, &inputs(1, 3), &[Fp::from(6)]).is_err());
+        assert!(run(6, Flatten { width: 1 }, &inputs(0, 1), &[Fp::from(1)]).is_err());
+    }
+
+    #[test]
+    fn flatten_rejects_wrong_index() {
+        // col * width + row
+        assert!(run(6, Flatten { width: 10 }, &inputs(2, 3), &[Fp::from(32)]).is_err());
+        assert!(synthesis_fails(
+            6,
+            Flatten { width: 0 },
+            &inputs(0, 0),
+            &[Fp::zero()]
+        ));
+    }
+}
@@ -0,0 +1,300 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::to_u128;
+use crate::ACell;
+
+// GF(2^8) 上的乘法（AES 用的域），模多项式是 x^8 + x^4 + x^3 + x + 1 (0x11b)
+// 和 ByteXorChip 一样用一张 (a, b, a * b) 的lookup table，一共 2^16 行，电路至少需要 k = 17
+// 顺便也保证了 a 和 b 都是byte
+//
+//  a | b | c | q_lookup
+//
+#[derive(Debug, Clone)]
+pub struct Gf256MulConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_lookup: Selector,
+    pub table_a: TableColumn,
+    pub table_b: TableColumn,
+    pub table_c: TableColumn,
+}
+
+pub struct Gf256MulChip<F: FieldExt> {
+    config: Gf256MulConfig,
+    _marker: PhantomData<F>,
+}
+
+// 电路外面的参考实现：shift-and-add，每次 a 乘 x 的时候溢出了就异或 0x1b（xtime）
+pub fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut out = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            out ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    out
+}
+
+impl<F: FieldExt> Gf256MulChip<F> {
+    pub fn construct(config: Gf256MulConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> Gf256MulConfig {
+        let q_lookup = meta.complex_selector();
+        let table_a = meta.lookup_table_column();
+        let table_b = meta.lookup_table_column();
+        let table_c = meta.lookup_table_column();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![
+                (q.clone() * a, table_a),
+                (q.clone() * b, table_b),
+                (q * c, table_c),
+            ]
+        });
+
+        Gf256MulConfig {
+            advice,
+            q_lookup,
+            table_a,
+            table_b,
+            table_c,
+        }
+    }
+
+    pub fn load(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "gf256 mul table",
+            |mut table| {
+                let mut offset = 0;
+                for a in 0..=255u8 {
+                    for b in 0..=255u8 {
+                        table.assign_cell(
+                            || "a",
+                            self.config.table_a,
+                            offset,
+                            || Ok(F::from(a as u64)),
+                        )?;
+                        table.assign_cell(
+                            || "b",
+                            self.config.table_b,
+                            offset,
+                            || Ok(F::from(b as u64)),
+                        )?;
+                        table.assign_cell(
+                            || "a * b",
+                            self.config.table_c,
+                            offset,
+                            || Ok(F::from(gf_mul(a, b) as u64)),
+                        )?;
+                        offset += 1;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn gf_mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "gf256 mul",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                // 不是byte的输入lookup反正也过不了，这里直接报错
+                let c_val = a.0.value().zip(b.0.value()).and_then(|(a, b)| {
+                    let a = u8::try_from(to_u128(a)).ok()?;
+                    let b = u8::try_from(to_u128(b)).ok()?;
+                    Some(F::from(gf_mul(a, b) as u64))
+                });
+
+                region
+                    .assign_advice(
+                        || "a * b",
+                        self.config.advice[2],
+                        0,
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [a_0, b_0, a_1, b_1, ...]，输出每一对的 a_i * b_i
+    #[derive(Clone, Default)]
+    struct Gf256Mul;
+
+    impl TestGadget<Fp> for Gf256Mul {
+        type Config = Gf256MulConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Gf256MulConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            Gf256MulChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: Gf256MulConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = Gf256MulChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+            inputs
+                .chunks(2)
+                .map(|pair| chip.gf_mul(layouter.namespace(|| "a * b"), &pair[0], &pair[1]))
+                .collect()
+        }
+    }
+
+    // 绕过witness生成，给 a * b 填一个错的结果
+    #[derive(Clone, Default)]
+    struct ForgedGf256Mul;
+
+    impl TestGadget<Fp> for ForgedGf256Mul {
+        type Config = Gf256MulConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Gf256MulConfig {
+            Gf256Mul::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Gf256MulConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Gf256MulChip::construct(config.clone()).load(layouter.namespace(|| "table"))?;
+            let c = layouter.assign_region(
+                || "forged gf256 mul",
+                |mut region| {
+                    config.q_lookup.enable(&mut region, 0)?;
+                    inputs[0]
+                        .0
+                        .copy_advice(|| "a", &mut region, config.advice[0], 0)?;
+                    inputs[1]
+                        .0
+                        .copy_advice(|| "b", &mut region, config.advice[1], 0)?;
+                    region
+                        .assign_advice(|| "a * b", config.advice[2], 0, || Ok(Fp::from(0xae)))
+                        .map(ACell)
+                },
+            )?;
+            Ok(vec![c])
+        }
+    }
+
+    // 另一种参考实现：先做无进位乘法得到 15 位的多项式，再对 0x11b 做多项式长除法
+    fn native_gf_mul(a: u8, b: u8) -> u8 {
+        let mut product = 0u16;
+        for i in 0..8 {
+            if (b >> i) & 1 == 1 {
+                product ^= (a as u16) << i;
+            }
+        }
+        for i in (8..15).rev() {
+            if (product >> i) & 1 == 1 {
+                product ^= 0x11b << (i - 8);
+            }
+        }
+        product as u8
+    }
+
+    #[test]
+    fn gf_mul_reference_agrees() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(gf_mul(a, b), native_gf_mul(a, b));
+            }
+        }
+        // FIPS-197 里面的例子
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+    }
+
+    #[test]
+    fn gf_mul_matches_native() {
+        let pairs: [(u8, u8); 8] = [
+            (0x57, 0x83),
+            (0x57, 0x13),
+            // 乘 0 / 乘 1
+            (0xab, 0x00),
+            (0x00, 0xcd),
+            (0xab, 0x01),
+            (0x01, 0xcd),
+            // xtime 的时候需要约化（最高位是 1）
+            (0x80, 0x02),
+            (0xff, 0xff),
+        ];
+        let inputs: Vec<_> = pairs
+            .iter()
+            .flat_map(|(a, b)| [Fp::from(*a as u64), Fp::from(*b as u64)])
+            .collect();
+        let expected: Vec<_> = pairs
+            .iter()
+            .map(|(a, b)| Fp::from(native_gf_mul(*a, *b) as u64))
+            .collect();
+        assert_eq!(run(17, Gf256Mul, &inputs, &expected), Ok(()));
+        assert_eq!(native_gf_mul(0x80, 0x02), 0x1b);
+    }
+
+    #[test]
+    fn gf_mul_rejects_forged_product() {
+        // 0x57 * 0x83 = 0xc1，填的是 0xae
+        let inputs = [Fp::from(0x57), Fp::from(0x83)];
+        assert!(run(17, ForgedGf256Mul, &inputs, &[Fp::from(0xae)]).is_err());
+    }
+
+    #[test]
+    fn gf_mul_rejects_non_byte() {
+        assert!(synthesis_fails(
+            17,
+            Gf256Mul,
+            &[Fp::from(0x100), Fp::one()],
+            &[Fp::zero()]
+        ));
+    }
+}
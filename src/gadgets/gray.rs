@@ -0,0 +1,250 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    bitwise::{BitwiseChip, BitwiseConfig},
+    constant::{ConstantChip, ConstantConfig},
+    div::{DivChip, DivConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// Gray code 的后继：g1 = gray(n) 的时候，g2 = gray(n + 1)
+// binary 转 Gray code 就是 gray(n) = n ^ (n >> 1)，n >> 1 用 DivChip 除以 2 得到
+// 反过来从 g1 求 n 只在witness里面做（b_i = b_{i+1} ^ g_i），电路里面只需要验证 gray(n) == g1
+// n + 1 要 mod 2^bits（DivChip 的余数），所以 2^bits - 1 的后继是 0（Gray code 也是循环的）
+// g1, g2 都需要 < 2^bits，bits <= 63
+#[derive(Debug, Clone)]
+pub struct GrayCodeConfig {
+    pub advice: Column<Advice>,
+    pub constant: ConstantConfig,
+    pub add: AddConfig,
+    pub div: DivConfig,
+    pub bitwise: BitwiseConfig,
+}
+
+pub struct GrayCodeChip<F: FieldExt> {
+    config: GrayCodeConfig,
+    _marker: PhantomData<F>,
+}
+
+// Gray code 转回 binary：n = g ^ (g >> 1) ^ (g >> 2) ^ ...
+fn gray_to_binary(mut g: u128) -> u128 {
+    let mut n = 0;
+    while g != 0 {
+        n ^= g;
+        g >>= 1;
+    }
+    n
+}
+
+impl<F: FieldExt> GrayCodeChip<F> {
+    pub fn construct(config: GrayCodeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> GrayCodeConfig {
+        meta.enable_equality(advice[0]);
+
+        GrayCodeConfig {
+            advice: advice[0],
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            add: AddChip::configure(meta, advice),
+            div: DivChip::configure(meta, advice, fixed),
+            bitwise: BitwiseChip::configure(meta, advice),
+        }
+    }
+
+    // gray(n) = n ^ (n >> 1)，同时也range check了 n < 2^bits
+    fn to_gray(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let div = DivChip::construct(self.config.div.clone());
+        let half = div.div(layouter.namespace(|| "n >> 1"), n, 2)?;
+
+        let bitwise = BitwiseChip::construct(self.config.bitwise.clone());
+        bitwise.xor(layouter.namespace(|| "n ^ (n >> 1)"), n, &half, bits)
+    }
+
+    pub fn assert_gray_successor(
+        &self,
+        mut layouter: impl Layouter<F>,
+        g1: &ACell<F>,
+        g2: &ACell<F>,
+        bits: usize,
+    ) -> Result<(), Error> {
+        if bits == 0 || bits > 63 {
+            return Err(Error::Synthesis);
+        }
+
+        let n = layouter.assign_region(
+            || "witness n",
+            |mut region| {
+                let n_val =
+                    g1.0.value()
+                        .map(|g| F::from_u128(gray_to_binary(to_u128(g))));
+
+                region
+                    .assign_advice(
+                        || "n",
+                        self.config.advice,
+                        0,
+                        || n_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )?;
+
+        let gray_n = self.to_gray(layouter.namespace(|| "gray(n)"), &n, bits)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let one = constant.load_constant(layouter.namespace(|| "one"), F::one())?;
+        let add = AddChip::construct(self.config.add.clone());
+        let n_plus_one = add.add(layouter.namespace(|| "n + 1"), &n, &one)?;
+        let div = DivChip::construct(self.config.div.clone());
+        let (_, next) = div.div_rem(
+            layouter.namespace(|| "(n + 1) mod 2^bits"),
+            &n_plus_one,
+            1 << bits,
+        )?;
+
+        let gray_next = self.to_gray(layouter.namespace(|| "gray(n + 1)"), &next, bits)?;
+
+        layouter.assign_region(
+            || "gray successor",
+            |mut region| {
+                region.constrain_equal(gray_n.0.cell(), g1.0.cell())?;
+                region.constrain_equal(gray_next.0.cell(), g2.0.cell())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [g1, g2]
+    #[derive(Clone, Default)]
+    struct GraySuccessor {
+        bits: usize,
+    }
+
+    impl TestGadget<Fp> for GraySuccessor {
+        type Config = GrayCodeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> GrayCodeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            GrayCodeChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: GrayCodeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            GrayCodeChip::construct(config)
+                .assert_gray_successor(layouter, &inputs[0], &inputs[1], self.bits)?;
+            Ok(vec![])
+        }
+    }
+
+    fn gray(n: u64) -> u64 {
+        n ^ (n >> 1)
+    }
+
+    fn inputs(g1: u64, g2: u64) -> Vec<Fp> {
+        vec![Fp::from(g1), Fp::from(g2)]
+    }
+
+    #[test]
+    fn gray_to_binary_inverts_gray() {
+        for n in 0..256u64 {
+            assert_eq!(gray_to_binary(gray(n) as u128), n as u128);
+        }
+    }
+
+    #[test]
+    fn gray_successor_matches_native() {
+        let bits = 3;
+        // 整个循环都走一遍，包括 gray(7) -> gray(0)
+        for n in 0..(1u64 << bits) {
+            let next = (n + 1) % (1 << bits);
+            assert_eq!(
+                run(9, GraySuccessor { bits }, &inputs(gray(n), gray(next)), &[]),
+                Ok(()),
+                "n = {}",
+                n
+            );
+        }
+        let bits = 8;
+        for n in [0u64, 127, 200, 255] {
+            let next = (n + 1) % (1 << bits);
+            assert_eq!(
+                run(9, GraySuccessor { bits }, &inputs(gray(n), gray(next)), &[]),
+                Ok(()),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn gray_successor_rejects_wrong_code() {
+        let bits = 3;
+        for n in 0..(1u64 << bits) {
+            let next = (n + 1) % (1 << bits);
+            // 后继的后继，前驱，以及直接用binary当成Gray code
+            for wrong in [gray((n + 2) % 8), gray((n + 7) % 8), next] {
+                if wrong == gray(next) {
+                    continue;
+                }
+                assert!(
+                    run(9, GraySuccessor { bits }, &inputs(gray(n), wrong), &[]).is_err(),
+                    "n = {}, g2 = {}",
+                    n,
+                    wrong
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gray_successor_rejects_out_of_range_code() {
+        // 8 不是一个 3-bit 的 Gray code
+        assert!(run(9, GraySuccessor { bits: 3 }, &inputs(8, gray(0)), &[]).is_err());
+        assert!(synthesis_fails(
+            9,
+            GraySuccessor { bits: 0 },
+            &inputs(0, 0),
+            &[]
+        ));
+        assert!(synthesis_fails(
+            9,
+            GraySuccessor { bits: 64 },
+            &inputs(0, 1),
+            &[]
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    bitwise::{BitwiseChip, BitwiseConfig},
+    popcount::{PopcountChip, PopcountConfig},
+};
+use crate::ACell;
+
+// Hamming 距离：a 和 b 有多少位不一样，也就是 popcount(a ^ b)
+// a, b 都需要 < 2^bits（BitwiseChip 会range check）
+#[derive(Debug, Clone)]
+pub struct HammingDistanceConfig {
+    pub bitwise: BitwiseConfig,
+    pub popcount: PopcountConfig,
+}
+
+pub struct HammingDistanceChip<F: FieldExt> {
+    config: HammingDistanceConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> HammingDistanceChip<F> {
+    pub fn construct(config: HammingDistanceConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> HammingDistanceConfig {
+        HammingDistanceConfig {
+            bitwise: BitwiseChip::configure(meta, advice),
+            popcount: PopcountChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn hamming_distance(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let bitwise = BitwiseChip::construct(self.config.bitwise.clone());
+        let diff = bitwise.xor(layouter.namespace(|| "a ^ b"), a, b, bits)?;
+
+        let popcount = PopcountChip::construct(self.config.popcount.clone());
+        popcount.popcount(layouter.namespace(|| "popcount(a ^ b)"), &diff, bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const BITS: usize = 8;
+
+    // 输入是 [a, b]
+    #[derive(Clone, Default)]
+    struct Hamming;
+
+    impl TestGadget<Fp> for Hamming {
+        type Config = HammingDistanceConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> HammingDistanceConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            HammingDistanceChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: HammingDistanceConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let distance = HammingDistanceChip::construct(config)
+                .hamming_distance(layouter, &inputs[0], &inputs[1], BITS)?;
+            Ok(vec![distance])
+        }
+    }
+
+    fn hamming(a: u8, b: u8) -> Fp {
+        Fp::from((a ^ b).count_ones() as u64)
+    }
+
+    fn inputs(a: u8, b: u8) -> Vec<Fp> {
+        vec![Fp::from(a as u64), Fp::from(b as u64)]
+    }
+
+    #[test]
+    fn hamming_matches_native() {
+        for (a, b) in [
+            // 一样的是 0，互补的是 bits
+            (0x5au8, 0x5au8),
+            (0x00, 0xff),
+            (0x0f, 0xf0),
+            (0x01, 0x03),
+            (0xde, 0xad),
+        ] {
+            assert_eq!(
+                run(6, Hamming, &inputs(a, b), &[hamming(a, b)]),
+                Ok(()),
+                "{:#04x} vs {:#04x}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn hamming_rejects_wrong_distance() {
+        assert!(run(6, Hamming, &inputs(0x00, 0xff), &[Fp::from(7)]).is_err());
+        assert!(run(6, Hamming, &inputs(0x12, 0x12), &[Fp::one()]).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig};
+use crate::ACell;
+
+// 证明数组满足最大堆的性质：a[i] >= a[2i + 1]，a[i] >= a[2i + 2]
+// 只需要对每一对 (parent, child) 做一次 LessThanOrEqualChip，没有孩子的节点直接跳过
+// 所有value都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct HeapConfig {
+    pub less_than_or_equal: LessThanOrEqualConfig,
+    pub bits: usize,
+}
+
+pub struct HeapChip<F: FieldExt> {
+    config: HeapConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> HeapChip<F> {
+    pub fn construct(config: HeapConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> HeapConfig {
+        HeapConfig {
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+            bits,
+        }
+    }
+
+    pub fn assert_max_heap(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+    ) -> Result<(), Error> {
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+
+        for child in 1..values.len() {
+            let parent = (child - 1) / 2;
+            less_than_or_equal.assert_less_than_or_equal(
+                layouter.namespace(|| format!("a[{}] <= a[{}]", child, parent)),
+                &values[child],
+                &values[parent],
+                self.config.bits,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Heap;
+
+    impl TestGadget<Fp> for Heap {
+        type Config = HeapConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> HeapConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            HeapChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: HeapConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            HeapChip::construct(config).assert_max_heap(layouter, inputs)?;
+            Ok(vec![])
+        }
+    }
+
+    fn is_max_heap(values: &[u64]) -> bool {
+        (1..values.len()).all(|child| values[(child - 1) / 2] >= values[child])
+    }
+
+    fn inputs(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn heap_matches_native() {
+        for values in [
+            vec![],
+            vec![42u64],
+            vec![9, 7, 8, 3, 7, 8],
+            // 相等的 parent / child 也是合法的
+            vec![5, 5, 5, 5],
+            // 最后一个内部节点只有左孩子
+            vec![255, 100, 200, 0, 100],
+        ] {
+            assert!(is_max_heap(&values));
+            assert_eq!(run(8, Heap, &inputs(&values), &[]), Ok(()), "{:?}", values);
+        }
+    }
+
+    #[test]
+    fn heap_rejects_violations() {
+        for values in [
+            vec![1u64, 2],
+            // 右孩子比 parent 大
+            vec![9, 7, 10],
+            // 违反发生在更深的一层
+            vec![9, 7, 8, 3, 8],
+            vec![9, 7, 8, 3, 7, 8, 9],
+        ] {
+            assert!(!is_max_heap(&values));
+            assert!(run(8, Heap, &inputs(&values), &[]).is_err(), "{:?}", values);
+        }
+    }
+}
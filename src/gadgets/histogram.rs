@@ -0,0 +1,310 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    index_select::{IndexSelectChip, IndexSelectConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    prefix_sum::{PrefixSumChip, PrefixSumConfig},
+    to_u128, Boolean,
+};
+use crate::ACell;
+
+// 证明 counts 是 values 的直方图
+// boundaries 是严格递增的常数 b_0 < b_1 < ... < b_{k-1}，一共 k + 1 个桶：
+//   桶 0: v < b_0，桶 j: b_{j-1} <= v < b_j，桶 k: v >= b_{k-1}
+// 也就是说刚好落在边界上的值属于右边那个桶
+//
+// 对每个 value 先算 lt_j = (v < b_j)，再witness一个 one-hot 向量 f 表示它落在哪个桶，
+// 用 IndexSelectChip（顺便检查了 f 是 one-hot 的）选出这个桶的两条边界上的比较结果：
+//   upper = [lt_0, ..., lt_{k-1}, 1] 里面选出来的必须是 1   （v < 右边界）
+//   lower = [0, lt_0, ..., lt_{k-1}] 里面选出来的必须是 0   （v >= 左边界）
+// 被选中的桶的 count 加一，也就是 count_j = Σ_i f_{i, j}
+// value 和 boundaries 都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct HistogramConfig {
+    pub advice: Column<Advice>,
+    pub constant: ConstantConfig,
+    pub less_than: LessThanConfig,
+    pub index_select: IndexSelectConfig,
+    pub prefix_sum: PrefixSumConfig,
+    pub bits: usize,
+}
+
+pub struct HistogramChip<F: FieldExt> {
+    config: HistogramConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> HistogramChip<F> {
+    pub fn construct(config: HistogramConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> HistogramConfig {
+        meta.enable_equality(advice[0]);
+
+        HistogramConfig {
+            advice: advice[0],
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            index_select: IndexSelectChip::configure(meta, advice),
+            prefix_sum: PrefixSumChip::configure(meta, [advice[0], advice[1]]),
+            bits,
+        }
+    }
+
+    pub fn assert_histogram(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+        boundaries: &[F],
+        counts: &[ACell<F>],
+    ) -> Result<(), Error> {
+        let increasing = boundaries
+            .windows(2)
+            .all(|pair| to_u128(&pair[0]) < to_u128(&pair[1]));
+        if !increasing || counts.len() != boundaries.len() + 1 {
+            return Err(Error::Synthesis);
+        }
+
+        // 没有value的时候每个桶都是 0
+        if values.is_empty() {
+            return layouter.assign_region(
+                || "empty histogram",
+                |mut region| {
+                    for count in counts {
+                        region.constrain_constant(count.0.cell(), F::zero())?;
+                    }
+                    Ok(())
+                },
+            );
+        }
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let index_select = IndexSelectChip::construct(self.config.index_select.clone());
+
+        let zero = constant.load_constant(layouter.namespace(|| "zero"), F::zero())?;
+        let one = constant.load_constant(layouter.namespace(|| "one"), F::one())?;
+        let bounds = boundaries
+            .iter()
+            .enumerate()
+            .map(|(j, b)| constant.load_constant(layouter.namespace(|| format!("b_{}", j)), *b))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // flags[j][i]：第 i 个 value 是不是在桶 j 里面
+        let mut flags = vec![Vec::with_capacity(values.len()); counts.len()];
+        for (i, value) in values.iter().enumerate() {
+            let lts = bounds
+                .iter()
+                .enumerate()
+                .map(|(j, bound)| {
+                    less_than
+                        .less_than(
+                            layouter.namespace(|| format!("v_{} < b_{}", i, j)),
+                            value,
+                            bound,
+                            self.config.bits,
+                        )
+                        .map(|lt| lt.0)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // 桶的编号就是第一个 lt_j = 1 的 j，都是 0 的话就是最后一个桶
+            let onehot = layouter.assign_region(
+                || format!("bucket of v_{}", i),
+                |mut region| {
+                    let bucket = lts
+                        .iter()
+                        .map(|lt| lt.0.value().map(|v| *v == F::one()))
+                        .collect::<Option<Vec<_>>>()
+                        .map(|lts| lts.iter().position(|lt| *lt).unwrap_or(lts.len()));
+
+                    (0..counts.len())
+                        .map(|j| {
+                            region
+                                .assign_advice(
+                                    || format!("in bucket {}", j),
+                                    self.config.advice,
+                                    j,
+                                    || {
+                                        bucket
+                                            .map(|bucket| F::from(bucket == j))
+                                            .ok_or(Error::Synthesis)
+                                    },
+                                )
+                                .map(|cell| Boolean(ACell(cell)))
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                },
+            )?;
+
+            let upper: Vec<_> = lts.iter().cloned().chain([one.clone()]).collect();
+            let lower: Vec<_> = [zero.clone()].into_iter().chain(lts).collect();
+            let below_upper = index_select.select(
+                layouter.namespace(|| format!("v_{} < upper bound", i)),
+                &onehot,
+                &upper,
+            )?;
+            let below_lower = index_select.select(
+                layouter.namespace(|| format!("v_{} >= lower bound", i)),
+                &onehot,
+                &lower,
+            )?;
+            layouter.assign_region(
+                || format!("v_{} is in its bucket", i),
+                |mut region| {
+                    region.constrain_constant(below_upper.0.cell(), F::one())?;
+                    region.constrain_constant(below_lower.0.cell(), F::zero())
+                },
+            )?;
+
+            for (bucket, flag) in flags.iter_mut().zip(onehot) {
+                bucket.push(flag.0);
+            }
+        }
+
+        let prefix_sum = PrefixSumChip::construct(self.config.prefix_sum.clone());
+        for (j, (bucket, count)) in flags.iter().zip(counts.iter()).enumerate() {
+            let sums =
+                prefix_sum.prefix_sum(layouter.namespace(|| format!("bucket {}", j)), bucket)?;
+            let total = sums.last().unwrap();
+            layouter.assign_region(
+                || format!("count {}", j),
+                |mut region| region.constrain_equal(total.0.cell(), count.0.cell()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 values...，后面接着 buckets 个 counts
+    #[derive(Clone, Default)]
+    struct Histogram {
+        boundaries: Vec<u64>,
+        buckets: usize,
+    }
+
+    impl Histogram {
+        fn new(boundaries: &[u64]) -> Self {
+            Self {
+                boundaries: boundaries.to_vec(),
+                buckets: boundaries.len() + 1,
+            }
+        }
+    }
+
+    impl TestGadget<Fp> for Histogram {
+        type Config = HistogramConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> HistogramConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            HistogramChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: HistogramConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (values, counts) = inputs.split_at(inputs.len() - self.buckets);
+            let boundaries: Vec<_> = self.boundaries.iter().map(|b| Fp::from(*b)).collect();
+            HistogramChip::construct(config).assert_histogram(
+                layouter,
+                values,
+                &boundaries,
+                counts,
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    const BOUNDARIES: [u64; 3] = [10, 20, 30];
+
+    fn histogram(values: &[u64]) -> Vec<u64> {
+        let mut counts = vec![0; BOUNDARIES.len() + 1];
+        for v in values {
+            counts[BOUNDARIES.iter().filter(|b| *b <= v).count()] += 1;
+        }
+        counts
+    }
+
+    fn inputs(values: &[u64], counts: &[u64]) -> Vec<Fp> {
+        values.iter().chain(counts).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn histogram_matches_native() {
+        for values in [
+            vec![],
+            vec![0u64, 5, 15, 25, 35, 255],
+            // 落在边界上的值属于右边的桶
+            vec![10, 20, 30, 9, 19, 29],
+            vec![7, 7, 7],
+        ] {
+            let gadget = Histogram::new(&BOUNDARIES);
+            assert_eq!(
+                run(10, gadget, &inputs(&values, &histogram(&values)), &[]),
+                Ok(()),
+                "values {:?}",
+                values
+            );
+        }
+    }
+
+    #[test]
+    fn histogram_rejects_wrong_counts() {
+        let values = [10u64, 15, 31];
+        for counts in [
+            // 10 被放进了左边的桶
+            [1u64, 0, 0, 1],
+            [0, 1, 1, 1],
+            [0, 3, 0, 0],
+        ] {
+            let gadget = Histogram::new(&BOUNDARIES);
+            assert!(run(10, gadget, &inputs(&values, &counts), &[]).is_err());
+        }
+        let gadget = Histogram::new(&BOUNDARIES);
+        assert!(run(10, gadget, &inputs(&[], &[1, 0, 0, 0]), &[]).is_err());
+    }
+
+    #[test]
+    fn histogram_rejects_bad_parameters() {
+        // boundaries 必须严格递增
+        let gadget = Histogram::new(&[20, 10]);
+        assert!(synthesis_fails(10, gadget, &inputs(&[5], &[1, 0, 0]), &[]));
+        let gadget = Histogram::new(&[10, 10]);
+        assert!(synthesis_fails(10, gadget, &inputs(&[5], &[1, 0, 0]), &[]));
+
+        // counts 的个数必须是 boundaries.len() + 1
+        let gadget = Histogram {
+            boundaries: BOUNDARIES.to_vec(),
+            buckets: 3,
+        };
+        assert!(synthesis_fails(10, gadget, &inputs(&[5], &[1, 0, 0]), &[]));
+    }
+}
@@ -0,0 +1,202 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    one_hot::{OneHotChip, OneHotConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 用一个 one-hot 向量去"索引"一个数组：out = Σ onehot_i * value_i
+// 先用 OneHotChip 保证 onehot 里面刚好有一个1，再用running sum算masked sum
+//
+//  flag | value | acc | q_first | q_step
+//  f_0  |  v_0  | f_0 * v_0       |  1  | 0
+//  f_1  |  v_1  | acc + f_1 * v_1 |  0  | 1
+//
+#[derive(Debug, Clone)]
+pub struct IndexSelectConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub one_hot: OneHotConfig,
+}
+
+pub struct IndexSelectChip<F: FieldExt> {
+    config: IndexSelectConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IndexSelectChip<F> {
+    pub fn construct(config: IndexSelectConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> IndexSelectConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("index select first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let flag = meta.query_advice(advice[0], Rotation::cur());
+            let value = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![q_first * (acc - flag * value)]
+        });
+
+        meta.create_gate("index select step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let flag = meta.query_advice(advice[0], Rotation::cur());
+            let value = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[2], Rotation::prev());
+
+            vec![q_step * (acc - (acc_prev + flag * value))]
+        });
+
+        IndexSelectConfig {
+            advice,
+            q_first,
+            q_step,
+            one_hot: OneHotChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn select(
+        &self,
+        mut layouter: impl Layouter<F>,
+        onehot: &[Boolean<F>],
+        values: &[ACell<F>],
+    ) -> Result<ACell<F>, Error> {
+        if onehot.len() != values.len() {
+            return Err(Error::Synthesis);
+        }
+
+        let one_hot = OneHotChip::construct(self.config.one_hot.clone());
+        one_hot.assert_one_hot(layouter.namespace(|| "one hot"), onehot)?;
+
+        layouter.assign_region(
+            || "index select",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+                let mut acc = None;
+
+                for (row, (flag, value)) in onehot.iter().zip(values.iter()).enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    let flag = &flag.0;
+                    flag.0
+                        .copy_advice(|| "flag", &mut region, self.config.advice[0], row)?;
+                    value
+                        .0
+                        .copy_advice(|| "value", &mut region, self.config.advice[1], row)?;
+
+                    acc_val = acc_val
+                        .zip(flag.0.value())
+                        .zip(value.0.value())
+                        .map(|((acc, f), v)| acc + *f * *v);
+                    acc = Some(
+                        region
+                            .assign_advice(
+                                || "acc",
+                                self.config.advice[2],
+                                row,
+                                || acc_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?,
+                    );
+                }
+
+                // one_hot 已经保证了 onehot 不是空的
+                acc.ok_or(Error::Synthesis)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入的前一半是 onehot，后一半是 values
+    #[derive(Clone, Default)]
+    struct Select;
+
+    impl TestGadget<Fp> for Select {
+        type Config = IndexSelectConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> IndexSelectConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            IndexSelectChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: IndexSelectConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (onehot, values) = inputs.split_at(inputs.len() / 2);
+            let onehot: Vec<_> = onehot.iter().cloned().map(Boolean).collect();
+            let out = IndexSelectChip::construct(config).select(layouter, &onehot, values)?;
+            Ok(vec![out])
+        }
+    }
+
+    fn inputs(index: usize, values: &[u64]) -> Vec<Fp> {
+        (0..values.len())
+            .map(|i| Fp::from((i == index) as u64))
+            .chain(values.iter().map(|v| Fp::from(*v)))
+            .collect()
+    }
+
+    #[test]
+    fn select_matches_native_index() {
+        let values = [10u64, 20, 30, 40];
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(run(5, Select, &inputs(i, &values), &[Fp::from(*v)]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn select_rejects_wrong_value() {
+        assert!(run(5, Select, &inputs(1, &[10, 20, 30]), &[Fp::from(30)]).is_err());
+    }
+
+    #[test]
+    fn select_rejects_non_one_hot_index() {
+        // 两个1的时候 out = 10 + 20，one hot 约束要挡住
+        let mut ins = inputs(0, &[10, 20]);
+        ins[1] = Fp::one();
+        assert!(run(5, Select, &ins, &[Fp::from(30)]).is_err());
+    }
+
+    #[test]
+    fn select_rejects_length_mismatch() {
+        let ins = [Fp::one(), Fp::zero(), Fp::from(10)];
+        assert!(synthesis_fails(5, Select, &ins, &[Fp::from(10)]));
+    }
+}
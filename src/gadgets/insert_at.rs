@@ -0,0 +1,203 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    mux::{MuxChip, MuxConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 在下标 k 处插入 value（k 是一个cell），后面的元素都往后挪一位，output 比 input 多一个元素
+// 对 output 的每个位置 j：
+//   eq_j = (j == k)
+//   passed_j = eq_0 + ... + eq_{j-1}（已经过了插入的位置）
+//   shifted_j = passed_j ? input[j - 1] : input[j]
+//   output[j] = eq_j ? value : shifted_j
+// 最后断言 eq_0 + ... + eq_n == 1，也就是 0 <= k <= n，这样 passed_j 也一定是 0 或 1
+// j = 0 的时候 passed 一定是 0，j = n 的时候（不是插入的位置）passed 一定是 1，这两处不需要mux
+#[derive(Debug, Clone)]
+pub struct InsertAtConfig {
+    pub constant: ConstantConfig,
+    pub is_equal: IsEqualConfig,
+    pub add: AddConfig,
+    pub mux: MuxConfig,
+}
+
+pub struct InsertAtChip<F: FieldExt> {
+    config: InsertAtConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> InsertAtChip<F> {
+    pub fn construct(config: InsertAtConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> InsertAtConfig {
+        InsertAtConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            is_equal: IsEqualChip::configure(meta, advice),
+            add: AddChip::configure(meta, advice),
+            mux: MuxChip::configure(meta, advice),
+        }
+    }
+
+    pub fn insert_at(
+        &self,
+        mut layouter: impl Layouter<F>,
+        input: &[ACell<F>],
+        value: &ACell<F>,
+        k: &ACell<F>,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        let add = AddChip::construct(self.config.add.clone());
+        let mux = MuxChip::construct(self.config.mux.clone());
+
+        let n = input.len();
+        let mut output = Vec::with_capacity(n + 1);
+        let mut passed: Option<ACell<F>> = None;
+
+        for j in 0..=n {
+            let j_cell = constant.load_constant(
+                layouter.namespace(|| format!("j = {}", j)),
+                F::from(j as u64),
+            )?;
+            let eq = is_equal.is_equal(layouter.namespace(|| format!("k == {}", j)), k, &j_cell)?;
+
+            let shifted = if n == 0 {
+                None
+            } else if j == 0 {
+                Some(input[0].clone())
+            } else if j == n {
+                Some(input[n - 1].clone())
+            } else {
+                Some(mux.mux(
+                    layouter.namespace(|| format!("shift {}", j)),
+                    &Boolean(passed.clone().unwrap()),
+                    &input[j - 1],
+                    &input[j],
+                )?)
+            };
+            output.push(match shifted {
+                Some(shifted) => mux.mux(
+                    layouter.namespace(|| format!("output {}", j)),
+                    &eq,
+                    value,
+                    &shifted,
+                )?,
+                // input 是空的，output 就只有 value
+                None => value.clone(),
+            });
+
+            passed = Some(match passed {
+                Some(passed) => add.add(
+                    layouter.namespace(|| format!("passed {}", j + 1)),
+                    &passed,
+                    &eq.0,
+                )?,
+                None => eq.0,
+            });
+        }
+
+        let total = passed.unwrap();
+        layouter.assign_region(
+            || "0 <= k <= n",
+            |mut region| region.constrain_constant(total.0.cell(), F::one()),
+        )?;
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 input...，后面接着 value 和 k
+    #[derive(Clone, Default)]
+    struct InsertAt;
+
+    impl TestGadget<Fp> for InsertAt {
+        type Config = InsertAtConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InsertAtConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            InsertAtChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: InsertAtConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (input, rest) = inputs.split_at(inputs.len() - 2);
+            InsertAtChip::construct(config).insert_at(layouter, input, &rest[0], &rest[1])
+        }
+    }
+
+    fn inputs(input: &[u64], value: u64, k: u64) -> Vec<Fp> {
+        input
+            .iter()
+            .chain([&value, &k])
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn insert_at_matches_native() {
+        let input = [10u64, 20, 30, 40];
+        // 最前面，中间，最后面
+        for k in 0..=input.len() {
+            let mut expected = input.to_vec();
+            expected.insert(k, 99);
+            assert_eq!(
+                run(7, InsertAt, &inputs(&input, 99, k as u64), &fp(&expected)),
+                Ok(()),
+                "k = {}",
+                k
+            );
+        }
+        assert_eq!(run(7, InsertAt, &inputs(&[], 5, 0), &fp(&[5])), Ok(()));
+    }
+
+    #[test]
+    fn insert_at_rejects_wrong_output() {
+        let input = [10u64, 20, 30];
+        // 插错了位置，覆盖掉原来的元素而不是往后挪
+        assert!(run(7, InsertAt, &inputs(&input, 99, 1), &fp(&[10, 20, 99, 30])).is_err());
+        assert!(run(7, InsertAt, &inputs(&input, 99, 1), &fp(&[10, 99, 30, 30])).is_err());
+    }
+
+    #[test]
+    fn insert_at_rejects_out_of_range_index() {
+        // k = n + 1 的时候没有一个位置匹配，output 就是往后挪之前的 input
+        let input = [10u64, 20, 30];
+        assert!(run(7, InsertAt, &inputs(&input, 99, 4), &fp(&[10, 20, 30, 30])).is_err());
+        assert!(run(7, InsertAt, &inputs(&[], 5, 1), &fp(&[5])).is_err());
+    }
+}
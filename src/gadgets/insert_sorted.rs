@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    permutation_check::{PermutationCheckChip, PermutationCheckConfig},
+    sorted::{SortedChip, SortedConfig},
+};
+use crate::ACell;
+
+// 证明 b 是把 v 插入到有序数组 a 之后的结果：
+// * b 是 sorted 的（相邻的差都 range check 到 bits 位）
+// * b 是 a ++ [v] 的一个permutation（grand product，gamma 的要求见 PermutationCheckChip）
+// a 本身是否有序这里不检查，b 只由 a 和 v 组成的multiset决定
+#[derive(Debug, Clone)]
+pub struct InsertSortedConfig {
+    pub sorted: SortedConfig,
+    pub permutation_check: PermutationCheckConfig,
+    pub bits: usize,
+}
+
+pub struct InsertSortedChip<F: FieldExt> {
+    config: InsertSortedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> InsertSortedChip<F> {
+    pub fn construct(config: InsertSortedConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        bits: usize,
+    ) -> InsertSortedConfig {
+        InsertSortedConfig {
+            sorted: SortedChip::configure(meta, [advice[0], advice[1]]),
+            permutation_check: PermutationCheckChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    pub fn assert_insert(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[ACell<F>],
+        v: &ACell<F>,
+        b: &[ACell<F>],
+        gamma: &ACell<F>,
+    ) -> Result<(), Error> {
+        let sorted = SortedChip::construct(self.config.sorted.clone());
+        sorted.assert_sorted(layouter.namespace(|| "b is sorted"), b, self.config.bits)?;
+
+        let mut a_with_v = a.to_vec();
+        a_with_v.push(v.clone());
+
+        let permutation_check =
+            PermutationCheckChip::construct(self.config.permutation_check.clone());
+        permutation_check.assert_permutation(
+            layouter.namespace(|| "b is a permutation of a ++ [v]"),
+            &a_with_v,
+            b,
+            gamma,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 a..., v, b..., gamma，其中 b 比 a 多一个
+    #[derive(Clone, Default)]
+    struct InsertSorted {
+        a_len: usize,
+    }
+
+    impl TestGadget<Fp> for InsertSorted {
+        type Config = InsertSortedConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InsertSortedConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            InsertSortedChip::configure(meta, advice, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: InsertSortedConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (a, rest) = inputs.split_at(self.a_len);
+            let (v, rest) = rest.split_first().unwrap();
+            let (b, gamma) = rest.split_at(self.a_len + 1);
+            InsertSortedChip::construct(config).assert_insert(layouter, a, v, b, &gamma[0])?;
+            Ok(vec![])
+        }
+    }
+
+    fn inputs(a: &[u64], v: u64, b: &[u64]) -> Vec<Fp> {
+        a.iter()
+            .chain([v].iter())
+            .chain(b)
+            .map(|x| Fp::from(*x))
+            .chain([Fp::from(0x5eed_5eed_5eed)])
+            .collect()
+    }
+
+    fn insert(a: &[u64], v: u64) -> Vec<u64> {
+        let mut b = a.to_vec();
+        b.insert(a.partition_point(|x| *x < v), v);
+        b
+    }
+
+    #[test]
+    fn insert_sorted_matches_native() {
+        let a = [3u64, 7, 7, 20];
+        // 插到最前面 / 中间 / 重复元素旁边 / 最后面
+        for v in [0u64, 5, 7, 255] {
+            let g = InsertSorted { a_len: a.len() };
+            assert_eq!(run(7, g, &inputs(&a, v, &insert(&a, v)), &[]), Ok(()));
+        }
+        // 插到空数组里面
+        let g = InsertSorted { a_len: 0 };
+        assert_eq!(run(7, g, &inputs(&[], 9, &[9]), &[]), Ok(()));
+    }
+
+    #[test]
+    fn insert_sorted_rejects_wrong_insertion() {
+        let a = [3u64, 7, 20];
+        let g = InsertSorted { a_len: a.len() };
+        for b in [
+            // 是 permutation，但是插错了位置，不是sorted
+            [3u64, 7, 20, 5],
+            // sorted，但是插进去的不是 v
+            [3, 6, 7, 20],
+            // sorted，但是丢了 a 里面的一个元素
+            [3, 5, 5, 20],
+        ] {
+            assert!(run(7, g.clone(), &inputs(&a, 5, &b), &[]).is_err());
+        }
+    }
+}
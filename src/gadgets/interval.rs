@@ -0,0 +1,152 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    mul::{MulChip, MulConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 两个闭区间 [a1, a2] 和 [b1, b2] 是否重叠：a1 <= b2 AND b1 <= a2
+// 两个比较结果都是 Boolean，AND 就是直接相乘（乘积也一定是 0 或 1）
+// 只共享一个端点（a2 == b1）也算重叠
+// 所有端点都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct IntervalOverlapConfig {
+    pub less_than_or_equal: LessThanOrEqualConfig,
+    pub mul: MulConfig,
+    pub bits: usize,
+}
+
+pub struct IntervalOverlapChip<F: FieldExt> {
+    config: IntervalOverlapConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IntervalOverlapChip<F> {
+    pub fn construct(config: IntervalOverlapConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> IntervalOverlapConfig {
+        IntervalOverlapConfig {
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+            mul: MulChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    pub fn overlaps(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a1: &ACell<F>,
+        a2: &ACell<F>,
+        b1: &ACell<F>,
+        b2: &ACell<F>,
+    ) -> Result<Boolean<F>, Error> {
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+        let left = less_than_or_equal.less_than_or_equal(
+            layouter.namespace(|| "a1 <= b2"),
+            a1,
+            b2,
+            self.config.bits,
+        )?;
+        let right = less_than_or_equal.less_than_or_equal(
+            layouter.namespace(|| "b1 <= a2"),
+            b1,
+            a2,
+            self.config.bits,
+        )?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        mul.mul(layouter.namespace(|| "and"), &left.0, &right.0)
+            .map(Boolean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [a1, a2, b1, b2]
+    #[derive(Clone, Default)]
+    struct Overlap;
+
+    impl TestGadget<Fp> for Overlap {
+        type Config = IntervalOverlapConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> IntervalOverlapConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            IntervalOverlapChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: IntervalOverlapConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let overlaps = IntervalOverlapChip::construct(config)
+                .overlaps(layouter, &inputs[0], &inputs[1], &inputs[2], &inputs[3])?;
+            Ok(vec![overlaps.0])
+        }
+    }
+
+    fn overlaps(a: (u64, u64), b: (u64, u64)) -> bool {
+        a.0.max(b.0) <= a.1.min(b.1)
+    }
+
+    fn inputs(a: (u64, u64), b: (u64, u64)) -> Vec<Fp> {
+        [a.0, a.1, b.0, b.1].iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn overlap_matches_native() {
+        for (a, b) in [
+            // 重叠，包含，完全一样
+            ((1u64, 5u64), (3u64, 8u64)),
+            ((0, 100), (20, 30)),
+            ((7, 7), (7, 7)),
+            // 只共享一个端点
+            ((1, 5), (5, 9)),
+            ((5, 9), (1, 5)),
+            // 不相交
+            ((1, 4), (5, 9)),
+            ((200, 255), (0, 199)),
+        ] {
+            let expected = Fp::from(overlaps(a, b) as u64);
+            assert_eq!(
+                run(6, Overlap, &inputs(a, b), &[expected]),
+                Ok(()),
+                "{:?} {:?}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn overlap_rejects_wrong_answer() {
+        assert!(run(6, Overlap, &inputs((1, 5), (5, 9)), &[Fp::zero()]).is_err());
+        assert!(run(6, Overlap, &inputs((1, 4), (5, 9)), &[Fp::one()]).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+use crate::ACell;
+
+// 判断 a == b，返回一个 Boolean
+// 和 is_zero 的做法一样：witness inv = (a - b)^{-1}（a == b 的时候随便给 0）
+//   eq = 1 - (a - b) * inv
+//   (a - b) * eq = 0
+// a != b 的时候第二条约束逼着 eq = 0，第一条约束又逼着 inv 必须是真正的逆
+//
+//   a  | b | eq | selector
+//  inv |   |    |
+//
+#[derive(Debug, Clone)]
+pub struct IsEqualConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+pub struct IsEqualChip<F: FieldExt> {
+    config: IsEqualConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IsEqualChip<F> {
+    pub fn construct(config: IsEqualConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> IsEqualConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("is equal", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let eq = meta.query_advice(advice[2], Rotation::cur());
+            let inv = meta.query_advice(advice[0], Rotation::next());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * (eq.clone() - (one - (a.clone() - b.clone()) * inv)),
+                s * (a - b) * eq,
+            ]
+        });
+
+        IsEqualConfig { advice, selector }
+    }
+
+    pub fn is_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<Boolean<F>, Error> {
+        layouter.assign_region(
+            || "is equal",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let diff = a.0.value().zip(b.0.value()).map(|(a, b)| *a - *b);
+                let inv_val = diff.map(|d| d.invert().unwrap_or(F::zero()));
+                let eq_val = diff.map(|d| F::from(d == F::zero()));
+
+                region.assign_advice(
+                    || "inv",
+                    self.config.advice[0],
+                    1,
+                    || inv_val.ok_or(Error::Synthesis),
+                )?;
+
+                region
+                    .assign_advice(
+                        || "eq",
+                        self.config.advice[2],
+                        0,
+                        || eq_val.ok_or(Error::Synthesis),
+                    )
+                    .map(|cell| Boolean(ACell(cell)))
+            },
+        )
+    }
+
+    // 如果只是想断言 a == b，直接加一个copy constraint就够了，不需要走gate
+    pub fn assert_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assert equal",
+            |mut region| region.constrain_equal(a.0.cell(), b.0.cell()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct IsEqual {
+        assert_only: bool,
+    }
+
+    impl TestGadget<Fp> for IsEqual {
+        type Config = IsEqualConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> IsEqualConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            IsEqualChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: IsEqualConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = IsEqualChip::construct(config);
+            if self.assert_only {
+                chip.assert_equal(layouter, &inputs[0], &inputs[1])?;
+                return Ok(vec![]);
+            }
+            Ok(vec![chip.is_equal(layouter, &inputs[0], &inputs[1])?.0])
+        }
+    }
+
+    #[test]
+    fn is_equal_matches_native() {
+        let g = IsEqual::default();
+        for (a, b) in [(0u64, 0u64), (5, 5), (5, 6), (0, 1)] {
+            let expected = Fp::from((a == b) as u64);
+            assert_eq!(
+                run(4, g.clone(), &[Fp::from(a), Fp::from(b)], &[expected]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn is_equal_rejects_wrong_flag() {
+        let g = IsEqual::default();
+        assert!(run(4, g.clone(), &[Fp::from(5), Fp::from(6)], &[Fp::one()]).is_err());
+        assert!(run(4, g, &[Fp::from(5), Fp::from(5)], &[Fp::zero()]).is_err());
+    }
+
+    #[test]
+    fn assert_equal_rejects_different_values() {
+        let g = IsEqual { assert_only: true };
+        assert_eq!(run(4, g.clone(), &[Fp::from(9), Fp::from(9)], &[]), Ok(()));
+        assert!(run(4, g, &[Fp::from(9), Fp::from(8)], &[]).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+use crate::ACell;
+
+// 判断 x == 0，返回一个 Boolean
+// witness inv = x^{-1}（x == 0 的时候随便给 0）
+//   z = 1 - x * inv
+//   x * z = 0
+//
+//   x  | z | selector
+//  inv |   |
+//
+#[derive(Debug, Clone)]
+pub struct IsZeroConfig {
+    pub advice: [Column<Advice>; 2],
+    pub selector: Selector,
+}
+
+pub struct IsZeroChip<F: FieldExt> {
+    config: IsZeroConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IsZeroChip<F> {
+    pub fn construct(config: IsZeroConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> IsZeroConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("is zero", |meta| {
+            let s = meta.query_selector(selector);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let z = meta.query_advice(advice[1], Rotation::cur());
+            let inv = meta.query_advice(advice[0], Rotation::next());
+            let one = Expression::Constant(F::one());
+
+            vec![s.clone() * (z.clone() - (one - x.clone() * inv)), s * x * z]
+        });
+
+        IsZeroConfig { advice, selector }
+    }
+
+    pub fn is_zero(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+    ) -> Result<Boolean<F>, Error> {
+        layouter.assign_region(
+            || "is zero",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                x.0.copy_advice(|| "x", &mut region, self.config.advice[0], 0)?;
+
+                let inv_val = x.0.value().map(|x| x.invert().unwrap_or(F::zero()));
+                let z_val = x.0.value().map(|x| F::from(*x == F::zero()));
+
+                region.assign_advice(
+                    || "inv",
+                    self.config.advice[0],
+                    1,
+                    || inv_val.ok_or(Error::Synthesis),
+                )?;
+
+                region
+                    .assign_advice(
+                        || "z",
+                        self.config.advice[1],
+                        0,
+                        || z_val.ok_or(Error::Synthesis),
+                    )
+                    .map(|cell| Boolean(ACell(cell)))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct IsZero;
+
+    impl TestGadget<Fp> for IsZero {
+        type Config = IsZeroConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> IsZeroConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            IsZeroChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: IsZeroConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                IsZeroChip::construct(config)
+                    .is_zero(layouter, &inputs[0])?
+                    .0,
+            ])
+        }
+    }
+
+    #[test]
+    fn is_zero_matches_native() {
+        for (x, expected) in [
+            (Fp::zero(), Fp::one()),
+            (Fp::one(), Fp::zero()),
+            (Fp::from(12345), Fp::zero()),
+            (-Fp::one(), Fp::zero()),
+        ] {
+            assert_eq!(run(4, IsZero, &[x], &[expected]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn is_zero_rejects_wrong_flag() {
+        assert!(run(4, IsZero, &[Fp::zero()], &[Fp::zero()]).is_err());
+        assert!(run(4, IsZero, &[Fp::from(3)], &[Fp::one()]).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    modulo::{ModChip, ModConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+};
+use crate::ACell;
+
+// 线性同余随机数生成器的一步：next = (a * state + c) mod m
+// a, c, m 都是常数，a * state + c 的商需要在 ModChip 的 QUOTIENT_BITS 以内
+#[derive(Debug, Clone)]
+pub struct LcgConfig {
+    pub mul_const: MulConstConfig,
+    pub constant: ConstantConfig,
+    pub add: AddConfig,
+    pub modulo: ModConfig,
+}
+
+pub struct LcgChip<F: FieldExt> {
+    config: LcgConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LcgChip<F> {
+    pub fn construct(config: LcgConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> LcgConfig {
+        LcgConfig {
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            add: AddChip::configure(meta, advice),
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &ACell<F>,
+        a: u64,
+        c: u64,
+        m: u64,
+    ) -> Result<ACell<F>, Error> {
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let scaled = mul_const.mul_const(layouter.namespace(|| "a * state"), state, F::from(a))?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let c = constant.load_constant(layouter.namespace(|| "c"), F::from(c))?;
+        let add = AddChip::construct(self.config.add.clone());
+        let sum = add.add(layouter.namespace(|| "a * state + c"), &scaled, &c)?;
+
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        modulo.modulo(layouter.namespace(|| "mod m"), &sum, m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 从 state 开始连续走 steps 步，每一步的结果都expose出来
+    #[derive(Clone, Default)]
+    struct Lcg {
+        a: u64,
+        c: u64,
+        m: u64,
+        steps: usize,
+    }
+
+    impl TestGadget<Fp> for Lcg {
+        type Config = LcgConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> LcgConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            LcgChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: LcgConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = LcgChip::construct(config);
+            let mut state = inputs[0].clone();
+            let mut out = Vec::with_capacity(self.steps);
+            for i in 0..self.steps {
+                state = chip.step(
+                    layouter.namespace(|| format!("step {}", i)),
+                    &state,
+                    self.a,
+                    self.c,
+                    self.m,
+                )?;
+                out.push(state.clone());
+            }
+            Ok(out)
+        }
+    }
+
+    fn lcg(g: &Lcg, mut state: u64) -> Vec<Fp> {
+        (0..g.steps)
+            .map(|_| {
+                state = ((g.a as u128 * state as u128 + g.c as u128) % g.m as u128) as u64;
+                Fp::from(state)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lcg_matches_native() {
+        // glibc 的参数，m = 2^31
+        let glibc = Lcg {
+            a: 1103515245,
+            c: 12345,
+            m: 1 << 31,
+            steps: 4,
+        };
+        // 很小的 m，每一步都会绕回来
+        let small = Lcg {
+            a: 5,
+            c: 3,
+            m: 16,
+            steps: 6,
+        };
+        for (g, seed) in [(&glibc, 0u64), (&glibc, 42), (&small, 0), (&small, 15)] {
+            assert_eq!(run(10, g.clone(), &[Fp::from(seed)], &lcg(g, seed)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn lcg_rejects_wrong_step() {
+        let g = Lcg {
+            a: 5,
+            c: 3,
+            m: 16,
+            steps: 1,
+        };
+        // 5 * 7 + 3 = 38，没有 mod m 的话就是 38
+        assert!(run(10, g.clone(), &[Fp::from(7)], &[Fp::from(38)]).is_err());
+        // 38 mod 16 = 6，再加上一个 m 也不行
+        assert!(run(10, g, &[Fp::from(7)], &[Fp::from(22)]).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    pow2, to_u128, Boolean,
+};
+use crate::ACell;
+
+// 比较两个数的大小：lt = (a < b)
+// 假设 a, b 都 < 2^bits，那么 diff = a - b + lt * 2^bits 一定落在 [0, 2^bits) 里面
+// 如果prover给的 lt 是错的，diff 就会超出这个范围，decompose那边就过不了
+//
+//  a    |  b  | lt | shift(fixed) | selector
+//  diff |     |    |              |
+//
+#[derive(Debug, Clone)]
+pub struct LessThanConfig {
+    pub advice: [Column<Advice>; 3],
+    pub shift: Column<Fixed>,
+    pub selector: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct LessThanChip<F: FieldExt> {
+    config: LessThanConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LessThanChip<F> {
+    pub fn construct(config: LessThanConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        shift: Column<Fixed>,
+    ) -> LessThanConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("less than", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let lt = meta.query_advice(advice[2], Rotation::cur());
+            let diff = meta.query_advice(advice[0], Rotation::next());
+            let shift = meta.query_fixed(shift, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * lt.clone() * (one - lt.clone()),
+                s * (a - b + lt * shift - diff),
+            ]
+        });
+
+        LessThanConfig {
+            advice,
+            shift,
+            selector,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn less_than(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<Boolean<F>, Error> {
+        let (lt, diff) = layouter.assign_region(
+            || "less than",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+                region.assign_fixed(|| "2^bits", self.config.shift, 0, || Ok(pow2::<F>(bits)))?;
+
+                let lt_val =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .map(|(a, b)| to_u128(a) < to_u128(b));
+                let lt = region
+                    .assign_advice(
+                        || "lt",
+                        self.config.advice[2],
+                        0,
+                        || lt_val.map(F::from).ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                let diff_val =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .zip(lt_val)
+                        .map(|((a, b), lt)| {
+                            if lt {
+                                *a - *b + pow2::<F>(bits)
+                            } else {
+                                *a - *b
+                            }
+                        });
+                let diff = region
+                    .assign_advice(
+                        || "diff",
+                        self.config.advice[0],
+                        1,
+                        || diff_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                Ok((Boolean(lt), diff))
+            },
+        )?;
+
+        // diff 必须 < 2^bits
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check diff"), &diff, bits)?;
+
+        Ok(lt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct LessThan {
+        bits: usize,
+    }
+
+    impl TestGadget<Fp> for LessThan {
+        type Config = LessThanConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> LessThanConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            LessThanChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: LessThanConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let lt = LessThanChip::construct(config)
+                .less_than(layouter, &inputs[0], &inputs[1], self.bits)?;
+            Ok(vec![lt.0])
+        }
+    }
+
+    #[test]
+    fn less_than_matches_native() {
+        for (a, b) in [
+            (0u64, 0u64),
+            (0, 1),
+            (1, 0),
+            (3, 200),
+            (200, 3),
+            (255, 255),
+            (254, 255),
+        ] {
+            let expected = Fp::from((a < b) as u64);
+            assert_eq!(
+                run(
+                    5,
+                    LessThan { bits: 8 },
+                    &[Fp::from(a), Fp::from(b)],
+                    &[expected]
+                ),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn less_than_rejects_wrong_flag() {
+        assert!(run(
+            5,
+            LessThan { bits: 8 },
+            &[Fp::from(3), Fp::from(5)],
+            &[Fp::zero()]
+        )
+        .is_err());
+        assert!(run(
+            5,
+            LessThan { bits: 8 },
+            &[Fp::from(5), Fp::from(3)],
+            &[Fp::one()]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn less_than_rejects_operands_wider_than_bits() {
+        // 300 - 2 不在 [0, 2^8) 里面，diff 的range check过不了
+        assert!(run(
+            5,
+            LessThan { bits: 8 },
+            &[Fp::from(300), Fp::from(2)],
+            &[Fp::zero()]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    pow2, to_u128, Boolean,
+};
+use crate::ACell;
+
+// le = (a <= b)，做法和 LessThanChip 一样，只是换了一下diff：
+//   diff = b - a + (1 - le) * 2^bits
+// a, b < 2^bits 的时候，只有正确的 le 才能让 diff 落在 [0, 2^bits) 里面
+//
+//  a    |  b  | le | shift(fixed) | selector
+//  diff |     |    |              |
+//
+#[derive(Debug, Clone)]
+pub struct LessThanOrEqualConfig {
+    pub advice: [Column<Advice>; 3],
+    pub shift: Column<Fixed>,
+    pub selector: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct LessThanOrEqualChip<F: FieldExt> {
+    config: LessThanOrEqualConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LessThanOrEqualChip<F> {
+    pub fn construct(config: LessThanOrEqualConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // shift 这个fixed column同时也被用来放常数（assert的时候要把 le 约束成 1）
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        shift: Column<Fixed>,
+    ) -> LessThanOrEqualConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_constant(shift);
+
+        meta.create_gate("less than or equal", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let le = meta.query_advice(advice[2], Rotation::cur());
+            let diff = meta.query_advice(advice[0], Rotation::next());
+            let shift = meta.query_fixed(shift, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * le.clone() * (one.clone() - le.clone()),
+                s * (b - a + (one - le) * shift - diff),
+            ]
+        });
+
+        LessThanOrEqualConfig {
+            advice,
+            shift,
+            selector,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn less_than_or_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<Boolean<F>, Error> {
+        let (le, diff) = layouter.assign_region(
+            || "less than or equal",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+                region.assign_fixed(|| "2^bits", self.config.shift, 0, || Ok(pow2::<F>(bits)))?;
+
+                let le_val =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .map(|(a, b)| to_u128(a) <= to_u128(b));
+                let le = region
+                    .assign_advice(
+                        || "le",
+                        self.config.advice[2],
+                        0,
+                        || le_val.map(F::from).ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                let diff_val =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .zip(le_val)
+                        .map(|((a, b), le)| {
+                            if le {
+                                *b - *a
+                            } else {
+                                *b - *a + pow2::<F>(bits)
+                            }
+                        });
+                let diff = region
+                    .assign_advice(
+                        || "diff",
+                        self.config.advice[0],
+                        1,
+                        || diff_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                Ok((Boolean(le), diff))
+            },
+        )?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check diff"), &diff, bits)?;
+
+        Ok(le)
+    }
+
+    // 断言 a <= b
+    pub fn assert_less_than_or_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<(), Error> {
+        let le = self.less_than_or_equal(layouter.namespace(|| "a <= b"), a, b, bits)?;
+
+        layouter.assign_region(
+            || "a <= b holds",
+            |mut region| region.constrain_constant(le.0 .0.cell(), F::one()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct LessThanOrEqual {
+        assert_only: bool,
+    }
+
+    impl TestGadget<Fp> for LessThanOrEqual {
+        type Config = LessThanOrEqualConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> LessThanOrEqualConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            LessThanOrEqualChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: LessThanOrEqualConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = LessThanOrEqualChip::construct(config);
+            if self.assert_only {
+                chip.assert_less_than_or_equal(layouter, &inputs[0], &inputs[1], 8)?;
+                return Ok(vec![]);
+            }
+            Ok(vec![
+                chip.less_than_or_equal(layouter, &inputs[0], &inputs[1], 8)?
+                    .0,
+            ])
+        }
+    }
+
+    #[test]
+    fn less_than_or_equal_matches_native() {
+        let g = LessThanOrEqual::default();
+        for (a, b) in [
+            (0u64, 0u64),
+            (1, 0),
+            (0, 1),
+            (255, 255),
+            (255, 254),
+            (7, 200),
+        ] {
+            let expected = Fp::from((a <= b) as u64);
+            assert_eq!(
+                run(5, g.clone(), &[Fp::from(a), Fp::from(b)], &[expected]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn less_than_or_equal_rejects_wrong_flag() {
+        let g = LessThanOrEqual::default();
+        assert!(run(5, g.clone(), &[Fp::from(4), Fp::from(4)], &[Fp::zero()]).is_err());
+        assert!(run(5, g, &[Fp::from(5), Fp::from(4)], &[Fp::one()]).is_err());
+    }
+
+    #[test]
+    fn assert_less_than_or_equal_rejects_greater() {
+        let g = LessThanOrEqual { assert_only: true };
+        assert_eq!(run(5, g.clone(), &[Fp::from(4), Fp::from(4)], &[]), Ok(()));
+        assert!(run(5, g, &[Fp::from(5), Fp::from(4)], &[]).is_err());
+    }
+}
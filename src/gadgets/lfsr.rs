@@ -0,0 +1,218 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    bitwise::{BitwiseChip, BitwiseConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    recompose::{RecomposeChip, RecomposeConfig},
+};
+use crate::ACell;
+
+// LFSR（Fibonacci形式）走一步：
+//   feedback = s[t_0] ^ s[t_1] ^ ...（s[i] 是 state 的第 i 位，taps 是固定的）
+//   state' = ((state << 1) mod 2^bits) | feedback
+// DecomposeChip 把 state 拆成 bits 个bit（同时断言 state < 2^bits），BitwiseChip（bits = 1）把tap的bit XOR 起来，
+// 最后 RecomposeChip 按 [feedback, s_0, ..., s_{bits-2}] 拼回去，最高位 s_{bits-1} 被移出去
+// 全 0 的 state 走一步还是全 0
+#[derive(Debug, Clone)]
+pub struct LfsrConfig {
+    pub decompose: DecomposeConfig,
+    pub bitwise: BitwiseConfig,
+    pub recompose: RecomposeConfig,
+}
+
+pub struct LfsrChip<F: FieldExt> {
+    config: LfsrConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LfsrChip<F> {
+    pub fn construct(config: LfsrConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> LfsrConfig {
+        LfsrConfig {
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            bitwise: BitwiseChip::configure(meta, advice),
+            recompose: RecomposeChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &ACell<F>,
+        taps: &[usize],
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let (first_tap, rest_taps) = taps.split_first().ok_or(Error::Synthesis)?;
+        if taps.iter().any(|&tap| tap >= bits) {
+            return Err(Error::Synthesis);
+        }
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let state_bits = decompose.decompose(layouter.namespace(|| "state bits"), state, bits)?;
+
+        let bitwise = BitwiseChip::construct(self.config.bitwise.clone());
+        let mut feedback = state_bits[*first_tap].0.clone();
+        for &tap in rest_taps {
+            feedback = bitwise.xor(
+                layouter.namespace(|| format!("xor tap {}", tap)),
+                &feedback,
+                &state_bits[tap].0,
+                1,
+            )?;
+        }
+
+        let shifted: Vec<_> = std::iter::once(feedback)
+            .chain(state_bits[..bits - 1].iter().map(|bit| bit.0.clone()))
+            .collect();
+
+        let recompose = RecomposeChip::construct(self.config.recompose.clone());
+        recompose.recompose(layouter.namespace(|| "next state"), &shifted, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [state]，连续走 steps 步，输出每一步之后的 state
+    #[derive(Clone, Default)]
+    struct Lfsr {
+        taps: Vec<usize>,
+        bits: usize,
+        steps: usize,
+    }
+
+    impl TestGadget<Fp> for Lfsr {
+        type Config = LfsrConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> LfsrConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            LfsrChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: LfsrConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = LfsrChip::construct(config);
+            let mut state = inputs[0].clone();
+            let mut outputs = vec![];
+            for i in 0..self.steps {
+                state = chip.step(
+                    layouter.namespace(|| format!("step {}", i)),
+                    &state,
+                    &self.taps,
+                    self.bits,
+                )?;
+                outputs.push(state.clone());
+            }
+            Ok(outputs)
+        }
+    }
+
+    fn native_step(state: u64, taps: &[usize], bits: usize) -> u64 {
+        let feedback = taps.iter().fold(0, |acc, &t| acc ^ ((state >> t) & 1));
+        ((state << 1) & ((1 << bits) - 1)) | feedback
+    }
+
+    fn native_sequence(seed: u64, taps: &[usize], bits: usize, steps: usize) -> Vec<u64> {
+        std::iter::successors(Some(seed), |&s| Some(native_step(s, taps, bits)))
+            .skip(1)
+            .take(steps)
+            .collect()
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn lfsr_matches_native() {
+        // x^16 + x^14 + x^13 + x^11 + 1
+        let taps = vec![15, 13, 12, 10];
+        let expected = native_sequence(0xace1, &taps, 16, 8);
+        let g = Lfsr {
+            taps,
+            bits: 16,
+            steps: 8,
+        };
+        assert_eq!(run(10, g, &fp(&[0xace1]), &fp(&expected)), Ok(()));
+    }
+
+    #[test]
+    fn lfsr_maximal_period() {
+        // x^4 + x^3 + 1 是本原多项式，非零的state走 15 步回到原点
+        let taps = vec![3, 2];
+        let expected = native_sequence(0b1001, &taps, 4, 15);
+        assert_eq!(expected.last(), Some(&0b1001));
+        let g = Lfsr {
+            taps,
+            bits: 4,
+            steps: 15,
+        };
+        assert_eq!(run(9, g, &fp(&[0b1001]), &fp(&expected)), Ok(()));
+    }
+
+    #[test]
+    fn lfsr_zero_state_stays_zero() {
+        let g = Lfsr {
+            taps: vec![3, 2],
+            bits: 4,
+            steps: 3,
+        };
+        assert_eq!(run(9, g, &fp(&[0]), &fp(&[0, 0, 0])), Ok(()));
+    }
+
+    #[test]
+    fn lfsr_rejects_wrong_output() {
+        let g = Lfsr {
+            taps: vec![3, 2],
+            bits: 4,
+            steps: 1,
+        };
+        // 1001 -> 0011；漏掉 feedback，或者最高位没有移出去
+        assert_eq!(run(9, g.clone(), &fp(&[0b1001]), &fp(&[0b0011])), Ok(()));
+        assert!(run(9, g.clone(), &fp(&[0b1001]), &fp(&[0b0010])).is_err());
+        assert!(run(9, g.clone(), &fp(&[0b1001]), &fp(&[0b10011])).is_err());
+        // state 本身超出了 bits 位
+        assert!(run(9, g, &fp(&[0b11001]), &fp(&[0b0011])).is_err());
+    }
+
+    #[test]
+    fn lfsr_rejects_bad_taps() {
+        let g = Lfsr {
+            taps: vec![4],
+            bits: 4,
+            steps: 1,
+        };
+        assert!(synthesis_fails(9, g, &fp(&[1]), &fp(&[2])));
+        let g = Lfsr {
+            taps: vec![],
+            bits: 4,
+            steps: 1,
+        };
+        assert!(synthesis_fails(9, g, &fp(&[1]), &fp(&[2])));
+    }
+}
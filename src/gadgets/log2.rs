@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::to_u128;
+use crate::ACell;
+
+// 用lookup求 floor(log2(x))，x 在 [0, 2^k) 里面
+// table里面放的其实是 (x, bitlen(x))，而 bitlen(x) = floor(log2(x)) + 1
+// lookup的时候约束 (x, out + 1) 在table里面，这样做有两个好处：
+// * selector关掉的时候lookup的是 (0, 0)，正好就是table里面 x = 0 那一行
+// * x = 0 的时候 out + 1 = 0，也就是 out = -1（p - 1），我们把它当作 log2(0) 的sentinel
+//
+//  x | out | q_lookup
+//
+#[derive(Debug, Clone)]
+pub struct Log2Config {
+    pub advice: [Column<Advice>; 2],
+    pub q_lookup: Selector,
+    pub table_x: TableColumn,
+    pub table_bitlen: TableColumn,
+}
+
+pub struct Log2Chip<F: FieldExt> {
+    config: Log2Config,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Log2Chip<F> {
+    pub fn construct(config: Log2Config) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> Log2Config {
+        let q_lookup = meta.complex_selector();
+        let table_x = meta.lookup_table_column();
+        let table_bitlen = meta.lookup_table_column();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let out = meta.query_advice(advice[1], Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![(q.clone() * x, table_x), (q * (out + one), table_bitlen)]
+        });
+
+        Log2Config {
+            advice,
+            q_lookup,
+            table_x,
+            table_bitlen,
+        }
+    }
+
+    // table一共 2^k 行，k 不要太大
+    pub fn load(&self, mut layouter: impl Layouter<F>, k: usize) -> Result<(), Error> {
+        layouter.assign_table(
+            || "log2 table",
+            |mut table| {
+                for x in 0..(1u64 << k) {
+                    let offset = x as usize;
+                    table.assign_cell(|| "x", self.config.table_x, offset, || Ok(F::from(x)))?;
+                    table.assign_cell(
+                        || "bitlen",
+                        self.config.table_bitlen,
+                        offset,
+                        || Ok(F::from((64 - x.leading_zeros()) as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn log2(&self, mut layouter: impl Layouter<F>, x: &ACell<F>) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "log2",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                x.0.copy_advice(|| "x", &mut region, self.config.advice[0], 0)?;
+
+                let out_val = x.0.value().map(|x| {
+                    let bitlen = 128 - to_u128(x).leading_zeros() as u64;
+                    F::from(bitlen) - F::one()
+                });
+
+                region
+                    .assign_advice(
+                        || "log2(x)",
+                        self.config.advice[1],
+                        0,
+                        || out_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // table 只放 [0, 2^6)
+    #[derive(Clone, Default)]
+    struct Log2;
+
+    impl TestGadget<Fp> for Log2 {
+        type Config = Log2Config;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Log2Config {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            Log2Chip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: Log2Config,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = Log2Chip::construct(config);
+            chip.load(layouter.namespace(|| "table"), 6)?;
+            inputs
+                .iter()
+                .map(|x| chip.log2(layouter.namespace(|| "log2"), x))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn log2_matches_native() {
+        let xs: Vec<u64> = (1..64).collect();
+        let inputs: Vec<_> = xs.iter().map(|x| Fp::from(*x)).collect();
+        let expected: Vec<_> = xs.iter().map(|x| Fp::from(x.ilog2() as u64)).collect();
+        assert_eq!(run(8, Log2, &inputs, &expected), Ok(()));
+    }
+
+    #[test]
+    fn log2_of_zero_is_minus_one() {
+        assert_eq!(run(7, Log2, &[Fp::zero()], &[-Fp::one()]), Ok(()));
+    }
+
+    #[test]
+    fn log2_rejects_wrong_output() {
+        assert!(run(7, Log2, &[Fp::from(9)], &[Fp::from(2)]).is_err());
+    }
+
+    #[test]
+    fn log2_rejects_value_outside_table() {
+        assert!(run(7, Log2, &[Fp::from(64)], &[Fp::from(6)]).is_err());
+    }
+}
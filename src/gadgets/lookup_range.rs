@@ -0,0 +1,259 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{pow2, to_u128};
+use crate::ACell;
+
+// lookup table里面放的是 [0, 2^LIMB_BITS)
+pub const LIMB_BITS: usize = 8;
+
+// 用lookup做range check：x < 2^bits
+// 把 x 拆成若干个 LIMB_BITS 位的limb，每个limb都在table里面lookup，
+// 和 DecomposeChip 一样从最高的limb开始累加：acc = 2^LIMB_BITS * acc_prev + limb，最后一行的 acc 就是 x
+// bits 不是 LIMB_BITS 的倍数的时候，最高的limb只有 r 位，所以再lookup一次 limb * 2^(LIMB_BITS - r)：
+// 两次lookup都过了，limb 才一定 < 2^r。其它行的 shift 就是 1，相当于同一个值查两次
+// selector关掉的时候lookup的是 0，table里面本来就有
+//
+//  limb      |  acc  | shift(fixed) | q_first | q_step | q_lookup
+//  l_{n-1}   |  ...  | 2^(LIMB - r) |    1    |   0    |    1
+//  ...       |  ...  |      1       |    0    |   1    |    1
+//  l_0       |   x   |      1       |    0    |   1    |    1
+//
+#[derive(Debug, Clone)]
+pub struct LookupRangeCheckConfig {
+    pub advice: [Column<Advice>; 2],
+    pub shift: Column<Fixed>,
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub q_lookup: Selector,
+    pub table: TableColumn,
+}
+
+pub struct LookupRangeCheckChip<F: FieldExt> {
+    config: LookupRangeCheckConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LookupRangeCheckChip<F> {
+    pub fn construct(config: LookupRangeCheckConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        shift: Column<Fixed>,
+    ) -> LookupRangeCheckConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+        let q_lookup = meta.complex_selector();
+        let table = meta.lookup_table_column();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        meta.create_gate("lookup range check first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let limb = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![q_first * (acc - limb)]
+        });
+
+        meta.create_gate("lookup range check step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let limb = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[1], Rotation::prev());
+
+            vec![q_step * (acc - (acc_prev * pow2::<F>(LIMB_BITS) + limb))]
+        });
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let limb = meta.query_advice(advice[0], Rotation::cur());
+
+            vec![(q * limb, table)]
+        });
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let limb = meta.query_advice(advice[0], Rotation::cur());
+            let shift = meta.query_fixed(shift, Rotation::cur());
+
+            vec![(q * limb * shift, table)]
+        });
+
+        LookupRangeCheckConfig {
+            advice,
+            shift,
+            q_first,
+            q_step,
+            q_lookup,
+            table,
+        }
+    }
+
+    pub fn load(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "range table",
+            |mut table| {
+                for value in 0..(1u64 << LIMB_BITS) {
+                    table.assign_cell(
+                        || "value",
+                        self.config.table,
+                        value as usize,
+                        || Ok(F::from(value)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn range_check(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bits: usize,
+    ) -> Result<(), Error> {
+        if bits == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        let num_limbs = bits.div_ceil(LIMB_BITS);
+        // 最高的limb有几位
+        let top_bits = bits - (num_limbs - 1) * LIMB_BITS;
+
+        layouter.assign_region(
+            || "lookup range check",
+            |mut region| {
+                let x_val = x.0.value().map(to_u128);
+                let mut acc_val = Some(F::zero());
+
+                for row in 0..num_limbs {
+                    // 第 row 行放的是第 i 个limb（从最高的往下）
+                    let i = num_limbs - 1 - row;
+                    let limb_val = x_val.map(|x| {
+                        let shift = i * LIMB_BITS;
+                        if shift < 128 {
+                            F::from(((x >> shift) & ((1 << LIMB_BITS) - 1)) as u64)
+                        } else {
+                            F::zero()
+                        }
+                    });
+                    acc_val = acc_val
+                        .zip(limb_val)
+                        .map(|(acc, limb)| acc * pow2::<F>(LIMB_BITS) + limb);
+
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+                    self.config.q_lookup.enable(&mut region, row)?;
+
+                    let shift = if row == 0 {
+                        pow2::<F>(LIMB_BITS - top_bits)
+                    } else {
+                        F::one()
+                    };
+                    region.assign_fixed(|| "shift", self.config.shift, row, || Ok(shift))?;
+
+                    region.assign_advice(
+                        || format!("limb {}", i),
+                        self.config.advice[0],
+                        row,
+                        || limb_val.ok_or(Error::Synthesis),
+                    )?;
+
+                    if row == num_limbs - 1 {
+                        x.0.copy_advice(|| "x", &mut region, self.config.advice[1], row)?;
+                    } else {
+                        region.assign_advice(
+                            || "acc",
+                            self.config.advice[1],
+                            row,
+                            || acc_val.ok_or(Error::Synthesis),
+                        )?;
+                    }
+                }
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct LookupRange {
+        bits: usize,
+    }
+
+    impl TestGadget<Fp> for LookupRange {
+        type Config = LookupRangeCheckConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> LookupRangeCheckConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            let shift = meta.fixed_column();
+            LookupRangeCheckChip::configure(meta, advice, shift)
+        }
+
+        fn synthesize(
+            &self,
+            config: LookupRangeCheckConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = LookupRangeCheckChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+            chip.range_check(layouter.namespace(|| "range check"), &inputs[0], self.bits)?;
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn lookup_range_matches_native() {
+        // 8 的倍数，以及最高的limb不满的情况
+        for bits in [1usize, 3, 8, 12, 16, 20] {
+            for x in [
+                0u64,
+                1,
+                5,
+                255,
+                256,
+                4095,
+                4096,
+                65535,
+                65536,
+                (1 << 20) - 1,
+            ] {
+                let result = run(9, LookupRange { bits }, &[Fp::from(x)], &[]);
+                assert_eq!(result.is_ok(), x < 1 << bits, "x = {}, bits = {}", x, bits);
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_range_rejects_field_wraparound() {
+        // -1 的低 128 位拆出来的limb都在table里面，但是拼回去不等于 x
+        assert!(run(9, LookupRange { bits: 16 }, &[-Fp::one()], &[]).is_err());
+        assert!(synthesis_fails(
+            9,
+            LookupRange { bits: 0 },
+            &[Fp::zero()],
+            &[]
+        ));
+    }
+}
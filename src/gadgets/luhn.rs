@@ -0,0 +1,229 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    base_b::{BaseBChip, BaseBConfig},
+    constant::{ConstantChip, ConstantConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    modulo::{ModChip, ModConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    mux::{MuxChip, MuxConfig},
+    prefix_sum::{PrefixSumChip, PrefixSumConfig},
+    sub::{SubChip, SubConfig},
+};
+use crate::ACell;
+
+// Luhn 校验（信用卡号那种）：digits 按书写顺序给出，最后一位是校验位
+// 从右往左数，第 2, 4, 6... 位要乘 2，乘完 > 9 的话再减 9：
+//   doubled = 2 * d
+//   d' = (9 < doubled) ? doubled - 9 : doubled
+// 所有 d' 加起来要能被 10 整除
+// 每一位都先用 BaseBChip 拆成 1 个十进制digit，也就是断言 d < 10，所以 doubled <= 18 < 2^5
+// 只有一位的时候没有要乘 2 的位，只有 "0" 是合法的
+#[derive(Debug, Clone)]
+pub struct LuhnConfig {
+    pub base_b: BaseBConfig,
+    pub constant: ConstantConfig,
+    pub mul_const: MulConstConfig,
+    pub less_than: LessThanConfig,
+    pub sub: SubConfig,
+    pub mux: MuxConfig,
+    pub prefix_sum: PrefixSumConfig,
+    pub modulo: ModConfig,
+}
+
+pub struct LuhnChip<F: FieldExt> {
+    config: LuhnConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LuhnChip<F> {
+    pub fn construct(config: LuhnConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> LuhnConfig {
+        LuhnConfig {
+            base_b: BaseBChip::configure(meta, advice, fixed),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            sub: SubChip::configure(meta, advice),
+            mux: MuxChip::configure(meta, advice),
+            prefix_sum: PrefixSumChip::configure(meta, [advice[0], advice[1]]),
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn assert_luhn_valid(
+        &self,
+        mut layouter: impl Layouter<F>,
+        digits: &[ACell<F>],
+    ) -> Result<(), Error> {
+        if digits.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        let base_b = BaseBChip::construct(self.config.base_b.clone());
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let sub = SubChip::construct(self.config.sub.clone());
+        let mux = MuxChip::construct(self.config.mux.clone());
+
+        let nine = constant.load_constant(layouter.namespace(|| "nine"), F::from(9))?;
+
+        let mut terms = Vec::with_capacity(digits.len());
+        for (i, digit) in digits.iter().rev().enumerate() {
+            base_b.decompose(
+                layouter.namespace(|| format!("digit {} < 10", i)),
+                digit,
+                10,
+                1,
+            )?;
+
+            if i % 2 == 0 {
+                terms.push(digit.clone());
+                continue;
+            }
+
+            let doubled = mul_const.mul_const(
+                layouter.namespace(|| format!("2 * d_{}", i)),
+                digit,
+                F::from(2),
+            )?;
+            let over = less_than.less_than(
+                layouter.namespace(|| format!("9 < 2 * d_{}", i)),
+                &nine,
+                &doubled,
+                5,
+            )?;
+            let reduced = sub.sub(
+                layouter.namespace(|| format!("2 * d_{} - 9", i)),
+                &doubled,
+                &nine,
+            )?;
+            terms.push(mux.mux(
+                layouter.namespace(|| format!("term {}", i)),
+                &over,
+                &reduced,
+                &doubled,
+            )?);
+        }
+
+        let prefix_sum = PrefixSumChip::construct(self.config.prefix_sum.clone());
+        let sums = prefix_sum.prefix_sum(layouter.namespace(|| "sum"), &terms)?;
+
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        let r = modulo.modulo(layouter.namespace(|| "sum % 10"), sums.last().unwrap(), 10)?;
+
+        layouter.assign_region(
+            || "sum % 10 == 0",
+            |mut region| region.constrain_constant(r.0.cell(), F::zero()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Luhn;
+
+    impl TestGadget<Fp> for Luhn {
+        type Config = LuhnConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> LuhnConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            LuhnChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: LuhnConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            LuhnChip::construct(config).assert_luhn_valid(layouter, inputs)?;
+            Ok(vec![])
+        }
+    }
+
+    fn luhn_valid(digits: &[u64]) -> bool {
+        let sum: u64 = digits
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, d)| match (i % 2, 2 * d) {
+                (0, _) => *d,
+                (_, doubled) if doubled > 9 => doubled - 9,
+                (_, doubled) => doubled,
+            })
+            .sum();
+        sum.is_multiple_of(10)
+    }
+
+    fn digits(number: &str) -> Vec<u64> {
+        number.bytes().map(|b| (b - b'0') as u64).collect()
+    }
+
+    fn fp(digits: &[u64]) -> Vec<Fp> {
+        digits.iter().map(|d| Fp::from(*d)).collect()
+    }
+
+    #[test]
+    fn luhn_accepts_valid_numbers() {
+        for number in [
+            "79927398713",
+            "4111111111111111",
+            "5555555555554444",
+            "0",
+            "18",
+            "59",
+        ] {
+            let digits = digits(number);
+            assert!(luhn_valid(&digits), "{}", number);
+            assert_eq!(run(11, Luhn, &fp(&digits), &[]), Ok(()), "{}", number);
+        }
+    }
+
+    #[test]
+    fn luhn_rejects_invalid_numbers() {
+        // 改一位，或者交换相邻两位
+        for number in [
+            "79927398710",
+            "4111111111111112",
+            "5555555555554445",
+            "7",
+            "81",
+        ] {
+            let digits = digits(number);
+            assert!(!luhn_valid(&digits), "{}", number);
+            assert!(run(11, Luhn, &fp(&digits), &[]).is_err(), "{}", number);
+        }
+    }
+
+    #[test]
+    fn luhn_rejects_non_digits() {
+        // 2 * 4 + 12 = 20 能被 10 整除，但是 12 不是一个十进制digit
+        assert!(run(11, Luhn, &fp(&[4, 12]), &[]).is_err());
+        assert!(synthesis_fails(11, Luhn, &[], &[]));
+    }
+}
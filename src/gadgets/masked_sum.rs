@@ -0,0 +1,200 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+use crate::ACell;
+
+// masked sum：sum = Σ mask_i * value_i，mask 是boolean
+// 和 IndexSelectChip 一样是一个running sum，只是不要求mask是 one-hot 的
+//
+//  mask | value | acc                   | q_first | q_step
+//  m_0  |  v_0  | m_0 * v_0             |    1    |   0
+//  m_1  |  v_1  | acc_prev + m_1 * v_1  |    0    |   1
+//
+#[derive(Debug, Clone)]
+pub struct MaskedSumConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_first: Selector,
+    pub q_step: Selector,
+}
+
+pub struct MaskedSumChip<F: FieldExt> {
+    config: MaskedSumConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MaskedSumChip<F> {
+    pub fn construct(config: MaskedSumConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> MaskedSumConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("masked sum first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let mask = meta.query_advice(advice[0], Rotation::cur());
+            let value = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_first.clone() * mask.clone() * (one - mask.clone()),
+                q_first * (acc - mask * value),
+            ]
+        });
+
+        meta.create_gate("masked sum step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let mask = meta.query_advice(advice[0], Rotation::cur());
+            let value = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[2], Rotation::prev());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_step.clone() * mask.clone() * (one - mask.clone()),
+                q_step * (acc - (acc_prev + mask * value)),
+            ]
+        });
+
+        MaskedSumConfig {
+            advice,
+            q_first,
+            q_step,
+        }
+    }
+
+    // masks 和 values 长度必须一样，而且不能是空的
+    pub fn masked_sum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        masks: &[Boolean<F>],
+        values: &[ACell<F>],
+    ) -> Result<ACell<F>, Error> {
+        if masks.len() != values.len() || masks.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "masked sum",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+                let mut acc = None;
+
+                for (row, (mask, value)) in masks.iter().zip(values.iter()).enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    let mask = &mask.0;
+                    mask.0
+                        .copy_advice(|| "mask", &mut region, self.config.advice[0], row)?;
+                    value
+                        .0
+                        .copy_advice(|| "value", &mut region, self.config.advice[1], row)?;
+
+                    acc_val = acc_val
+                        .zip(mask.0.value())
+                        .zip(value.0.value())
+                        .map(|((acc, m), v)| acc + *m * *v);
+                    acc = Some(
+                        region
+                            .assign_advice(
+                                || "acc",
+                                self.config.advice[2],
+                                row,
+                                || acc_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?,
+                    );
+                }
+
+                acc.ok_or(Error::Synthesis)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入的前一半是 masks，后一半是 values
+    #[derive(Clone, Default)]
+    struct MaskedSum;
+
+    impl TestGadget<Fp> for MaskedSum {
+        type Config = MaskedSumConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MaskedSumConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            MaskedSumChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: MaskedSumConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (masks, values) = inputs.split_at(inputs.len() / 2);
+            let masks: Vec<_> = masks.iter().cloned().map(Boolean).collect();
+            Ok(vec![
+                MaskedSumChip::construct(config).masked_sum(layouter, &masks, values)?
+            ])
+        }
+    }
+
+    fn inputs(masks: &[u64], values: &[u64]) -> Vec<Fp> {
+        masks.iter().chain(values).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn masked_sum_matches_native() {
+        let values = [3u64, 5, 7, 11];
+        for masks in [[0u64, 0, 0, 0], [1, 0, 1, 0], [1, 1, 1, 1], [0, 0, 0, 1]] {
+            let sum: u64 = masks.iter().zip(values).map(|(m, v)| m * v).sum();
+            assert_eq!(
+                run(5, MaskedSum, &inputs(&masks, &values), &[Fp::from(sum)]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn masked_sum_rejects_wrong_sum() {
+        assert!(run(5, MaskedSum, &inputs(&[1, 0], &[3, 5]), &[Fp::from(8)]).is_err());
+    }
+
+    #[test]
+    fn masked_sum_rejects_non_boolean_mask() {
+        assert!(run(5, MaskedSum, &inputs(&[2, 0], &[3, 5]), &[Fp::from(6)]).is_err());
+    }
+
+    #[test]
+    fn masked_sum_rejects_empty_input() {
+        assert!(synthesis_fails(5, MaskedSum, &[], &[]));
+    }
+}
@@ -0,0 +1,195 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::bitwise::{BitwiseChip, BitwiseConfig};
+use crate::ACell;
+
+// 布尔矩阵的平方：N[i][j] = OR_k (M[i][k] AND M[k][j])
+// 也就是图的邻接矩阵走两步之后的可达性
+// AND / OR 都用 BitwiseChip（bits = 1），顺便也就检查了 M 的每个entry都是 0 或 1
+#[derive(Debug, Clone)]
+pub struct MatrixSquareConfig {
+    pub bitwise: BitwiseConfig,
+}
+
+pub struct MatrixSquareChip<F: FieldExt> {
+    config: MatrixSquareConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MatrixSquareChip<F> {
+    pub fn construct(config: MatrixSquareConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> MatrixSquareConfig {
+        MatrixSquareConfig {
+            bitwise: BitwiseChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_matrix_square(
+        &self,
+        mut layouter: impl Layouter<F>,
+        m: &[Vec<ACell<F>>],
+        n: &[Vec<ACell<F>>],
+    ) -> Result<(), Error> {
+        let size = m.len();
+        let square = |x: &[Vec<ACell<F>>]| x.len() == size && x.iter().all(|row| row.len() == size);
+        if !square(m) || !square(n) {
+            return Err(Error::Synthesis);
+        }
+
+        let bitwise = BitwiseChip::construct(self.config.bitwise.clone());
+        for (i, row) in m.iter().enumerate() {
+            for j in 0..size {
+                let mut acc: Option<ACell<F>> = None;
+                for (k, m_ik) in row.iter().enumerate() {
+                    let term = bitwise.and(
+                        layouter.namespace(|| format!("M[{}][{}] & M[{}][{}]", i, k, k, j)),
+                        m_ik,
+                        &m[k][j],
+                        1,
+                    )?;
+                    acc = Some(match acc {
+                        Some(acc) => bitwise.or(
+                            layouter.namespace(|| format!("N[{}][{}]", i, j)),
+                            &acc,
+                            &term,
+                            1,
+                        )?,
+                        None => term,
+                    });
+                }
+
+                if let Some(acc) = acc {
+                    layouter.assign_region(
+                        || format!("N[{}][{}]", i, j),
+                        |mut region| region.constrain_equal(acc.0.cell(), n[i][j].0.cell()),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入的前一半是 M（按行展开），后一半是 N
+    #[derive(Clone, Default)]
+    struct MatrixSquare {
+        size: usize,
+    }
+
+    impl TestGadget<Fp> for MatrixSquare {
+        type Config = MatrixSquareConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MatrixSquareConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            MatrixSquareChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: MatrixSquareConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (m, n) = inputs.split_at(inputs.len() / 2);
+            let rows =
+                |x: &[ACell<Fp>]| x.chunks(self.size).map(|r| r.to_vec()).collect::<Vec<_>>();
+            MatrixSquareChip::construct(config).assert_matrix_square(
+                layouter,
+                &rows(m),
+                &rows(n),
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    fn square(m: &[u64], size: usize) -> Vec<u64> {
+        (0..size * size)
+            .map(|idx| {
+                let (i, j) = (idx / size, idx % size);
+                (0..size).any(|k| m[i * size + k] == 1 && m[k * size + j] == 1) as u64
+            })
+            .collect()
+    }
+
+    fn inputs(m: &[u64], n: &[u64]) -> Vec<Fp> {
+        m.iter().chain(n).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn matrix_square_matches_native() {
+        let cases: [(usize, Vec<u64>); 5] = [
+            // 单位矩阵
+            (3, vec![1, 0, 0, 0, 1, 0, 0, 0, 1]),
+            // 全零
+            (3, vec![0; 9]),
+            // 路径 0 -> 1 -> 2
+            (3, vec![0, 1, 0, 0, 0, 1, 0, 0, 0]),
+            (2, vec![1, 1, 1, 1]),
+            (1, vec![1]),
+        ];
+        for (size, m) in cases {
+            let n = square(&m, size);
+            assert_eq!(
+                run(8, MatrixSquare { size }, &inputs(&m, &n), &[]),
+                Ok(()),
+                "M = {:?}",
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn matrix_square_rejects_wrong_product() {
+        let m = [0u64, 1, 0, 0, 0, 1, 0, 0, 0];
+        let mut n = square(&m, 3);
+        // 0 -> 2 两步可达，说成不可达
+        n[2] = 0;
+        assert!(run(8, MatrixSquare { size: 3 }, &inputs(&m, &n), &[]).is_err());
+
+        // 把 M 本身当成 M²
+        assert!(run(8, MatrixSquare { size: 3 }, &inputs(&m, &m), &[]).is_err());
+    }
+
+    #[test]
+    fn matrix_square_rejects_non_boolean_entries() {
+        // entry 是 2 的话 AND 的 1-bit 分解就不成立
+        let m = [2u64, 0, 0, 1];
+        let n = [1u64, 0, 0, 1];
+        assert!(run(8, MatrixSquare { size: 2 }, &inputs(&m, &n), &[]).is_err());
+    }
+
+    #[test]
+    fn matrix_square_rejects_non_square() {
+        // 2x2 的数据按 3 一行切开就不是方阵了
+        let m = [1u64, 0, 0, 1];
+        assert!(synthesis_fails(
+            8,
+            MatrixSquare { size: 3 },
+            &inputs(&m, &m),
+            &[]
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    permutation_check::{PermutationCheckChip, PermutationCheckConfig},
+    sorted::{SortedChip, SortedConfig},
+};
+use crate::ACell;
+
+// 证明 c 是两个sorted数组 a 和 b 的归并结果
+// 只需要两件事：
+// * c 是sorted的（SortedChip）
+// * c 是 a ++ b 的一个permutation（PermutationCheckChip）
+// a 和 b 本身是否sorted并不影响结论，这里不再重复检查
+#[derive(Debug, Clone)]
+pub struct MergeConfig {
+    pub sorted: SortedConfig,
+    pub permutation: PermutationCheckConfig,
+    pub bits: usize,
+}
+
+pub struct MergeChip<F: FieldExt> {
+    config: MergeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MergeChip<F> {
+    pub fn construct(config: MergeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // 所有value都需要 < 2^bits
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        bits: usize,
+    ) -> MergeConfig {
+        MergeConfig {
+            sorted: SortedChip::configure(meta, [advice[0], advice[1]]),
+            permutation: PermutationCheckChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    pub fn assert_merge(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[ACell<F>],
+        b: &[ACell<F>],
+        c: &[ACell<F>],
+        gamma: &ACell<F>,
+    ) -> Result<(), Error> {
+        let sorted = SortedChip::construct(self.config.sorted.clone());
+        sorted.assert_sorted(layouter.namespace(|| "c is sorted"), c, self.config.bits)?;
+
+        let a_b: Vec<ACell<F>> = a.iter().chain(b.iter()).cloned().collect();
+        let permutation = PermutationCheckChip::construct(self.config.permutation.clone());
+        permutation.assert_permutation(
+            layouter.namespace(|| "c is a permutation of a ++ b"),
+            &a_b,
+            c,
+            gamma,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 a ++ b ++ c ++ [gamma]
+    #[derive(Clone, Default)]
+    struct Merge {
+        a_len: usize,
+        b_len: usize,
+    }
+
+    impl TestGadget<Fp> for Merge {
+        type Config = MergeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MergeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            MergeChip::configure(meta, advice, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: MergeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (gamma, values) = inputs.split_last().unwrap();
+            let (a, rest) = values.split_at(self.a_len);
+            let (b, c) = rest.split_at(self.b_len);
+            MergeChip::construct(config).assert_merge(layouter, a, b, c, gamma)?;
+            Ok(vec![])
+        }
+    }
+
+    fn check(a: &[u64], b: &[u64], c: &[u64]) -> bool {
+        let inputs: Vec<Fp> = a
+            .iter()
+            .chain(b)
+            .chain(c)
+            .map(|v| Fp::from(*v))
+            .chain([Fp::from(0xdead_beef)])
+            .collect();
+        let g = Merge {
+            a_len: a.len(),
+            b_len: b.len(),
+        };
+        run(8, g, &inputs, &[]).is_ok()
+    }
+
+    #[test]
+    fn merge_accepts_native_merge() {
+        let (a, b) = ([1u64, 4, 4, 9], [2u64, 4, 10]);
+        let mut c: Vec<u64> = a.iter().chain(b.iter()).cloned().collect();
+        c.sort();
+        assert!(check(&a, &b, &c));
+        assert!(check(&[], &[1, 2], &[1, 2]));
+    }
+
+    #[test]
+    fn merge_rejects_unsorted_output() {
+        assert!(!check(&[1, 3], &[2], &[1, 3, 2]));
+    }
+
+    #[test]
+    fn merge_rejects_dropped_element() {
+        // 排好序了但是把 3 换成了 2
+        assert!(!check(&[1, 3], &[2], &[1, 2, 2]));
+    }
+}
@@ -0,0 +1,196 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::less_than::{LessThanChip, LessThanConfig};
+use crate::ACell;
+
+// 取两个数的 min 和 max
+// 先用 LessThanChip 算出 lt = (a < b)，然后：
+//   min = lt * a + (1 - lt) * b
+//   max = a + b - min
+//
+//  a   |  b  | lt | selector
+//  min | max |    |
+//
+#[derive(Debug, Clone)]
+pub struct MinMaxConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    pub less_than: LessThanConfig,
+}
+
+pub struct MinMaxChip<F: FieldExt> {
+    config: MinMaxConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MinMaxChip<F> {
+    pub fn construct(config: MinMaxConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> MinMaxConfig {
+        let selector = meta.selector();
+        let less_than = LessThanChip::configure(meta, advice, fixed);
+
+        meta.create_gate("min max", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let lt = meta.query_advice(advice[2], Rotation::cur());
+            let min = meta.query_advice(advice[0], Rotation::next());
+            let max = meta.query_advice(advice[1], Rotation::next());
+
+            vec![
+                s.clone() * (min.clone() - (b.clone() + lt * (a.clone() - b.clone()))),
+                s * (max - (a + b - min)),
+            ]
+        });
+
+        MinMaxConfig {
+            advice,
+            selector,
+            less_than,
+        }
+    }
+
+    // 返回 (min, max)，a 和 b 都需要 < 2^bits
+    pub fn min_max(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let lt = less_than.less_than(layouter.namespace(|| "a < b"), a, b, bits)?;
+
+        layouter.assign_region(
+            || "min max",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+                lt.0 .0
+                    .copy_advice(|| "lt", &mut region, self.config.advice[2], 0)?;
+
+                let min_val =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .zip(lt.value())
+                        .map(|((a, b), lt)| if lt { *a } else { *b });
+                let max_val =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .zip(lt.value())
+                        .map(|((a, b), lt)| if lt { *b } else { *a });
+
+                let min = region
+                    .assign_advice(
+                        || "min",
+                        self.config.advice[0],
+                        1,
+                        || min_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+                let max = region
+                    .assign_advice(
+                        || "max",
+                        self.config.advice[1],
+                        1,
+                        || max_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                Ok((min, max))
+            },
+        )
+    }
+
+    pub fn min(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        self.min_max(layouter, a, b, bits).map(|(min, _)| min)
+    }
+
+    pub fn max(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        self.min_max(layouter, a, b, bits).map(|(_, max)| max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct MinMax;
+
+    impl TestGadget<Fp> for MinMax {
+        type Config = MinMaxConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MinMaxConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            MinMaxChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: MinMaxConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (min, max) =
+                MinMaxChip::construct(config).min_max(layouter, &inputs[0], &inputs[1], 8)?;
+            Ok(vec![min, max])
+        }
+    }
+
+    #[test]
+    fn min_max_matches_native() {
+        for (a, b) in [(0u64, 0u64), (1, 9), (9, 1), (255, 0), (17, 17)] {
+            let expected = [Fp::from(a.min(b)), Fp::from(a.max(b))];
+            assert_eq!(
+                run(5, MinMax, &[Fp::from(a), Fp::from(b)], &expected),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn min_max_rejects_swapped_outputs() {
+        assert!(run(
+            5,
+            MinMax,
+            &[Fp::from(1), Fp::from(9)],
+            &[Fp::from(9), Fp::from(1)]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    byte_xor::{ByteXorChip, ByteXorConfig},
+    constant::{ConstantChip, ConstantConfig},
+    gf256::{Gf256MulChip, Gf256MulConfig},
+};
+use crate::ACell;
+
+// AES 的 MixColumns，作用在一列 4 个byte上（GF(2^8) 里面乘一个固定的circulant矩阵）：
+//   out_i = 2 * a_i ^ 3 * a_{i+1} ^ a_{i+2} ^ a_{i+3}（下标 mod 4）
+// 3 * a = 2 * a ^ a，所以每个byte只需要用 Gf256MulChip 乘一次 2，剩下的都是 ByteXorChip
+// 全 0 的列还是全 0
+// 用之前要先调用 load 把 gf256 mul table 和 xor table 都放进电路
+#[derive(Debug, Clone)]
+pub struct MixColumnsConfig {
+    pub constant: ConstantConfig,
+    pub gf256_mul: Gf256MulConfig,
+    pub byte_xor: ByteXorConfig,
+}
+
+pub struct MixColumnsChip<F: FieldExt> {
+    config: MixColumnsConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MixColumnsChip<F> {
+    pub fn construct(config: MixColumnsConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> MixColumnsConfig {
+        MixColumnsConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            gf256_mul: Gf256MulChip::configure(meta, advice),
+            byte_xor: ByteXorChip::configure(meta, advice),
+        }
+    }
+
+    pub fn load(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        Gf256MulChip::construct(self.config.gf256_mul.clone())
+            .load(layouter.namespace(|| "gf256 mul table"))?;
+        ByteXorChip::construct(self.config.byte_xor.clone())
+            .load(layouter.namespace(|| "xor table"))
+    }
+
+    pub fn mix_column(
+        &self,
+        mut layouter: impl Layouter<F>,
+        col: &[ACell<F>; 4],
+    ) -> Result<[ACell<F>; 4], Error> {
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let two = constant.load_constant(layouter.namespace(|| "2"), F::from(2))?;
+
+        let gf256_mul = Gf256MulChip::construct(self.config.gf256_mul.clone());
+        let doubled = col
+            .iter()
+            .enumerate()
+            .map(|(i, a)| gf256_mul.gf_mul(layouter.namespace(|| format!("2 * a_{}", i)), a, &two))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let byte_xor = ByteXorChip::construct(self.config.byte_xor.clone());
+        let mut out = Vec::with_capacity(4);
+        for i in 0..4 {
+            let (i1, i2, i3) = ((i + 1) % 4, (i + 2) % 4, (i + 3) % 4);
+            let tripled = byte_xor.xor(
+                layouter.namespace(|| format!("3 * a_{}", i1)),
+                &doubled[i1],
+                &col[i1],
+            )?;
+            let acc = byte_xor.xor(
+                layouter.namespace(|| format!("out_{}: 2 * a_{} ^ 3 * a_{}", i, i, i1)),
+                &doubled[i],
+                &tripled,
+            )?;
+            let acc = byte_xor.xor(
+                layouter.namespace(|| format!("out_{}: ^ a_{}", i, i2)),
+                &acc,
+                &col[i2],
+            )?;
+            out.push(byte_xor.xor(
+                layouter.namespace(|| format!("out_{}: ^ a_{}", i, i3)),
+                &acc,
+                &col[i3],
+            )?);
+        }
+
+        out.try_into().map_err(|_| Error::Synthesis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::{
+        gf256::gf_mul,
+        testing::{run, TestGadget},
+    };
+
+    // 输入是若干列（每 4 个byte一列），输出每一列 MixColumns 之后的结果
+    #[derive(Clone, Default)]
+    struct MixColumns;
+
+    impl TestGadget<Fp> for MixColumns {
+        type Config = MixColumnsConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MixColumnsConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            MixColumnsChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: MixColumnsConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = MixColumnsChip::construct(config);
+            chip.load(layouter.namespace(|| "tables"))?;
+            let mut outputs = vec![];
+            for col in inputs.chunks(4) {
+                let col: [ACell<Fp>; 4] = col.to_vec().try_into().unwrap();
+                outputs.extend(chip.mix_column(layouter.namespace(|| "column"), &col)?);
+            }
+            Ok(outputs)
+        }
+    }
+
+    // 乘一个 circulant 矩阵，第一行是 coeffs
+    fn native_mix(col: [u8; 4], coeffs: [u8; 4]) -> [u8; 4] {
+        let mut out = [0u8; 4];
+        for (i, o) in out.iter_mut().enumerate() {
+            for (j, c) in coeffs.iter().enumerate() {
+                *o ^= gf_mul(*c, col[(i + j) % 4]);
+            }
+        }
+        out
+    }
+
+    // FIPS-197 / 常见的 MixColumns 测试向量
+    const VECTORS: [([u8; 4], [u8; 4]); 6] = [
+        ([0xdb, 0x13, 0x53, 0x45], [0x8e, 0x4d, 0xa1, 0xbc]),
+        ([0xf2, 0x0a, 0x22, 0x5c], [0x9f, 0xdc, 0x58, 0x9d]),
+        ([0x01, 0x01, 0x01, 0x01], [0x01, 0x01, 0x01, 0x01]),
+        ([0xc6, 0xc6, 0xc6, 0xc6], [0xc6, 0xc6, 0xc6, 0xc6]),
+        ([0xd4, 0xd4, 0xd4, 0xd5], [0xd5, 0xd5, 0xd7, 0xd6]),
+        ([0x2d, 0x26, 0x31, 0x4c], [0x4d, 0x7e, 0xbd, 0xf8]),
+    ];
+
+    fn fp(bytes: &[u8]) -> Vec<Fp> {
+        bytes.iter().map(|b| Fp::from(*b as u64)).collect()
+    }
+
+    #[test]
+    fn mix_column_native_vectors() {
+        for (col, expected) in VECTORS {
+            assert_eq!(native_mix(col, [2, 3, 1, 1]), expected);
+            // InvMixColumns 把结果变回去
+            assert_eq!(native_mix(expected, [14, 11, 13, 9]), col);
+        }
+    }
+
+    #[test]
+    fn mix_column_matches_native() {
+        let mut inputs = vec![];
+        let mut expected = vec![];
+        for (col, out) in VECTORS {
+            inputs.extend(fp(&col));
+            expected.extend(fp(&out));
+        }
+        // 全 0 的列
+        inputs.extend(fp(&[0; 4]));
+        expected.extend(fp(&[0; 4]));
+        assert_eq!(run(17, MixColumns, &inputs, &expected), Ok(()));
+    }
+
+    #[test]
+    fn mix_column_rejects_wrong_output() {
+        let (col, out) = VECTORS[0];
+        // 输入没有变换直接输出，或者最后一个byte错了
+        assert!(run(17, MixColumns, &fp(&col), &fp(&col)).is_err());
+        let mut wrong = out;
+        wrong[3] ^= 1;
+        assert!(run(17, MixColumns, &fp(&col), &fp(&wrong)).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+// 这里放可以复用的gadgets，每个gadget都按照 FiboChip 的写法来组织：
+// Config 里面定义 columns 和 selector，Chip 负责 construct / configure / assign
+// 目前 main 里面还没有用到这些chip，所以先关掉 dead_code 的warning
+#![allow(dead_code)]
+
+use halo2_proofs::arithmetic::FieldExt;
+
+use crate::ACell;
+
+pub mod abs;
+pub mod add;
+pub mod adjacency;
+pub mod barrel_shift;
+pub mod base_b;
+pub mod base_convert;
+pub mod binary_search;
+pub mod bit_reversal;
+pub mod bitwise;
+pub mod booth;
+pub mod bounded;
+pub mod bracket;
+pub mod bubble_pass;
+pub mod byte_xor;
+pub mod capacity;
+pub mod case;
+pub mod catalan;
+pub mod cdf;
+pub mod change;
+pub mod checksum;
+pub mod clamp;
+pub mod collatz;
+pub mod commit;
+pub mod commit_update;
+pub mod constant;
+pub mod coprime;
+pub mod cordic;
+pub mod csa;
+pub mod de_bruijn;
+pub mod decompose;
+pub mod delete_at;
+pub mod diagonal;
+pub mod digital_root;
+pub mod diophantine;
+pub mod div;
+pub mod dot_product;
+pub mod dyn_range;
+pub mod ema;
+pub mod factorial;
+pub mod feistel;
+pub mod fibo_doubling;
+pub mod fixed_mul;
+pub mod flatten_index;
+pub mod gf256;
+pub mod gray;
+pub mod hamming;
+pub mod heap;
+pub mod histogram;
+pub mod index_select;
+pub mod insert_at;
+pub mod insert_sorted;
+pub mod interval;
+pub mod is_equal;
+pub mod is_zero;
+pub mod lcg;
+pub mod less_than;
+pub mod less_than_or_equal;
+pub mod lfsr;
+pub mod log2;
+pub mod lookup_range;
+pub mod luhn;
+pub mod masked_sum;
+pub mod matrix_square;
+pub mod merge;
+pub mod minmax;
+pub mod mix_columns;
+pub mod mod_add;
+pub mod mod_mul;
+pub mod mod_neg;
+pub mod mod_sqrt;
+pub mod modinv_table;
+pub mod modmul_chain;
+pub mod modulo;
+pub mod monotone_bool;
+pub mod mont_ladder;
+pub mod morton;
+pub mod morton_decode;
+pub mod mul;
+pub mod mul_const;
+pub mod mux;
+pub mod newton_sqrt;
+pub mod nim;
+pub mod normalize;
+pub mod on_curve;
+pub mod one_hot;
+pub mod otp;
+pub mod palindrome;
+pub mod parity;
+pub mod parity_bit;
+pub mod path;
+pub mod percentile;
+pub mod permutation_check;
+pub mod poly_eval;
+pub mod popcount;
+pub mod poseidon;
+pub mod pow_difficulty;
+pub mod prefix_sum;
+pub mod priority_encoder;
+pub mod quantize;
+pub mod rank;
+pub mod reciprocal;
+pub mod recompose;
+pub mod rle;
+pub mod rs_encode;
+pub mod sat_add;
+pub mod scalar_reduce;
+pub mod sign_extend;
+pub mod six_bit;
+pub mod sorted;
+pub mod sqrt;
+pub mod sub;
+pub mod sudoku;
+pub mod threshold;
+pub mod tictactoe;
+pub mod tolerance;
+pub mod trace;
+pub mod transpose;
+pub mod triangular;
+pub mod two_sum;
+pub mod upper_triangular;
+pub mod utf8;
+pub mod weighted_majority;
+pub mod weighted_threshold;
+pub mod window_min;
+pub mod wrapping_mul;
+pub mod xor_list;
+pub mod z_order;
+
+#[cfg(test)]
+pub(crate) mod testing;
+
+// 一个已经被约束成 0 或 1 的 cell
+// 只有在gate里面约束过 b * (1 - b) = 0 的chip才应该返回 Boolean
+#[derive(Debug, Clone)]
+pub struct Boolean<F: FieldExt>(pub ACell<F>);
+
+impl<F: FieldExt> Boolean<F> {
+    pub fn value(&self) -> Option<bool> {
+        self.0 .0.value().map(|v| *v == F::one())
+    }
+}
+
+// witness 生成的时候需要做一些整数运算，这里假设value本身足够小（< 2^128）
+pub(crate) fn to_u128<F: FieldExt>(v: &F) -> u128 {
+    v.get_lower_128()
+}
+
+// 2^n 作为field element
+pub(crate) fn pow2<F: FieldExt>(n: usize) -> F {
+    (0..n).fold(F::one(), |acc, _| acc.double())
+}
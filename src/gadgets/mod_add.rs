@@ -0,0 +1,187 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    base_b::{BaseBChip, BaseBConfig},
+    constant::{ConstantChip, ConstantConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    mux::{MuxChip, MuxConfig},
+    sub::{SubChip, SubConfig},
+};
+use crate::ACell;
+
+// 模加法：out = (a + b) mod m，a, b 都已经 < m
+// a + b < 2m，所以最多只需要减一次 m：
+//   sum = a + b
+//   no_wrap = (sum < m)
+//   out = no_wrap ? sum : sum - m
+// 最后再用 BaseBChip 把 out 拆成 1 个 m 进制的digit，也就是断言 out < m（输出是canonical的）
+// a + b == m 的时候 out = 0
+#[derive(Debug, Clone)]
+pub struct ModAddConfig {
+    pub add: AddConfig,
+    pub sub: SubConfig,
+    pub constant: ConstantConfig,
+    pub less_than: LessThanConfig,
+    pub mux: MuxConfig,
+    pub base_b: BaseBConfig,
+}
+
+pub struct ModAddChip<F: FieldExt> {
+    config: ModAddConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ModAddChip<F> {
+    pub fn construct(config: ModAddConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ModAddConfig {
+        ModAddConfig {
+            add: AddChip::configure(meta, advice),
+            sub: SubChip::configure(meta, advice),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            mux: MuxChip::configure(meta, advice),
+            base_b: BaseBChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn mod_add(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        m: u64,
+    ) -> Result<ACell<F>, Error> {
+        if m < 2 {
+            return Err(Error::Synthesis);
+        }
+        // sum < 2m
+        let bits = 65 - m.leading_zeros() as usize;
+
+        let add = AddChip::construct(self.config.add.clone());
+        let sum = add.add(layouter.namespace(|| "a + b"), a, b)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let m_cell = constant.load_constant(layouter.namespace(|| "m"), F::from(m))?;
+
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let no_wrap = less_than.less_than(layouter.namespace(|| "sum < m"), &sum, &m_cell, bits)?;
+
+        let sub = SubChip::construct(self.config.sub.clone());
+        let wrapped = sub.sub(layouter.namespace(|| "sum - m"), &sum, &m_cell)?;
+
+        let mux = MuxChip::construct(self.config.mux.clone());
+        let out = mux.mux(layouter.namespace(|| "reduce"), &no_wrap, &sum, &wrapped)?;
+
+        let base_b = BaseBChip::construct(self.config.base_b.clone());
+        base_b.decompose(layouter.namespace(|| "out < m"), &out, m, 1)?;
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [a, b]，输出 (a + b) mod m
+    #[derive(Clone, Default)]
+    struct ModAdd {
+        m: u64,
+    }
+
+    impl TestGadget<Fp> for ModAdd {
+        type Config = ModAddConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ModAddConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ModAddChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ModAddConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                ModAddChip::construct(config).mod_add(layouter, &inputs[0], &inputs[1], self.m)?
+            ])
+        }
+    }
+
+    fn check(m: u64, a: u64, b: u64, out: u64) -> bool {
+        run(
+            8,
+            ModAdd { m },
+            &[Fp::from(a), Fp::from(b)],
+            &[Fp::from(out)],
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn mod_add_matches_native() {
+        let m = 7;
+        for a in 0..m {
+            for b in 0..m {
+                assert!(check(m, a, b, (a + b) % m), "{} + {}", a, b);
+            }
+        }
+
+        let m = 1_000_003;
+        for (a, b) in [
+            (0, 0),
+            (12345, 54321),
+            (m - 1, 1),
+            (m - 1, m - 1),
+            (500_000, 500_003),
+        ] {
+            assert!(check(m, a, b, (a + b) % m), "{} + {}", a, b);
+        }
+    }
+
+    #[test]
+    fn mod_add_sum_equal_to_m_gives_zero() {
+        assert!(check(7, 3, 4, 0));
+        assert!(!check(7, 3, 4, 7));
+    }
+
+    #[test]
+    fn mod_add_rejects_wrong_output() {
+        // 不减 m（out 不是canonical的）或者在不该减的时候减了
+        assert!(!check(7, 6, 6, 12));
+        assert!(!check(7, 2, 3, 5 + 7));
+        assert!(!check(7, 2, 3, 4));
+    }
+
+    #[test]
+    fn mod_add_rejects_tiny_modulus() {
+        assert!(synthesis_fails(
+            8,
+            ModAdd { m: 1 },
+            &[Fp::zero(), Fp::zero()],
+            &[Fp::zero()]
+        ));
+    }
+}
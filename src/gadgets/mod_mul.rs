@@ -0,0 +1,129 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    modulo::{ModChip, ModConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 模乘法：out = (a * b) mod m
+// MulChip 算 a * b，再用 ModChip 取模，输出一定 < m
+// a * b 的商要 < 2^QUOTIENT_BITS，a, b 都 < m 的时候一定满足
+#[derive(Debug, Clone)]
+pub struct ModMulConfig {
+    pub mul: MulConfig,
+    pub modulo: ModConfig,
+}
+
+pub struct ModMulChip<F: FieldExt> {
+    config: ModMulConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ModMulChip<F> {
+    pub fn construct(config: ModMulConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ModMulConfig {
+        ModMulConfig {
+            mul: MulChip::configure(meta, advice),
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn mod_mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        m: u64,
+    ) -> Result<ACell<F>, Error> {
+        let mul = MulChip::construct(self.config.mul.clone());
+        let product = mul.mul(layouter.namespace(|| "a * b"), a, b)?;
+
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        modulo.modulo(layouter.namespace(|| "mod m"), &product, m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [a, b]，输出 (a * b) mod m
+    #[derive(Clone, Default)]
+    struct ModMul {
+        m: u64,
+    }
+
+    impl TestGadget<Fp> for ModMul {
+        type Config = ModMulConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ModMulConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ModMulChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ModMulConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                ModMulChip::construct(config).mod_mul(layouter, &inputs[0], &inputs[1], self.m)?
+            ])
+        }
+    }
+
+    fn check(m: u64, a: u64, b: u64, out: u64) -> bool {
+        run(
+            10,
+            ModMul { m },
+            &[Fp::from(a), Fp::from(b)],
+            &[Fp::from(out)],
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn mod_mul_matches_native() {
+        let m = 1_000_000_007u64;
+        for (a, b) in [
+            (0, 5),
+            (1, m - 1),
+            (m - 1, m - 1),
+            (123_456_789, 987_654_321),
+            (2, 3),
+        ] {
+            let out = (a as u128 * b as u128 % m as u128) as u64;
+            assert!(check(m, a, b, out), "{} * {}", a, b);
+        }
+    }
+
+    #[test]
+    fn mod_mul_rejects_wrong_output() {
+        // 没有取模，或者多减了一个 m
+        assert!(check(7, 5, 6, 2));
+        assert!(!check(7, 5, 6, 30));
+        assert!(!check(7, 5, 6, 9));
+    }
+}
@@ -0,0 +1,122 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    modulo::{ModChip, ModConfig},
+    sub::{SubChip, SubConfig},
+};
+use crate::ACell;
+
+// 模 m 的相反数：neg = (m - x) mod m
+// 先把 x 约化到 [0, m)，这样 m - r 落在 (0, m] 里面，不会在field里面变成负数，再 mod m 一次
+// x = 0 的时候 m - 0 = m，mod 之后就是 0
+#[derive(Debug, Clone)]
+pub struct ModNegConfig {
+    pub constant: ConstantConfig,
+    pub sub: SubConfig,
+    pub modulo: ModConfig,
+}
+
+pub struct ModNegChip<F: FieldExt> {
+    config: ModNegConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ModNegChip<F> {
+    pub fn construct(config: ModNegConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ModNegConfig {
+        ModNegConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            sub: SubChip::configure(meta, advice),
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn mod_neg(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        m: u64,
+    ) -> Result<ACell<F>, Error> {
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        let r = modulo.modulo(layouter.namespace(|| "x mod m"), x, m)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let m_cell = constant.load_constant(layouter.namespace(|| "m"), F::from(m))?;
+        let sub = SubChip::construct(self.config.sub.clone());
+        let diff = sub.sub(layouter.namespace(|| "m - r"), &m_cell, &r)?;
+
+        modulo.modulo(layouter.namespace(|| "(m - r) mod m"), &diff, m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const M: u64 = 13;
+
+    #[derive(Clone, Default)]
+    struct ModNeg;
+
+    impl TestGadget<Fp> for ModNeg {
+        type Config = ModNegConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ModNegConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ModNegChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ModNegConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let neg = ModNegChip::construct(config).mod_neg(layouter, &inputs[0], M)?;
+            Ok(vec![neg])
+        }
+    }
+
+    #[test]
+    fn mod_neg_matches_native() {
+        // 包括 x = 0，x = m - 1，以及 x >= m 的时候先约化
+        for x in [0u64, 1, 6, 12, 13, 14, 100] {
+            let expected = (M - x % M) % M;
+            assert_eq!(
+                run(8, ModNeg, &[Fp::from(x)], &[Fp::from(expected)]),
+                Ok(()),
+                "x = {}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn mod_neg_rejects_wrong_output() {
+        // 没有最后那次 mod（x = 0 的时候 m - 0 = m），或者直接是 field 里面的 -x
+        assert!(run(8, ModNeg, &[Fp::zero()], &[Fp::from(M)]).is_err());
+        assert!(run(8, ModNeg, &[Fp::from(5)], &[-Fp::from(5)]).is_err());
+        assert!(run(8, ModNeg, &[Fp::from(5)], &[Fp::from(7)]).is_err());
+    }
+}
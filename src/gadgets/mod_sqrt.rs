@@ -0,0 +1,173 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    modulo::{ModChip, ModConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 模 p 的平方根：r^2 ≡ n (mod p)，r 是prover给的witness（比如用 Tonelli-Shanks 在电路外面算出来）
+// * r mod p == r，也就是 r < p
+// * (r * r) mod p == n，n 也必须已经 < p
+// canonical = true 的时候再要求 r <= (p - 1) / 2，也就是两个根 r 和 p - r 里面取小的那个
+// n = 0 的时候 r 只能是 0；n 不是二次剩余的时候根本找不到 r
+#[derive(Debug, Clone)]
+pub struct ModSqrtConfig {
+    pub mul: MulConfig,
+    pub modulo: ModConfig,
+    pub constant: ConstantConfig,
+    pub less_than_or_equal: LessThanOrEqualConfig,
+    pub canonical: bool,
+}
+
+pub struct ModSqrtChip<F: FieldExt> {
+    config: ModSqrtConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ModSqrtChip<F> {
+    pub fn construct(config: ModSqrtConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        canonical: bool,
+    ) -> ModSqrtConfig {
+        ModSqrtConfig {
+            mul: MulChip::configure(meta, advice),
+            modulo: ModChip::configure(meta, advice, fixed),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+            canonical,
+        }
+    }
+
+    pub fn assert_mod_sqrt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: &ACell<F>,
+        r: &ACell<F>,
+        p: u64,
+    ) -> Result<(), Error> {
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        let r_reduced = modulo.modulo(layouter.namespace(|| "r mod p"), r, p)?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let square = mul.mul(layouter.namespace(|| "r * r"), r, r)?;
+        let square_reduced = modulo.modulo(layouter.namespace(|| "r^2 mod p"), &square, p)?;
+
+        layouter.assign_region(
+            || "r < p, r^2 == n",
+            |mut region| {
+                region.constrain_equal(r_reduced.0.cell(), r.0.cell())?;
+                region.constrain_equal(square_reduced.0.cell(), n.0.cell())
+            },
+        )?;
+
+        if self.config.canonical {
+            let constant = ConstantChip::construct(self.config.constant.clone());
+            let half = constant
+                .load_constant(layouter.namespace(|| "(p - 1) / 2"), F::from((p - 1) / 2))?;
+            let less_than_or_equal =
+                LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+            less_than_or_equal.assert_less_than_or_equal(
+                layouter.namespace(|| "r <= (p - 1) / 2"),
+                r,
+                &half,
+                64,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const P: u64 = 13;
+
+    // 输入是 [n, r]，没有输出
+    #[derive(Clone, Default)]
+    struct ModSqrt<const CANONICAL: bool>;
+
+    impl<const CANONICAL: bool> TestGadget<Fp> for ModSqrt<CANONICAL> {
+        type Config = ModSqrtConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ModSqrtConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ModSqrtChip::configure(meta, advice, fixed, CANONICAL)
+        }
+
+        fn synthesize(
+            &self,
+            config: ModSqrtConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            ModSqrtChip::construct(config).assert_mod_sqrt(layouter, &inputs[0], &inputs[1], P)?;
+            Ok(vec![])
+        }
+    }
+
+    fn check<const CANONICAL: bool>(n: u64, r: u64) -> bool {
+        run(10, ModSqrt::<CANONICAL>, &[Fp::from(n), Fp::from(r)], &[]).is_ok()
+    }
+
+    #[test]
+    fn mod_sqrt_matches_native() {
+        for n in 0..P {
+            for r in 0..P {
+                let is_root = r * r % P == n;
+                assert_eq!(check::<false>(n, r), is_root, "n = {}, r = {}", n, r);
+                assert_eq!(
+                    check::<true>(n, r),
+                    is_root && r <= (P - 1) / 2,
+                    "canonical n = {}, r = {}",
+                    n,
+                    r
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mod_sqrt_zero_only_has_zero_root() {
+        assert!(check::<true>(0, 0));
+        assert!(!check::<false>(0, P));
+    }
+
+    #[test]
+    fn mod_sqrt_rejects_unreduced_inputs() {
+        // 3^2 = 9，但是 r = 3 + 13 不是 < p 的
+        assert!(check::<false>(9, 3));
+        assert!(!check::<false>(9, 3 + P));
+        // n 也必须已经约化过
+        assert!(!check::<false>(9 + P, 3));
+    }
+
+    #[test]
+    fn mod_sqrt_non_residue_has_no_root() {
+        // 2 不是模 13 的二次剩余
+        assert!((0..P).all(|r| !check::<false>(2, r)));
+    }
+}
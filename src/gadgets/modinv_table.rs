@@ -0,0 +1,251 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::to_u128;
+use crate::ACell;
+
+// 小素数 m 下的模逆：x * x_inv ≡ 1 (mod m)
+// 把所有 (x, x_inv)（x = 1..m-1）预先算好放进lookup table，然后lookup (1, x, x_inv)
+// 和 AdjacencyChip 一样多一个tag列，这样selector关掉的时候 (0, 0, 0) 那一行不会被当成 0 的逆
+// x = 0（或者 x >= m）不在table里面，没有逆，witness生成直接返回 Error::Synthesis
+// m 在configure的时候就定下来（table和witness生成都要用），load的时候检查 m 是素数、table放得进 2^k 行
+//
+//  x | x_inv | q_lookup
+//
+#[derive(Debug, Clone)]
+pub struct ModInverseTableConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_lookup: Selector,
+    pub table_tag: TableColumn,
+    pub table_x: TableColumn,
+    pub table_inv: TableColumn,
+    pub modulus: u64,
+}
+
+pub struct ModInverseTableChip<F: FieldExt> {
+    config: ModInverseTableConfig,
+    _marker: PhantomData<F>,
+}
+
+// 费马小定理：x^(m - 2) mod m
+fn mod_pow(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result
+}
+
+fn is_prime(m: u64) -> bool {
+    m >= 2
+        && (2..)
+            .take_while(|d| d * d <= m)
+            .all(|d| !m.is_multiple_of(d))
+}
+
+impl<F: FieldExt> ModInverseTableChip<F> {
+    pub fn construct(config: ModInverseTableConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        modulus: u64,
+    ) -> ModInverseTableConfig {
+        let q_lookup = meta.complex_selector();
+        let table_tag = meta.lookup_table_column();
+        let table_x = meta.lookup_table_column();
+        let table_inv = meta.lookup_table_column();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        meta.lookup(|meta| {
+            let q = meta.query_selector(q_lookup);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let inv = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![
+                (q.clone(), table_tag),
+                (q.clone() * x, table_x),
+                (q * inv, table_inv),
+            ]
+        });
+
+        ModInverseTableConfig {
+            advice,
+            q_lookup,
+            table_tag,
+            table_x,
+            table_inv,
+            modulus,
+        }
+    }
+
+    // table一共 m 行（tag = 0 的那一行加上 x = 1..m-1），所以 m 不能超过电路的 2^k 行
+    // 不是素数的话有些 x 没有逆，费马小定理算出来的也不对，直接拒绝
+    // （blinding rows 也要占几行，放不下的话 prover 会报 NotEnoughRowsAvailable）
+    pub fn load(&self, mut layouter: impl Layouter<F>, k: u32) -> Result<(), Error> {
+        let modulus = self.config.modulus;
+        if !is_prime(modulus) || k >= 64 || modulus > 1 << k {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_table(
+            || "mod inverse table",
+            |mut table| {
+                let rows = std::iter::once((0, 0, 0))
+                    .chain((1..modulus).map(|x| (1, x, mod_pow(x, modulus - 2, modulus))));
+                for (offset, (tag, x, inv)) in rows.enumerate() {
+                    table.assign_cell(
+                        || "tag",
+                        self.config.table_tag,
+                        offset,
+                        || Ok(F::from(tag)),
+                    )?;
+                    table.assign_cell(|| "x", self.config.table_x, offset, || Ok(F::from(x)))?;
+                    table.assign_cell(
+                        || "x_inv",
+                        self.config.table_inv,
+                        offset,
+                        || Ok(F::from(inv)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn inverse(&self, mut layouter: impl Layouter<F>, x: &ACell<F>) -> Result<ACell<F>, Error> {
+        let modulus = self.config.modulus;
+
+        layouter.assign_region(
+            || "mod inverse",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+
+                x.0.copy_advice(|| "x", &mut region, self.config.advice[0], 0)?;
+
+                let inv_val = x.0.value().map(|x| {
+                    let x = to_u128(x);
+                    (x != 0 && x < modulus as u128)
+                        .then(|| F::from(mod_pow(x as u64, modulus - 2, modulus)))
+                });
+
+                region
+                    .assign_advice(
+                        || "x_inv",
+                        self.config.advice[1],
+                        0,
+                        || inv_val.flatten().ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    const K: u32 = 6;
+
+    // 每个输入求一次逆，输出所有的 x_inv
+    #[derive(Clone, Default)]
+    struct ModInverse<const M: u64>;
+
+    impl<const M: u64> TestGadget<Fp> for ModInverse<M> {
+        type Config = ModInverseTableConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ModInverseTableConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            ModInverseTableChip::configure(meta, advice, M)
+        }
+
+        fn synthesize(
+            &self,
+            config: ModInverseTableConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = ModInverseTableChip::construct(config);
+            chip.load(layouter.namespace(|| "table"), K)?;
+            inputs
+                .iter()
+                .map(|x| chip.inverse(layouter.namespace(|| "inverse"), x))
+                .collect()
+        }
+    }
+
+    fn native_inverse(x: u64, m: u64) -> u64 {
+        (1..m).find(|inv| x * inv % m == 1).unwrap()
+    }
+
+    #[test]
+    fn modinv_matches_native() {
+        for (xs, m) in [(vec![1u64, 2, 3, 5, 12, 16], 17), (vec![1, 3, 2, 4], 5)] {
+            let inputs: Vec<_> = xs.iter().map(|x| Fp::from(*x)).collect();
+            let expected: Vec<_> = xs.iter().map(|x| Fp::from(native_inverse(*x, m))).collect();
+            let result = match m {
+                17 => run(K, ModInverse::<17>, &inputs, &expected),
+                _ => run(K, ModInverse::<5>, &inputs, &expected),
+            };
+            assert_eq!(result, Ok(()), "m = {}", m);
+        }
+    }
+
+    #[test]
+    fn modinv_rejects_wrong_inverse() {
+        assert!(run(K, ModInverse::<17>, &[Fp::from(3)], &[Fp::from(5)]).is_err());
+    }
+
+    #[test]
+    fn modinv_of_zero_fails() {
+        // 0 和 >= m 的值没有逆
+        assert!(synthesis_fails(
+            K,
+            ModInverse::<17>,
+            &[Fp::zero()],
+            &[Fp::zero()]
+        ));
+        assert!(synthesis_fails(
+            K,
+            ModInverse::<17>,
+            &[Fp::from(17)],
+            &[Fp::one()]
+        ));
+    }
+
+    #[test]
+    fn modinv_rejects_bad_modulus() {
+        // 不是素数
+        assert!(synthesis_fails(
+            K,
+            ModInverse::<15>,
+            &[Fp::from(2)],
+            &[Fp::from(8)]
+        ));
+        assert!(synthesis_fails(K, ModInverse::<1>, &[], &[]));
+        // 127 行的table放不进 2^6 行
+        assert!(synthesis_fails(
+            K,
+            ModInverse::<127>,
+            &[Fp::from(2)],
+            &[Fp::from(64)]
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    modulo::{ModChip, ModConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 连乘取模：r = (((x_1 * x_2) mod m) * x_3) mod m ...
+// 第一个因子先 mod m，之后每一步都是 acc = (acc * x_i) mod m，所以 acc 一直 < m
+// acc * x_i 的商要 < 2^QUOTIENT_BITS，也就是每个 x_i 都需要 < 2^64
+// 只有一个因子的时候结果就是 x_1 mod m；有一个因子是 0 的话之后就一直是 0
+#[derive(Debug, Clone)]
+pub struct ModMulChainConfig {
+    pub mul: MulConfig,
+    pub modulo: ModConfig,
+}
+
+pub struct ModMulChainChip<F: FieldExt> {
+    config: ModMulChainConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ModMulChainChip<F> {
+    pub fn construct(config: ModMulChainConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ModMulChainConfig {
+        ModMulChainConfig {
+            mul: MulChip::configure(meta, advice),
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn modmul_chain(
+        &self,
+        mut layouter: impl Layouter<F>,
+        factors: &[ACell<F>],
+        m: u64,
+    ) -> Result<ACell<F>, Error> {
+        let (first, rest) = factors.split_first().ok_or(Error::Synthesis)?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let modulo = ModChip::construct(self.config.modulo.clone());
+
+        let mut acc = modulo.modulo(layouter.namespace(|| "x_0 mod m"), first, m)?;
+        for (i, factor) in rest.iter().enumerate() {
+            let product = mul.mul(
+                layouter.namespace(|| format!("acc * x_{}", i + 1)),
+                &acc,
+                factor,
+            )?;
+            acc = modulo.modulo(
+                layouter.namespace(|| format!("step {} mod m", i + 1)),
+                &product,
+                m,
+            )?;
+        }
+
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    const M: u64 = 1_000_000_007;
+
+    #[derive(Clone, Default)]
+    struct ModMulChain;
+
+    impl TestGadget<Fp> for ModMulChain {
+        type Config = ModMulChainConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ModMulChainConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ModMulChainChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ModMulChainConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let r = ModMulChainChip::construct(config).modmul_chain(layouter, inputs, M)?;
+            Ok(vec![r])
+        }
+    }
+
+    fn native(factors: &[u64]) -> u64 {
+        factors
+            .iter()
+            .fold(1u128, |acc, x| acc * *x as u128 % M as u128) as u64
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn modmul_chain_matches_native() {
+        for factors in [
+            // 只有一个因子（也要先 mod m）
+            vec![5u64],
+            vec![M + 5],
+            vec![123_456_789, 987_654_321, 555_555_555],
+            vec![u64::MAX, u64::MAX],
+            // 有一个是 0，之后就一直是 0
+            vec![7, 0, 11, 13],
+        ] {
+            assert_eq!(
+                run(
+                    10,
+                    ModMulChain,
+                    &fp(&factors),
+                    &[Fp::from(native(&factors))]
+                ),
+                Ok(()),
+                "{:?}",
+                factors
+            );
+        }
+    }
+
+    #[test]
+    fn modmul_chain_rejects_wrong_result() {
+        let factors = [123_456_789u64, 987_654_321];
+        // 没有取模的完整乘积
+        let full = Fp::from(123_456_789) * Fp::from(987_654_321);
+        assert!(run(10, ModMulChain, &fp(&factors), &[full]).is_err());
+        assert!(run(
+            10,
+            ModMulChain,
+            &fp(&factors),
+            &[Fp::from(native(&factors) + M)]
+        )
+        .is_err());
+        assert!(synthesis_fails(10, ModMulChain, &[], &[Fp::zero()]));
+    }
+}
@@ -0,0 +1,215 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// q 最多 64 bits，配合 m < 2^64，q * m + r 一定不会在field里面溢出
+pub const QUOTIENT_BITS: usize = 64;
+
+// 求 x mod m（m 是一个常数）
+// witness q, r，使得 x = q * m + r，并且 0 <= r < m
+// r < m 是通过同时range check r 和 t = m - 1 - r 来保证的
+//
+//  x | q | r | m(fixed) | selector
+//  t |   |   |          |
+//
+#[derive(Debug, Clone)]
+pub struct ModConfig {
+    pub advice: [Column<Advice>; 3],
+    pub modulus: Column<Fixed>,
+    pub selector: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct ModChip<F: FieldExt> {
+    config: ModConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ModChip<F> {
+    pub fn construct(config: ModConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        modulus: Column<Fixed>,
+    ) -> ModConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("mod", |meta| {
+            let s = meta.query_selector(selector);
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let q = meta.query_advice(advice[1], Rotation::cur());
+            let r = meta.query_advice(advice[2], Rotation::cur());
+            let t = meta.query_advice(advice[0], Rotation::next());
+            let m = meta.query_fixed(modulus, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * (q * m.clone() + r.clone() - x),
+                s * (r + t + one - m),
+            ]
+        });
+
+        ModConfig {
+            advice,
+            modulus,
+            selector,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    // 返回 (q, r)
+    pub fn div_rem(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        m: u64,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        if m == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        let (q, r, t) = layouter.assign_region(
+            || "mod",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                x.0.copy_advice(|| "x", &mut region, self.config.advice[0], 0)?;
+                region.assign_fixed(|| "m", self.config.modulus, 0, || Ok(F::from(m)))?;
+
+                let x_val = x.0.value().map(to_u128);
+                let q_val = x_val.map(|x| F::from_u128(x / m as u128));
+                let r_val = x_val.map(|x| F::from_u128(x % m as u128));
+                let t_val = r_val.map(|r| F::from(m - 1) - r);
+
+                let q = region
+                    .assign_advice(
+                        || "q",
+                        self.config.advice[1],
+                        0,
+                        || q_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+                let r = region
+                    .assign_advice(
+                        || "r",
+                        self.config.advice[2],
+                        0,
+                        || r_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+                let t = region
+                    .assign_advice(
+                        || "m - 1 - r",
+                        self.config.advice[0],
+                        1,
+                        || t_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                Ok((q, r, t))
+            },
+        )?;
+
+        // r 和 m - 1 - r 都要落在 [0, 2^m_bits) 里面，这样才能保证 r <= m - 1
+        let m_bits = (64 - (m - 1).leading_zeros() as usize).max(1);
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check q"), &q, QUOTIENT_BITS)?;
+        decompose.decompose(layouter.namespace(|| "range check r"), &r, m_bits)?;
+        decompose.decompose(layouter.namespace(|| "range check m - 1 - r"), &t, m_bits)?;
+
+        Ok((q, r))
+    }
+
+    pub fn modulo(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        m: u64,
+    ) -> Result<ACell<F>, Error> {
+        self.div_rem(layouter, x, m).map(|(_, r)| r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct DivRem {
+        m: u64,
+    }
+
+    impl TestGadget<Fp> for DivRem {
+        type Config = ModConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ModConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ModChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ModConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (q, r) = ModChip::construct(config).div_rem(layouter, &inputs[0], self.m)?;
+            Ok(vec![q, r])
+        }
+    }
+
+    #[test]
+    fn div_rem_matches_native() {
+        for (x, m) in [
+            (0u64, 1u64),
+            (17, 5),
+            (4, 5),
+            (1 << 40, 1000),
+            (u64::MAX, u64::MAX),
+        ] {
+            let expected = [Fp::from(x / m), Fp::from(x % m)];
+            assert_eq!(run(8, DivRem { m }, &[Fp::from(x)], &expected), Ok(()));
+        }
+    }
+
+    #[test]
+    fn div_rem_rejects_unreduced_remainder() {
+        // 17 = 2 * 5 + 7 满足等式，但是 7 >= 5
+        assert!(run(
+            8,
+            DivRem { m: 5 },
+            &[Fp::from(17)],
+            &[Fp::from(2), Fp::from(7)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn div_rem_rejects_zero_modulus() {
+        assert!(synthesis_fails(8, DivRem { m: 0 }, &[Fp::from(3)], &[]));
+    }
+}
@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+
+// 证明一串boolean不会从 1 掉回 0（比如一个 "finalized" flag，一旦置位就不能撤销）
+// flags 放在同一个column的连续行里面，相邻两行满足 prev * (1 - next) = 0
+//
+//  flag | q_bool | q_pair
+//  f_0  |   1    |   1
+//  f_1  |   1    |   1
+//  f_n  |   1    |   0
+//
+#[derive(Debug, Clone)]
+pub struct MonotoneBoolConfig {
+    pub advice: Column<Advice>,
+    pub q_bool: Selector,
+    pub q_pair: Selector,
+}
+
+pub struct MonotoneBoolChip<F: FieldExt> {
+    config: MonotoneBoolConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MonotoneBoolChip<F> {
+    pub fn construct(config: MonotoneBoolConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: Column<Advice>) -> MonotoneBoolConfig {
+        let q_bool = meta.selector();
+        let q_pair = meta.selector();
+
+        meta.enable_equality(advice);
+
+        meta.create_gate("flag is boolean", |meta| {
+            let q_bool = meta.query_selector(q_bool);
+            let cur = meta.query_advice(advice, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![q_bool * cur.clone() * (one - cur)]
+        });
+
+        // 最后一行没有 next，所以单独一个gate，只在有相邻对的行上打开
+        meta.create_gate("no 1 -> 0", |meta| {
+            let q_pair = meta.query_selector(q_pair);
+            let cur = meta.query_advice(advice, Rotation::cur());
+            let next = meta.query_advice(advice, Rotation::next());
+            let one = Expression::Constant(F::one());
+
+            vec![q_pair * cur * (one - next)]
+        });
+
+        MonotoneBoolConfig {
+            advice,
+            q_bool,
+            q_pair,
+        }
+    }
+
+    // 空的或者只有一个flag的时候没有相邻对，直接通过
+    pub fn assert_monotone(
+        &self,
+        mut layouter: impl Layouter<F>,
+        flags: &[Boolean<F>],
+    ) -> Result<(), Error> {
+        if flags.is_empty() {
+            return Ok(());
+        }
+
+        layouter.assign_region(
+            || "monotone bool",
+            |mut region| {
+                for (row, flag) in flags.iter().enumerate() {
+                    self.config.q_bool.enable(&mut region, row)?;
+                    if row + 1 < flags.len() {
+                        self.config.q_pair.enable(&mut region, row)?;
+                    }
+
+                    let flag = &flag.0;
+                    flag.0
+                        .copy_advice(|| "flag", &mut region, self.config.advice, row)?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::{
+        gadgets::testing::{run, TestGadget},
+        ACell,
+    };
+
+    #[derive(Clone, Default)]
+    struct Monotone;
+
+    impl TestGadget<Fp> for Monotone {
+        type Config = MonotoneBoolConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MonotoneBoolConfig {
+            let advice = meta.advice_column();
+            MonotoneBoolChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: MonotoneBoolConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let flags: Vec<_> = inputs.iter().cloned().map(Boolean).collect();
+            MonotoneBoolChip::construct(config).assert_monotone(layouter, &flags)?;
+            Ok(vec![])
+        }
+    }
+
+    fn check(flags: &[u64]) -> bool {
+        let inputs: Vec<_> = flags.iter().map(|f| Fp::from(*f)).collect();
+        run(5, Monotone, &inputs, &[]).is_ok()
+    }
+
+    // 和 native 的判断对比：所有长度为 4 的boolean序列
+    #[test]
+    fn monotone_matches_native_for_all_length_4() {
+        for mask in 0..16u64 {
+            let flags: Vec<u64> = (0..4).map(|i| (mask >> i) & 1).collect();
+            let native = flags.windows(2).all(|w| w[0] <= w[1]);
+            assert_eq!(check(&flags), native, "flags {:?}", flags);
+        }
+    }
+
+    #[test]
+    fn monotone_accepts_short_sequences() {
+        assert!(check(&[]));
+        assert!(check(&[0]));
+        assert!(check(&[1]));
+    }
+
+    #[test]
+    fn monotone_rejects_non_boolean_flag() {
+        assert!(!check(&[0, 2]));
+    }
+}
@@ -0,0 +1,227 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    mod_mul::{ModMulChip, ModMulConfig},
+    modulo::{ModChip, ModConfig},
+    mux::{MuxChip, MuxConfig},
+};
+use crate::ACell;
+
+// 用 Montgomery ladder 算模幂：out = base^exp mod m
+// R0 = 1, R1 = base mod m，从 exp 的最高位开始，每一位都做完全一样的操作：
+//   prod = R0 * R1 mod m
+//   sq   = (bit ? R1 : R0)^2 mod m
+//   R0   = bit ? prod : sq
+//   R1   = bit ? sq : prod
+// 一直保持 R1 = R0 * base，最后 R0 就是结果
+// exp 用 DecomposeChip 拆成 bits 个bit（同时也检查了 exp < 2^bits）
+// exp = 0 的时候每一步都是 R0 = R0^2 = 1，结果是 1 mod m（m = 1 的时候是 0）
+#[derive(Debug, Clone)]
+pub struct MontgomeryLadderConfig {
+    pub constant: ConstantConfig,
+    pub decompose: DecomposeConfig,
+    pub modulo: ModConfig,
+    pub mod_mul: ModMulConfig,
+    pub mux: MuxConfig,
+}
+
+pub struct MontgomeryLadderChip<F: FieldExt> {
+    config: MontgomeryLadderConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MontgomeryLadderChip<F> {
+    pub fn construct(config: MontgomeryLadderConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> MontgomeryLadderConfig {
+        MontgomeryLadderConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            modulo: ModChip::configure(meta, advice, fixed),
+            mod_mul: ModMulChip::configure(meta, advice, fixed),
+            mux: MuxChip::configure(meta, advice),
+        }
+    }
+
+    pub fn mod_exp(
+        &self,
+        mut layouter: impl Layouter<F>,
+        base: &ACell<F>,
+        exp: &ACell<F>,
+        m: u64,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        if m == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let exp_bits = decompose.decompose(layouter.namespace(|| "exp bits"), exp, bits)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let mut r0 = constant.load_constant(layouter.namespace(|| "R0 = 1"), F::from(1 % m))?;
+
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        let mut r1 = modulo.modulo(layouter.namespace(|| "R1 = base mod m"), base, m)?;
+
+        let mod_mul = ModMulChip::construct(self.config.mod_mul.clone());
+        let mux = MuxChip::construct(self.config.mux.clone());
+        for (i, bit) in exp_bits.iter().enumerate().rev() {
+            let prod = mod_mul.mod_mul(
+                layouter.namespace(|| format!("bit {}: R0 * R1", i)),
+                &r0,
+                &r1,
+                m,
+            )?;
+            let x = mux.mux(
+                layouter.namespace(|| format!("bit {}: square input", i)),
+                bit,
+                &r1,
+                &r0,
+            )?;
+            let sq = mod_mul.mod_mul(
+                layouter.namespace(|| format!("bit {}: square", i)),
+                &x,
+                &x,
+                m,
+            )?;
+
+            r0 = mux.mux(
+                layouter.namespace(|| format!("bit {}: R0", i)),
+                bit,
+                &prod,
+                &sq,
+            )?;
+            r1 = mux.mux(
+                layouter.namespace(|| format!("bit {}: R1", i)),
+                bit,
+                &sq,
+                &prod,
+            )?;
+        }
+
+        Ok(r0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const BITS: usize = 6;
+
+    // 输入是 [base, exp]，输出 base^exp mod m
+    #[derive(Clone, Default)]
+    struct ModExp {
+        m: u64,
+    }
+
+    impl TestGadget<Fp> for ModExp {
+        type Config = MontgomeryLadderConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MontgomeryLadderConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            MontgomeryLadderChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: MontgomeryLadderConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![MontgomeryLadderChip::construct(config)
+                .mod_exp(layouter, &inputs[0], &inputs[1], self.m, BITS)?])
+        }
+    }
+
+    // 普通的 square-and-multiply
+    fn native_mod_exp(base: u64, exp: u64, m: u64) -> u64 {
+        let m = m as u128;
+        let mut result = 1 % m;
+        let mut b = base as u128 % m;
+        let mut e = exp;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * b % m;
+            }
+            b = b * b % m;
+            e >>= 1;
+        }
+        result as u64
+    }
+
+    fn check(m: u64, base: u64, exp: u64, out: u64) -> bool {
+        run(
+            12,
+            ModExp { m },
+            &[Fp::from(base), Fp::from(exp)],
+            &[Fp::from(out)],
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn mod_exp_matches_native() {
+        let m = 1_000_000_007;
+        for (base, exp) in [
+            (2, 10),
+            (3, 63),
+            (123_456_789, 37),
+            (m - 1, 5),
+            (m + 5, 7),
+            (0, 9),
+        ] {
+            assert!(
+                check(m, base, exp, native_mod_exp(base, exp, m)),
+                "{}^{} mod {}",
+                base,
+                exp,
+                m
+            );
+        }
+        // 费马小定理：a^(p-1) = 1 (mod p)
+        assert_eq!(native_mod_exp(5, 12, 13), 1);
+        assert!(check(13, 5, 12, 1));
+    }
+
+    #[test]
+    fn mod_exp_zero_exponent() {
+        assert!(check(97, 42, 0, 1));
+        assert!(check(97, 0, 0, 1));
+        assert!(check(1, 42, 0, 0));
+    }
+
+    #[test]
+    fn mod_exp_rejects_wrong_output() {
+        assert!(!check(97, 3, 5, 243));
+        assert!(!check(97, 3, 5, 243 % 97 + 1));
+    }
+
+    #[test]
+    fn mod_exp_rejects_oversized_exponent() {
+        // exp 要 < 2^BITS
+        assert!(!check(97, 3, 1 << BITS, native_mod_exp(3, 1 << BITS, 97)));
+    }
+}
@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    recompose::{RecomposeChip, RecomposeConfig},
+};
+use crate::ACell;
+
+// Morton code（Z-order）：把 x 和 y 的bit交错排列
+//   morton 的第 2i 位 = x 的第 i 位，第 2i + 1 位 = y 的第 i 位
+// DecomposeChip 把 x, y 各拆成 bits 个bit（同时断言它们都 < 2^bits），
+// 再用 RecomposeChip 按 [x_0, y_0, x_1, y_1, ...] 拼回去
+// 结果有 2 * bits 位，RecomposeChip 要求它 < 2^128，所以 bits <= 63
+// x = 0 的时候 morton 只有奇数位，y = 0 的时候只有偶数位
+#[derive(Debug, Clone)]
+pub struct MortonConfig {
+    pub decompose: DecomposeConfig,
+    pub recompose: RecomposeConfig,
+}
+
+pub struct MortonChip<F: FieldExt> {
+    config: MortonConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MortonChip<F> {
+    pub fn construct(config: MortonConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> MortonConfig {
+        MortonConfig {
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            recompose: RecomposeChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn interleave(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        y: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let x_bits = decompose.decompose(layouter.namespace(|| "x bits"), x, bits)?;
+        let y_bits = decompose.decompose(layouter.namespace(|| "y bits"), y, bits)?;
+
+        let interleaved: Vec<_> = x_bits
+            .iter()
+            .zip(&y_bits)
+            .flat_map(|(x_i, y_i)| [x_i.0.clone(), y_i.0.clone()])
+            .collect();
+
+        let recompose = RecomposeChip::construct(self.config.recompose.clone());
+        recompose.recompose(layouter.namespace(|| "morton"), &interleaved, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const BITS: usize = 8;
+
+    // 输入是 [x, y]，输出 morton(x, y)
+    #[derive(Clone, Default)]
+    struct Morton;
+
+    impl TestGadget<Fp> for Morton {
+        type Config = MortonConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MortonConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            MortonChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: MortonConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                MortonChip::construct(config).interleave(layouter, &inputs[0], &inputs[1], BITS)?
+            ])
+        }
+    }
+
+    fn native_morton(x: u64, y: u64, bits: usize) -> u64 {
+        (0..bits).fold(0, |acc, i| {
+            acc | (((x >> i) & 1) << (2 * i)) | (((y >> i) & 1) << (2 * i + 1))
+        })
+    }
+
+    fn check(x: u64, y: u64, morton: u64) -> bool {
+        run(8, Morton, &[Fp::from(x), Fp::from(y)], &[Fp::from(morton)]).is_ok()
+    }
+
+    #[test]
+    fn morton_matches_native() {
+        assert_eq!(native_morton(0b11, 0b01, 2), 0b0111);
+        for (x, y) in [(0, 0), (5, 9), (0xff, 0xff), (0x0f, 0xf0), (200, 17)] {
+            assert!(check(x, y, native_morton(x, y, BITS)), "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn morton_zero_coordinate() {
+        // x = 0 只剩奇数位，y = 0 只剩偶数位
+        assert!(check(0, 0xff, 0xaaaa));
+        assert!(check(0xff, 0, 0x5555));
+    }
+
+    #[test]
+    fn morton_rejects_wrong_code() {
+        // x 和 y 弄反了
+        assert!(!check(5, 9, native_morton(9, 5, BITS)));
+        // 直接拼起来而不是交错
+        assert!(!check(5, 9, (9 << BITS) | 5));
+        // 坐标超出了 bits 位
+        assert!(!check(0x100, 0, native_morton(0x100, 0, BITS + 1)));
+    }
+}
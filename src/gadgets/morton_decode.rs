@@ -0,0 +1,202 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    recompose::{RecomposeChip, RecomposeConfig},
+};
+use crate::ACell;
+
+// MortonChip 反过来：把 morton 拆成 2 * bits 个bit（同时断言 morton < 2^(2 * bits)），
+// 偶数位用 RecomposeChip 拼成 x，奇数位拼成 y
+// 和 MortonChip 一样 bits <= 63；interleave 之后再 deinterleave 得到的就是原来的 (x, y)
+#[derive(Debug, Clone)]
+pub struct MortonDecodeConfig {
+    pub decompose: DecomposeConfig,
+    pub recompose: RecomposeConfig,
+}
+
+pub struct MortonDecodeChip<F: FieldExt> {
+    config: MortonDecodeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MortonDecodeChip<F> {
+    pub fn construct(config: MortonDecodeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> MortonDecodeConfig {
+        MortonDecodeConfig {
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            recompose: RecomposeChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn deinterleave(
+        &self,
+        mut layouter: impl Layouter<F>,
+        morton: &ACell<F>,
+        bits: usize,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let morton_bits =
+            decompose.decompose(layouter.namespace(|| "morton bits"), morton, 2 * bits)?;
+
+        let (x_bits, y_bits): (Vec<_>, Vec<_>) = morton_bits
+            .chunks(2)
+            .map(|pair| (pair[0].0.clone(), pair[1].0.clone()))
+            .unzip();
+
+        let recompose = RecomposeChip::construct(self.config.recompose.clone());
+        let x = recompose.recompose(layouter.namespace(|| "x"), &x_bits, 2)?;
+        let y = recompose.recompose(layouter.namespace(|| "y"), &y_bits, 2)?;
+
+        Ok((x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::{
+        morton::{MortonChip, MortonConfig},
+        testing::{run, TestGadget},
+    };
+
+    const BITS: usize = 8;
+
+    // 输入是 [morton]，输出 [x, y]
+    #[derive(Clone, Default)]
+    struct MortonDecode;
+
+    impl TestGadget<Fp> for MortonDecode {
+        type Config = MortonDecodeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MortonDecodeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            MortonDecodeChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: MortonDecodeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (x, y) =
+                MortonDecodeChip::construct(config).deinterleave(layouter, &inputs[0], BITS)?;
+            Ok(vec![x, y])
+        }
+    }
+
+    // 输入是 [x, y]，先用 MortonChip 编码再解码，输出 [x', y']
+    #[derive(Clone, Default)]
+    struct RoundTrip;
+
+    impl TestGadget<Fp> for RoundTrip {
+        type Config = (MortonConfig, MortonDecodeConfig);
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            (
+                MortonChip::configure(meta, advice, fixed),
+                MortonDecodeChip::configure(meta, advice, fixed),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let morton = MortonChip::construct(config.0).interleave(
+                layouter.namespace(|| "encode"),
+                &inputs[0],
+                &inputs[1],
+                BITS,
+            )?;
+            let (x, y) = MortonDecodeChip::construct(config.1).deinterleave(
+                layouter.namespace(|| "decode"),
+                &morton,
+                BITS,
+            )?;
+            Ok(vec![x, y])
+        }
+    }
+
+    fn native_decode(morton: u64, bits: usize) -> (u64, u64) {
+        (0..bits).fold((0, 0), |(x, y), i| {
+            (
+                x | (((morton >> (2 * i)) & 1) << i),
+                y | (((morton >> (2 * i + 1)) & 1) << i),
+            )
+        })
+    }
+
+    fn check(morton: u64, x: u64, y: u64) -> bool {
+        run(
+            8,
+            MortonDecode,
+            &[Fp::from(morton)],
+            &[Fp::from(x), Fp::from(y)],
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn morton_decode_matches_native() {
+        assert_eq!(native_decode(0b0111, 2), (0b11, 0b01));
+        for morton in [0, 1, 2, 0x5555, 0xaaaa, 0xffff, 0x1234, 0xbeef] {
+            let (x, y) = native_decode(morton, BITS);
+            assert!(check(morton, x, y), "{:#x}", morton);
+        }
+    }
+
+    #[test]
+    fn morton_round_trip() {
+        for (x, y) in [
+            (0, 0),
+            (0, 0xff),
+            (0xff, 0),
+            (5, 9),
+            (0xff, 0xff),
+            (200, 17),
+        ] {
+            let xy = [Fp::from(x), Fp::from(y)];
+            assert_eq!(run(9, RoundTrip, &xy, &xy), Ok(()), "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn morton_decode_rejects_wrong_coordinates() {
+        let (x, y) = native_decode(0x1234, BITS);
+        // x 和 y 弄反了
+        assert!(!check(0x1234, y, x));
+        // 高低两半而不是奇偶位
+        assert!(!check(0x1234, 0x34, 0x12));
+        // morton 超出了 2 * bits 位
+        assert!(!check(0x1_0000, 0, 0));
+    }
+}
@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// c = a * b
+//
+//  a | b | c | selector
+//
+#[derive(Debug, Clone)]
+pub struct MulConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+pub struct MulChip<F: FieldExt> {
+    config: MulConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MulChip<F> {
+    pub fn construct(config: MulConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> MulConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("mul", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![s * (a * b - c)]
+        });
+
+        MulConfig { advice, selector }
+    }
+
+    pub fn mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "mul",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = a.0.value().zip(b.0.value()).map(|(a, b)| *a * *b);
+
+                region
+                    .assign_advice(
+                        || "c",
+                        self.config.advice[2],
+                        0,
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Mul;
+
+    impl TestGadget<Fp> for Mul {
+        type Config = MulConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MulConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            MulChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: MulConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                MulChip::construct(config).mul(layouter, &inputs[0], &inputs[1])?
+            ])
+        }
+    }
+
+    #[test]
+    fn mul_matches_native() {
+        assert_eq!(
+            run(4, Mul, &[Fp::from(6), Fp::from(7)], &[Fp::from(42)]),
+            Ok(())
+        );
+        assert_eq!(run(4, Mul, &[-Fp::one(), -Fp::one()], &[Fp::one()]), Ok(()));
+    }
+
+    #[test]
+    fn mul_rejects_wrong_product() {
+        assert!(run(4, Mul, &[Fp::from(6), Fp::from(7)], &[Fp::from(13)]).is_err());
+    }
+}
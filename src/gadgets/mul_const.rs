@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// c = a * k，k 是一个放在fixed column里面的常数（每次调用都可以不一样）
+//
+//  a | c | k(fixed) | selector
+//
+#[derive(Debug, Clone)]
+pub struct MulConstConfig {
+    pub advice: [Column<Advice>; 2],
+    pub constant: Column<Fixed>,
+    pub selector: Selector,
+}
+
+pub struct MulConstChip<F: FieldExt> {
+    config: MulConstConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MulConstChip<F> {
+    pub fn construct(config: MulConstConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        constant: Column<Fixed>,
+    ) -> MulConstConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("mul const", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let c = meta.query_advice(advice[1], Rotation::cur());
+            let k = meta.query_fixed(constant, Rotation::cur());
+
+            vec![s * (a * k - c)]
+        });
+
+        MulConstConfig {
+            advice,
+            constant,
+            selector,
+        }
+    }
+
+    pub fn mul_const(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        k: F,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "mul const",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                region.assign_fixed(|| "k", self.config.constant, 0, || Ok(k))?;
+
+                let c_val = a.0.value().map(|a| *a * k);
+
+                region
+                    .assign_advice(
+                        || "c",
+                        self.config.advice[1],
+                        0,
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct MulConst {
+        k: u64,
+    }
+
+    impl TestGadget<Fp> for MulConst {
+        type Config = MulConstConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MulConstConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            let constant = meta.fixed_column();
+            MulConstChip::configure(meta, advice, constant)
+        }
+
+        fn synthesize(
+            &self,
+            config: MulConstConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![MulConstChip::construct(config).mul_const(
+                layouter,
+                &inputs[0],
+                Fp::from(self.k),
+            )?])
+        }
+    }
+
+    #[test]
+    fn mul_const_matches_native() {
+        for (a, k) in [(0u64, 5u64), (7, 0), (7, 1), (12, 100)] {
+            assert_eq!(
+                run(4, MulConst { k }, &[Fp::from(a)], &[Fp::from(a * k)]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn mul_const_rejects_wrong_product() {
+        assert!(run(4, MulConst { k: 3 }, &[Fp::from(5)], &[Fp::from(16)]).is_err());
+    }
+}
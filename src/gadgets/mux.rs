@@ -0,0 +1,140 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+use crate::ACell;
+
+// out = sel ? a : b
+// 写成多项式就是 out = b + sel * (a - b)，sel 已经是 Boolean 了，这里不再重复约束
+//
+//  sel | a | b | selector
+//  out |   |   |
+//
+#[derive(Debug, Clone)]
+pub struct MuxConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+pub struct MuxChip<F: FieldExt> {
+    config: MuxConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MuxChip<F> {
+    pub fn construct(config: MuxConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> MuxConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("mux", |meta| {
+            let s = meta.query_selector(selector);
+            let sel = meta.query_advice(advice[0], Rotation::cur());
+            let a = meta.query_advice(advice[1], Rotation::cur());
+            let b = meta.query_advice(advice[2], Rotation::cur());
+            let out = meta.query_advice(advice[0], Rotation::next());
+
+            vec![s * (b.clone() + sel * (a - b) - out)]
+        });
+
+        MuxConfig { advice, selector }
+    }
+
+    pub fn mux(
+        &self,
+        mut layouter: impl Layouter<F>,
+        sel: &Boolean<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "mux",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let sel = &sel.0;
+                sel.0
+                    .copy_advice(|| "sel", &mut region, self.config.advice[0], 0)?;
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[1], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[2], 0)?;
+
+                let out_val = sel
+                    .0
+                    .value()
+                    .zip(a.0.value())
+                    .zip(b.0.value())
+                    .map(|((sel, a), b)| if *sel == F::one() { *a } else { *b });
+
+                region
+                    .assign_advice(
+                        || "out",
+                        self.config.advice[0],
+                        1,
+                        || out_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 sel, a, b
+    #[derive(Clone, Default)]
+    struct Mux;
+
+    impl TestGadget<Fp> for Mux {
+        type Config = MuxConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MuxConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            MuxChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: MuxConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let sel = Boolean(inputs[0].clone());
+            Ok(vec![
+                MuxChip::construct(config).mux(layouter, &sel, &inputs[1], &inputs[2])?
+            ])
+        }
+    }
+
+    #[test]
+    fn mux_matches_native() {
+        let (a, b) = (Fp::from(11), Fp::from(22));
+        assert_eq!(run(4, Mux, &[Fp::one(), a, b], &[a]), Ok(()));
+        assert_eq!(run(4, Mux, &[Fp::zero(), a, b], &[b]), Ok(()));
+    }
+
+    #[test]
+    fn mux_rejects_wrong_branch() {
+        let (a, b) = (Fp::from(11), Fp::from(22));
+        assert!(run(4, Mux, &[Fp::one(), a, b], &[b]).is_err());
+        assert!(run(4, Mux, &[Fp::zero(), a, b], &[a]).is_err());
+    }
+}
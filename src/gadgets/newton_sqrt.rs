@@ -0,0 +1,242 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    div::{DivChip, DivConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    mul::{MulChip, MulConfig},
+    sub::{SubChip, SubConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// 整数平方根的一步 Newton 迭代：x' = (x + n / x) / 2（都是向下取整）
+// DivChip 只能除以常数，n / x 的除数是一个cell，所以这里自己witness q = n / x，再证明
+//   r = n - q * x，r < x
+// q 和 r 都range check到 bits 位，x 也是，所以 q * x + r == n 在field里面不会绕回来；x = 0 的时候 r < 0 不可能成立
+// 然后 AddChip 算 x + q，DivChip 除以 2
+// n, x 都需要 < 2^bits，DivChip 的商最多 QUOTIENT_BITS = 64 位，所以 bits <= 64
+// x 已经收敛到 isqrt(n) 之后，x' 要么还是 x，要么在 x 和 x + 1 之间来回（比如 n = k^2 - 1 的时候）
+#[derive(Debug, Clone)]
+pub struct NewtonSqrtStepConfig {
+    pub advice: Column<Advice>,
+    pub decompose: DecomposeConfig,
+    pub mul: MulConfig,
+    pub sub: SubConfig,
+    pub less_than: LessThanConfig,
+    pub add: AddConfig,
+    pub div: DivConfig,
+    pub bits: usize,
+}
+
+pub struct NewtonSqrtStepChip<F: FieldExt> {
+    config: NewtonSqrtStepConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> NewtonSqrtStepChip<F> {
+    pub fn construct(config: NewtonSqrtStepConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 用来放 LessThanChip 的 shift、DivChip 的除数，还有 r < x 要等于的常数 1
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> NewtonSqrtStepConfig {
+        meta.enable_equality(advice[0]);
+        meta.enable_constant(fixed);
+
+        NewtonSqrtStepConfig {
+            advice: advice[0],
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            mul: MulChip::configure(meta, advice),
+            sub: SubChip::configure(meta, advice),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            add: AddChip::configure(meta, advice),
+            div: DivChip::configure(meta, advice, fixed),
+            bits,
+        }
+    }
+
+    pub fn step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        n: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let bits = self.config.bits;
+        if bits > 64 {
+            return Err(Error::Synthesis);
+        }
+
+        let q = layouter.assign_region(
+            || "witness n / x",
+            |mut region| {
+                let q_val =
+                    n.0.value()
+                        .zip(x.0.value())
+                        .and_then(|(n, x)| to_u128(n).checked_div(to_u128(x)))
+                        .map(F::from_u128);
+
+                region
+                    .assign_advice(
+                        || "q",
+                        self.config.advice,
+                        0,
+                        || q_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let product = mul.mul(layouter.namespace(|| "q * x"), &q, x)?;
+        let sub = SubChip::construct(self.config.sub.clone());
+        let r = sub.sub(layouter.namespace(|| "r = n - q * x"), n, &product)?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check x"), x, bits)?;
+        decompose.decompose(layouter.namespace(|| "range check q"), &q, bits)?;
+        decompose.decompose(layouter.namespace(|| "range check r"), &r, bits)?;
+
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let lt = less_than.less_than(layouter.namespace(|| "r < x"), &r, x, bits)?;
+        layouter.assign_region(
+            || "r < x",
+            |mut region| region.constrain_constant(lt.0 .0.cell(), F::one()),
+        )?;
+
+        let add = AddChip::construct(self.config.add.clone());
+        let sum = add.add(layouter.namespace(|| "x + n / x"), x, &q)?;
+
+        let div = DivChip::construct(self.config.div.clone());
+        div.div(layouter.namespace(|| "/ 2"), &sum, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [x_0, n]，连续迭代 steps 次，输出每一次的 x
+    #[derive(Clone, Default)]
+    struct NewtonSqrt {
+        steps: usize,
+    }
+
+    impl TestGadget<Fp> for NewtonSqrt {
+        type Config = NewtonSqrtStepConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> NewtonSqrtStepConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            NewtonSqrtStepChip::configure(meta, advice, fixed, 16)
+        }
+
+        fn synthesize(
+            &self,
+            config: NewtonSqrtStepConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = NewtonSqrtStepChip::construct(config);
+            let mut x = inputs[0].clone();
+            let mut outputs = vec![];
+            for i in 0..self.steps {
+                x = chip.step(layouter.namespace(|| format!("step {}", i)), &x, &inputs[1])?;
+                outputs.push(x.clone());
+            }
+            Ok(outputs)
+        }
+    }
+
+    fn native_iterates(x: u64, n: u64, steps: usize) -> Vec<u64> {
+        std::iter::successors(Some(x), |&x| Some((x + n / x) / 2))
+            .skip(1)
+            .take(steps)
+            .collect()
+    }
+
+    fn native_isqrt(n: u64) -> u64 {
+        (0..=n).take_while(|r| r * r <= n).last().unwrap()
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn newton_sqrt_converges_like_native() {
+        for (x0, n, steps) in [(1000u64, 1000u64, 8), (60000, 65000, 12), (1, 50, 5)] {
+            let expected = native_iterates(x0, n, steps);
+            assert_eq!(*expected.last().unwrap(), native_isqrt(n), "n = {}", n);
+            assert_eq!(
+                run(12, NewtonSqrt { steps }, &fp(&[x0, n]), &fp(&expected)),
+                Ok(()),
+                "x_0 = {}, n = {}",
+                x0,
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn newton_sqrt_converged_step() {
+        // 已经收敛：144 的平方根是 12，x' 还是 12
+        assert_eq!(
+            run(10, NewtonSqrt { steps: 2 }, &fp(&[12, 144]), &fp(&[12, 12])),
+            Ok(())
+        );
+        // n = k^2 - 1 的时候在 k - 1 和 k 之间来回
+        assert_eq!(
+            run(
+                10,
+                NewtonSqrt { steps: 3 },
+                &fp(&[10, 99]),
+                &fp(&[9, 10, 9])
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn newton_sqrt_rejects_wrong_step() {
+        // (100 + 1000 / 100) / 2 = 55
+        assert!(run(10, NewtonSqrt { steps: 1 }, &fp(&[100, 1000]), &fp(&[55])).is_ok());
+        assert!(run(10, NewtonSqrt { steps: 1 }, &fp(&[100, 1000]), &fp(&[56])).is_err());
+        // x 超出了 bits 位
+        assert!(run(
+            10,
+            NewtonSqrt { steps: 1 },
+            &fp(&[1 << 16, 1000]),
+            &fp(&[32768])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn newton_sqrt_rejects_zero_guess() {
+        assert!(synthesis_fails(
+            10,
+            NewtonSqrt { steps: 1 },
+            &fp(&[0, 1000]),
+            &fp(&[0])
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    bitwise::{BitwiseChip, BitwiseConfig},
+    is_zero::{IsZeroChip, IsZeroConfig},
+};
+use crate::ACell;
+
+// Nim：所有堆的大小 XOR 起来等于 0 的局面是必败态（P-position）
+// 用 BitwiseChip 把所有堆 XOR 起来，再用 IsZeroChip 断言结果是 0
+// 每个堆都需要 < 2^bits
+// 没有堆（或者所有堆都是空的）的时候 nim-sum 是 0，先手已经没法走了，也是必败态
+#[derive(Debug, Clone)]
+pub struct NimConfig {
+    pub bitwise: BitwiseConfig,
+    pub is_zero: IsZeroConfig,
+}
+
+pub struct NimChip<F: FieldExt> {
+    config: NimConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> NimChip<F> {
+    pub fn construct(config: NimConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 用来放 is_zero 的结果要等于的常数 1
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> NimConfig {
+        meta.enable_constant(fixed);
+
+        NimConfig {
+            bitwise: BitwiseChip::configure(meta, advice),
+            is_zero: IsZeroChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn assert_losing_position(
+        &self,
+        mut layouter: impl Layouter<F>,
+        heaps: &[ACell<F>],
+        bits: usize,
+    ) -> Result<(), Error> {
+        if heaps.is_empty() {
+            return Ok(());
+        }
+
+        let bitwise = BitwiseChip::construct(self.config.bitwise.clone());
+        let mut nim_sum = heaps[0].clone();
+        for (i, heap) in heaps.iter().enumerate().skip(1) {
+            nim_sum = bitwise.xor(
+                layouter.namespace(|| format!("xor heap {}", i)),
+                &nim_sum,
+                heap,
+                bits,
+            )?;
+        }
+
+        let is_zero = IsZeroChip::construct(self.config.is_zero.clone());
+        let zero = is_zero.is_zero(layouter.namespace(|| "nim sum is zero"), &nim_sum)?;
+
+        layouter.assign_region(
+            || "losing position",
+            |mut region| region.constrain_constant(zero.0 .0.cell(), F::one()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Nim;
+
+    impl TestGadget<Fp> for Nim {
+        type Config = NimConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> NimConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            NimChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: NimConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            NimChip::construct(config).assert_losing_position(layouter, inputs, 4)?;
+            Ok(vec![])
+        }
+    }
+
+    fn heaps(sizes: &[u64]) -> Vec<Fp> {
+        sizes.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn nim_matches_native() {
+        for sizes in [
+            // P-positions
+            vec![],
+            vec![0u64],
+            vec![0, 0, 0],
+            vec![1, 2, 3],
+            vec![5, 5],
+            vec![15, 9, 6],
+            // N-positions
+            vec![1],
+            vec![1, 2],
+            vec![3, 4, 5],
+            vec![15, 15, 1],
+        ] {
+            let nim_sum = sizes.iter().fold(0, |acc, v| acc ^ v);
+            assert_eq!(
+                run(7, Nim, &heaps(&sizes), &[]).is_ok(),
+                nim_sum == 0,
+                "heaps {:?}",
+                sizes
+            );
+        }
+    }
+
+    #[test]
+    fn nim_rejects_heap_wider_than_bits() {
+        // 16 ^ 16 = 0，但是 16 放不进 4 位
+        assert!(run(7, Nim, &heaps(&[16, 16]), &[]).is_err());
+    }
+}
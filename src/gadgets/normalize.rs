@@ -0,0 +1,137 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    is_zero::{IsZeroChip, IsZeroConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 大整数的limbs（little-endian，最后一个是最高位的limb）没有多余的前导0
+// 也就是说：最高位limb是0的话，所有limb都必须是0（0 本身的表示就是全0）
+//
+// 令 top_zero = is_zero(limb_{n-1})，all_zero = Π is_zero(limb_i)
+// all_zero 一定蕴含 top_zero，所以 “top_zero 蕴含 all_zero” 就等价于 top_zero == all_zero，
+// 直接用一个copy constraint约束就行
+#[derive(Debug, Clone)]
+pub struct NormalizeConfig {
+    pub is_zero: IsZeroConfig,
+    pub mul: MulConfig,
+}
+
+pub struct NormalizeChip<F: FieldExt> {
+    config: NormalizeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> NormalizeChip<F> {
+    pub fn construct(config: NormalizeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> NormalizeConfig {
+        NormalizeConfig {
+            is_zero: IsZeroChip::configure(meta, [advice[0], advice[1]]),
+            mul: MulChip::configure(meta, advice),
+        }
+    }
+
+    // 空的limbs也当作 0，认为是normalized
+    pub fn assert_normalized(
+        &self,
+        mut layouter: impl Layouter<F>,
+        limbs: &[ACell<F>],
+    ) -> Result<(), Error> {
+        if limbs.is_empty() {
+            return Ok(());
+        }
+
+        let is_zero = IsZeroChip::construct(self.config.is_zero.clone());
+        let mul = MulChip::construct(self.config.mul.clone());
+
+        let mut zeros = Vec::with_capacity(limbs.len());
+        for (i, limb) in limbs.iter().enumerate() {
+            let zero =
+                is_zero.is_zero(layouter.namespace(|| format!("limb {} is zero", i)), limb)?;
+            zeros.push(zero.0);
+        }
+
+        let mut all_zero = zeros[0].clone();
+        for zero in &zeros[1..] {
+            all_zero = mul.mul(layouter.namespace(|| "all zero"), &all_zero, zero)?;
+        }
+
+        let top_zero = zeros.last().unwrap();
+        layouter.assign_region(
+            || "top limb zero => all zero",
+            |mut region| region.constrain_equal(top_zero.0.cell(), all_zero.0.cell()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Normalize;
+
+    impl TestGadget<Fp> for Normalize {
+        type Config = NormalizeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> NormalizeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            NormalizeChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: NormalizeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            NormalizeChip::construct(config).assert_normalized(layouter, inputs)?;
+            Ok(vec![])
+        }
+    }
+
+    fn limbs(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn normalize_accepts_normalized_limbs() {
+        for values in [
+            vec![1u64],
+            vec![0, 0, 7],
+            vec![5, 0, 1],
+            // 0 就是全 0
+            vec![0, 0, 0],
+            vec![0],
+            vec![],
+        ] {
+            assert_eq!(run(5, Normalize, &limbs(&values), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn normalize_rejects_leading_zero_limb() {
+        for values in [vec![1u64, 0], vec![0, 3, 0], vec![5, 6, 0]] {
+            assert!(run(5, Normalize, &limbs(&values), &[]).is_err());
+        }
+    }
+}
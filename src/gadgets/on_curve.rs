@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 证明 (x, y) 在曲线 y^2 = x^3 + a * x + b 上（short Weierstrass，a 和 b 是常数）
+// 用 MulChip 算各个power，AddChip 把右边加起来，最后用 IsEqualChip 断言左右相等
+// 注意：无穷远点没有 (x, y) 的表示，不在这个chip的考虑范围内
+#[derive(Debug, Clone)]
+pub struct OnCurveConfig {
+    pub add: AddConfig,
+    pub mul: MulConfig,
+    pub is_equal: IsEqualConfig,
+    pub constant: ConstantConfig,
+}
+
+pub struct OnCurveChip<F: FieldExt> {
+    config: OnCurveConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> OnCurveChip<F> {
+    pub fn construct(config: OnCurveConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        constant: Column<Fixed>,
+    ) -> OnCurveConfig {
+        OnCurveConfig {
+            add: AddChip::configure(meta, advice),
+            mul: MulChip::configure(meta, advice),
+            is_equal: IsEqualChip::configure(meta, advice),
+            constant: ConstantChip::configure(meta, advice[0], constant),
+        }
+    }
+
+    pub fn assert_on_curve(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        y: &ACell<F>,
+        a: F,
+        b: F,
+    ) -> Result<(), Error> {
+        let add = AddChip::construct(self.config.add.clone());
+        let mul = MulChip::construct(self.config.mul.clone());
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        let constant = ConstantChip::construct(self.config.constant.clone());
+
+        // 左边：y^2
+        let y2 = mul.mul(layouter.namespace(|| "y^2"), y, y)?;
+
+        // 右边：x^3 + a * x + b
+        let x2 = mul.mul(layouter.namespace(|| "x^2"), x, x)?;
+        let x3 = mul.mul(layouter.namespace(|| "x^3"), &x2, x)?;
+        let a = constant.load_constant(layouter.namespace(|| "a"), a)?;
+        let ax = mul.mul(layouter.namespace(|| "a * x"), &a, x)?;
+        let b = constant.load_constant(layouter.namespace(|| "b"), b)?;
+        let rhs = add.add(layouter.namespace(|| "x^3 + a * x"), &x3, &ax)?;
+        let rhs = add.add(layouter.namespace(|| "x^3 + a * x + b"), &rhs, &b)?;
+
+        is_equal.assert_equal(layouter.namespace(|| "y^2 == rhs"), &y2, &rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct OnCurve {
+        a: u64,
+        b: u64,
+    }
+
+    impl TestGadget<Fp> for OnCurve {
+        type Config = OnCurveConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> OnCurveConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            OnCurveChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: OnCurveConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            OnCurveChip::construct(config).assert_on_curve(
+                layouter,
+                &inputs[0],
+                &inputs[1],
+                Fp::from(self.a),
+                Fp::from(self.b),
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    // Pallas 本身就是 y^2 = x^3 + 5，(-1, 2) 是它的generator
+    const PALLAS: OnCurve = OnCurve { a: 0, b: 5 };
+
+    #[test]
+    fn on_curve_accepts_points_on_curve() {
+        assert_eq!(run(5, PALLAS, &[-Fp::one(), Fp::from(2)], &[]), Ok(()));
+        assert_eq!(run(5, PALLAS, &[-Fp::one(), -Fp::from(2)], &[]), Ok(()));
+        // y^2 = x^3 + 2x + 3 上的 (3, 6)：27 + 6 + 3 = 36
+        assert_eq!(
+            run(5, OnCurve { a: 2, b: 3 }, &[Fp::from(3), Fp::from(6)], &[]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn on_curve_rejects_off_curve_point() {
+        assert!(run(5, PALLAS, &[-Fp::one(), Fp::from(3)], &[]).is_err());
+        assert!(run(5, OnCurve { a: 2, b: 3 }, &[Fp::from(3), Fp::from(7)], &[]).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::Boolean;
+
+// 证明一组flag是 one-hot 的：每个flag都是boolean，而且刚好有一个是1
+// 用一个running sum把所有flag加起来，最后一行的 acc 必须等于 1
+//
+//  flag | acc | q_first | q_step | q_last
+//  f_0  | f_0 |    1    |   0    |   0
+//  f_1  | ... |    0    |   1    |   0
+//  f_n  |  1  |    0    |   1    |   1
+//
+#[derive(Debug, Clone)]
+pub struct OneHotConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub q_last: Selector,
+}
+
+pub struct OneHotChip<F: FieldExt> {
+    config: OneHotConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> OneHotChip<F> {
+    pub fn construct(config: OneHotConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> OneHotConfig {
+        let col_flag = advice[0];
+        let col_acc = advice[1];
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+        let q_last = meta.selector();
+
+        meta.enable_equality(col_flag);
+        meta.enable_equality(col_acc);
+
+        // 第一行没有 acc_prev，单独一个gate
+        meta.create_gate("one hot first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let flag = meta.query_advice(col_flag, Rotation::cur());
+            let acc = meta.query_advice(col_acc, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                // Boolean 只是一个类型上的约定，这里还是再约束一次
+                q_first.clone() * flag.clone() * (one - flag.clone()),
+                q_first * (acc - flag),
+            ]
+        });
+
+        meta.create_gate("one hot step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let flag = meta.query_advice(col_flag, Rotation::cur());
+            let acc = meta.query_advice(col_acc, Rotation::cur());
+            let acc_prev = meta.query_advice(col_acc, Rotation::prev());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_step.clone() * flag.clone() * (one - flag.clone()),
+                q_step * (acc - (acc_prev + flag)),
+            ]
+        });
+
+        meta.create_gate("one hot last", |meta| {
+            let q_last = meta.query_selector(q_last);
+            let acc = meta.query_advice(col_acc, Rotation::cur());
+
+            vec![q_last * (acc - Expression::Constant(F::one()))]
+        });
+
+        OneHotConfig {
+            advice,
+            q_first,
+            q_step,
+            q_last,
+        }
+    }
+
+    // 空的flags不可能是 one-hot 的，直接返回错误
+    pub fn assert_one_hot(
+        &self,
+        mut layouter: impl Layouter<F>,
+        flags: &[Boolean<F>],
+    ) -> Result<(), Error> {
+        if flags.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "one hot",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+
+                for (row, flag) in flags.iter().enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+                    if row == flags.len() - 1 {
+                        self.config.q_last.enable(&mut region, row)?;
+                    }
+
+                    let flag = &flag.0;
+                    flag.0
+                        .copy_advice(|| "flag", &mut region, self.config.advice[0], row)?;
+
+                    acc_val = acc_val.zip(flag.0.value()).map(|(acc, f)| acc + *f);
+                    region.assign_advice(
+                        || "acc",
+                        self.config.advice[1],
+                        row,
+                        || acc_val.ok_or(Error::Synthesis),
+                    )?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::{
+        gadgets::testing::{run, synthesis_fails, TestGadget},
+        ACell,
+    };
+
+    #[derive(Clone, Default)]
+    struct OneHot;
+
+    impl TestGadget<Fp> for OneHot {
+        type Config = OneHotConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> OneHotConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            OneHotChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: OneHotConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let flags: Vec<_> = inputs.iter().cloned().map(Boolean).collect();
+            OneHotChip::construct(config).assert_one_hot(layouter, &flags)?;
+            Ok(vec![])
+        }
+    }
+
+    fn fp(v: &[u64]) -> Vec<Fp> {
+        v.iter().map(|x| Fp::from(*x)).collect()
+    }
+
+    #[test]
+    fn one_hot_accepts_single_one() {
+        for flags in [&[1u64][..], &[1, 0, 0], &[0, 1, 0], &[0, 0, 0, 1]] {
+            assert_eq!(run(5, OneHot, &fp(flags), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn one_hot_rejects_zero_or_many_ones() {
+        assert!(run(5, OneHot, &fp(&[0, 0, 0]), &[]).is_err());
+        assert!(run(5, OneHot, &fp(&[1, 1, 0]), &[]).is_err());
+    }
+
+    #[test]
+    fn one_hot_rejects_non_boolean_flags() {
+        // 2 + (-1) = 1，只有 boolean 约束能挡住
+        assert!(run(5, OneHot, &[Fp::from(2), -Fp::one()], &[]).is_err());
+    }
+
+    #[test]
+    fn one_hot_rejects_empty_flags() {
+        assert!(synthesis_fails(5, OneHot, &[], &[]));
+    }
+}
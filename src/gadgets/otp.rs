@@ -0,0 +1,133 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::byte_xor::{ByteXorChip, ByteXorConfig};
+use crate::ACell;
+
+// one-time pad：cipher_i == plain_i XOR key_i，逐byte检查
+// 直接复用 ByteXorChip 的lookup table，所以 plain / key / cipher 也都被约束成了byte
+// 注意：用之前要先调用 load 把xor table放进电路
+#[derive(Debug, Clone)]
+pub struct OtpConfig {
+    pub byte_xor: ByteXorConfig,
+}
+
+pub struct OtpChip<F: FieldExt> {
+    config: OtpConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> OtpChip<F> {
+    pub fn construct(config: OtpConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> OtpConfig {
+        OtpConfig {
+            byte_xor: ByteXorChip::configure(meta, advice),
+        }
+    }
+
+    pub fn load(&self, layouter: impl Layouter<F>) -> Result<(), Error> {
+        ByteXorChip::construct(self.config.byte_xor.clone()).load(layouter)
+    }
+
+    pub fn assert_otp(
+        &self,
+        mut layouter: impl Layouter<F>,
+        plain: &[ACell<F>],
+        key: &[ACell<F>],
+        cipher: &[ACell<F>],
+    ) -> Result<(), Error> {
+        if plain.len() != key.len() || plain.len() != cipher.len() {
+            return Err(Error::Synthesis);
+        }
+
+        let byte_xor = ByteXorChip::construct(self.config.byte_xor.clone());
+        for (i, ((p, k), c)) in plain.iter().zip(key).zip(cipher).enumerate() {
+            byte_xor.assert_xor(layouter.namespace(|| format!("byte {}", i)), p, k, c)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 plain ++ key ++ cipher，三段一样长
+    #[derive(Clone, Default)]
+    struct Otp;
+
+    impl TestGadget<Fp> for Otp {
+        type Config = OtpConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> OtpConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            OtpChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: OtpConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = OtpChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+            let n = inputs.len() / 3;
+            chip.assert_otp(layouter, &inputs[..n], &inputs[n..2 * n], &inputs[2 * n..])?;
+            Ok(vec![])
+        }
+    }
+
+    fn inputs(plain: &[u8], key: &[u8], cipher: &[u8]) -> Vec<Fp> {
+        plain
+            .iter()
+            .chain(key)
+            .chain(cipher)
+            .map(|b| Fp::from(*b as u64))
+            .collect()
+    }
+
+    fn encrypt(plain: &[u8], key: &[u8]) -> Vec<u8> {
+        plain.iter().zip(key).map(|(p, k)| p ^ k).collect()
+    }
+
+    #[test]
+    fn otp_accepts_native_encryption() {
+        let (plain, key) = (
+            b"attack at dawn",
+            b"\x13\x37\xc0\xde\x00\xff\x42\x99\x01\x02\x03\x04\x05\x06",
+        );
+        let cipher = encrypt(plain, key);
+        assert_eq!(run(17, Otp, &inputs(plain, key, &cipher), &[]), Ok(()));
+    }
+
+    #[test]
+    fn otp_rejects_tampered_ciphertext() {
+        let (plain, key) = (b"hi!", b"\x01\x02\x03");
+        let mut cipher = encrypt(plain, key);
+        cipher[1] ^= 0x80;
+        assert!(run(17, Otp, &inputs(plain, key, &cipher), &[]).is_err());
+    }
+
+    #[test]
+    fn otp_rejects_length_mismatch() {
+        // 4 个输入会被拆成 [1] [1] [1, 1]
+        let ins = [Fp::one(), Fp::one(), Fp::one(), Fp::one()];
+        assert!(synthesis_fails(17, Otp, &ins, &[]));
+    }
+}
@@ -0,0 +1,142 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::base_b::{BaseBChip, BaseBConfig};
+use crate::ACell;
+
+// 证明 x 的 base 进制表示（固定 num_digits 位，高位补0）是一个回文数
+// 用 BaseBChip 拆出digits，然后直接用copy constraint约束 digit_i == digit_{n-1-i}
+// 奇数位的时候中间那一位不用管，只有一位的时候什么都不用约束
+#[derive(Debug, Clone)]
+pub struct PalindromeConfig {
+    pub base_b: BaseBConfig,
+}
+
+pub struct PalindromeChip<F: FieldExt> {
+    config: PalindromeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PalindromeChip<F> {
+    pub fn construct(config: PalindromeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> PalindromeConfig {
+        PalindromeConfig {
+            base_b: BaseBChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn assert_palindrome(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        base: u64,
+        num_digits: usize,
+    ) -> Result<(), Error> {
+        let base_b = BaseBChip::construct(self.config.base_b.clone());
+        let digits = base_b.decompose(layouter.namespace(|| "digits"), x, base, num_digits)?;
+
+        layouter.assign_region(
+            || "palindrome",
+            |mut region| {
+                for i in 0..num_digits / 2 {
+                    region
+                        .constrain_equal(digits[i].0.cell(), digits[num_digits - 1 - i].0.cell())?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Palindrome {
+        base: u64,
+        num_digits: usize,
+    }
+
+    impl TestGadget<Fp> for Palindrome {
+        type Config = PalindromeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PalindromeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            PalindromeChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: PalindromeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            PalindromeChip::construct(config).assert_palindrome(
+                layouter,
+                &inputs[0],
+                self.base,
+                self.num_digits,
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    fn is_palindrome(mut x: u64, base: u64, num_digits: usize) -> bool {
+        let digits: Vec<_> = (0..num_digits)
+            .map(|_| {
+                let d = x % base;
+                x /= base;
+                d
+            })
+            .collect();
+        digits.iter().eq(digits.iter().rev())
+    }
+
+    #[test]
+    fn palindrome_matches_native() {
+        for (x, base, num_digits) in [
+            // 一位数
+            (7u64, 10u64, 1usize),
+            // 奇数位 / 偶数位
+            (121, 10, 3),
+            (123, 10, 3),
+            (1221, 10, 4),
+            (1231, 10, 4),
+            // 高位补0之后 0110 是回文，110 不是
+            (110, 10, 3),
+            (110, 10, 4),
+            (0b1001, 2, 4),
+            (0b1011, 2, 4),
+        ] {
+            let g = Palindrome { base, num_digits };
+            assert_eq!(
+                run(6, g, &[Fp::from(x)], &[]).is_ok(),
+                is_palindrome(x, base, num_digits),
+                "x = {}, base = {}, num_digits = {}",
+                x,
+                base,
+                num_digits
+            );
+        }
+    }
+}
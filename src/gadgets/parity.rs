@@ -0,0 +1,104 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 判断一个数是奇数还是偶数
+// 做法就是把 x 拆成bits，最低位就是parity flag：0 是偶数，1 是奇数
+// 等价于 x = 2q + r，其中 r 是boolean，q 也被range check到了 bits - 1 位
+#[derive(Debug, Clone)]
+pub struct ParityConfig {
+    pub decompose: DecomposeConfig,
+}
+
+pub struct ParityChip<F: FieldExt> {
+    config: ParityConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ParityChip<F> {
+    pub fn construct(config: ParityConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> ParityConfig {
+        ParityConfig {
+            decompose: DecomposeChip::configure(meta, advice),
+        }
+    }
+
+    // 注意：x 必须 < 2^bits，否则decompose那边就过不了
+    pub fn parity(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bits: usize,
+    ) -> Result<Boolean<F>, Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let bits = decompose.decompose(layouter.namespace(|| "decompose"), x, bits)?;
+
+        Ok(bits[0].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Parity {
+        bits: usize,
+    }
+
+    impl TestGadget<Fp> for Parity {
+        type Config = ParityConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ParityConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            ParityChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: ParityConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let flag = ParityChip::construct(config).parity(layouter, &inputs[0], self.bits)?;
+            Ok(vec![flag.0])
+        }
+    }
+
+    #[test]
+    fn parity_matches_native() {
+        for x in [0u64, 1, 2, 7, 100, 255] {
+            let expected = Fp::from(x % 2);
+            assert_eq!(
+                run(5, Parity { bits: 8 }, &[Fp::from(x)], &[expected]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn parity_rejects_wrong_flag() {
+        assert!(run(5, Parity { bits: 8 }, &[Fp::from(6)], &[Fp::one()]).is_err());
+        assert!(run(5, Parity { bits: 8 }, &[Fp::from(7)], &[Fp::zero()]).is_err());
+    }
+
+    #[test]
+    fn parity_rejects_value_wider_than_bits() {
+        assert!(run(5, Parity { bits: 8 }, &[Fp::from(256)], &[Fp::zero()]).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    bitwise::{BitwiseChip, BitwiseConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 一个字节的校验位（偶校验）：parity = b_0 ^ b_1 ^ ... ^ b_7，也就是 popcount 的奇偶
+// 先用 DecomposeChip 拆成 8 个bit（同时断言 byte < 256），再用 BitwiseChip 一位一位 XOR 起来
+// BitwiseChip 会把两个输入都range check到 1 位，a ^ b = a + b - 2 * (a & b) 只可能是 0 或 1，
+// 所以结果可以直接当 Boolean 用
+#[derive(Debug, Clone)]
+pub struct ParityBitConfig {
+    pub decompose: DecomposeConfig,
+    pub bitwise: BitwiseConfig,
+}
+
+pub struct ParityBitChip<F: FieldExt> {
+    config: ParityBitConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ParityBitChip<F> {
+    pub fn construct(config: ParityBitConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> ParityBitConfig {
+        ParityBitConfig {
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            bitwise: BitwiseChip::configure(meta, advice),
+        }
+    }
+
+    pub fn parity_bit(
+        &self,
+        mut layouter: impl Layouter<F>,
+        byte: &ACell<F>,
+    ) -> Result<Boolean<F>, Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let bits = decompose.decompose(layouter.namespace(|| "byte bits"), byte, 8)?;
+
+        let bitwise = BitwiseChip::construct(self.config.bitwise.clone());
+        let mut parity = bits[0].0.clone();
+        for (i, bit) in bits.iter().enumerate().skip(1) {
+            parity = bitwise.xor(
+                layouter.namespace(|| format!("xor bit {}", i)),
+                &parity,
+                &bit.0,
+                1,
+            )?;
+        }
+
+        Ok(Boolean(parity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct ParityBit;
+
+    impl TestGadget<Fp> for ParityBit {
+        type Config = ParityBitConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ParityBitConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            ParityBitChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: ParityBitConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let parity = ParityBitChip::construct(config).parity_bit(layouter, &inputs[0])?;
+            Ok(vec![parity.0])
+        }
+    }
+
+    fn parity(byte: u8) -> Fp {
+        Fp::from((byte.count_ones() % 2) as u64)
+    }
+
+    #[test]
+    fn parity_bit_matches_popcount() {
+        for byte in [0x00u8, 0x01, 0x03, 0x80, 0x7f, 0xff, 0xa5, 0x5b] {
+            assert_eq!(
+                run(6, ParityBit, &[Fp::from(byte as u64)], &[parity(byte)]),
+                Ok(()),
+                "byte {:#04x}",
+                byte
+            );
+        }
+    }
+
+    #[test]
+    fn parity_bit_rejects_wrong_parity() {
+        for byte in [0x00u8, 0x01, 0xfe] {
+            let wrong = Fp::one() - parity(byte);
+            assert!(run(6, ParityBit, &[Fp::from(byte as u64)], &[wrong]).is_err());
+        }
+    }
+
+    #[test]
+    fn parity_bit_rejects_non_byte() {
+        // 0x101 的低 8 位是 0x01，但它不是一个字节
+        assert!(run(6, ParityBit, &[Fp::from(0x101)], &[Fp::zero()]).is_err());
+        assert!(run(6, ParityBit, &[Fp::from(0x101)], &[Fp::one()]).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::adjacency::{AdjacencyChip, AdjacencyConfig};
+use crate::ACell;
+
+// 证明 nodes 是图里面的一条路径：每一对相邻的点都是一条边
+// 边的table和 AdjacencyChip 是同一个，用之前记得先 config.adjacency.load(...)
+// 只有一个点（或者没有点）的路径不需要任何约束
+#[derive(Debug, Clone)]
+pub struct PathConfig {
+    pub adjacency: AdjacencyConfig,
+}
+
+pub struct PathChip<F: FieldExt> {
+    config: PathConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PathChip<F> {
+    pub fn construct(config: PathConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> PathConfig {
+        PathConfig {
+            adjacency: AdjacencyChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_path(
+        &self,
+        mut layouter: impl Layouter<F>,
+        nodes: &[ACell<F>],
+    ) -> Result<(), Error> {
+        let adjacency = AdjacencyChip::construct(self.config.adjacency.clone());
+        for (i, pair) in nodes.windows(2).enumerate() {
+            adjacency.assert_edge(
+                layouter.namespace(|| format!("edge {}", i)),
+                &pair[0],
+                &pair[1],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 0 -> 1 -> 2 -> 3，外加一条回边 3 -> 0
+    const EDGES: [(u64, u64); 4] = [(0, 1), (1, 2), (2, 3), (3, 0)];
+
+    #[derive(Clone, Default)]
+    struct Path;
+
+    impl TestGadget<Fp> for Path {
+        type Config = PathConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PathConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            PathChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: PathConfig,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let edges: Vec<_> = EDGES
+                .iter()
+                .map(|(u, v)| (Fp::from(*u), Fp::from(*v)))
+                .collect();
+            config
+                .adjacency
+                .load(layouter.namespace(|| "table"), &edges)?;
+            PathChip::construct(config).assert_path(layouter, inputs)?;
+            Ok(vec![])
+        }
+    }
+
+    fn nodes(path: &[u64]) -> Vec<Fp> {
+        path.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn path_accepts_valid_paths() {
+        for path in [
+            vec![],
+            vec![2u64],
+            vec![0, 1],
+            vec![0, 1, 2, 3, 0, 1],
+            vec![3, 0],
+        ] {
+            assert_eq!(run(5, Path, &nodes(&path), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn path_rejects_broken_link() {
+        for path in [
+            vec![0u64, 2],
+            vec![0, 1, 2, 1],
+            vec![1, 0],
+            vec![0, 1, 2, 3, 3],
+        ] {
+            assert!(run(5, Path, &nodes(&path), &[]).is_err());
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::sorted::{SortedChip, SortedConfig};
+use crate::ACell;
+
+// 证明 value 是有序数组的第 p 百分位数（nearest-rank 的向下取整版本）：
+//   idx = floor(p * (n - 1) / 100)，value == sorted[idx]
+// p 和 n 在电路里面都是常数，所以 idx 也是常数，不需要 RankChip 去"找"，直接copy constraint就行
+// 数组本身用 SortedChip 断言是非递减的；第 0 百分位是最小值，第 100 百分位是最大值
+// 所有value都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct PercentileConfig {
+    pub sorted: SortedConfig,
+    pub bits: usize,
+}
+
+pub struct PercentileChip<F: FieldExt> {
+    config: PercentileConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PercentileChip<F> {
+    pub fn construct(config: PercentileConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        bits: usize,
+    ) -> PercentileConfig {
+        PercentileConfig {
+            sorted: SortedChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    pub fn assert_percentile(
+        &self,
+        mut layouter: impl Layouter<F>,
+        sorted: &[ACell<F>],
+        p: u64,
+        value: &ACell<F>,
+    ) -> Result<(), Error> {
+        if sorted.is_empty() || p > 100 {
+            return Err(Error::Synthesis);
+        }
+
+        let sorted_chip = SortedChip::construct(self.config.sorted.clone());
+        sorted_chip.assert_sorted(layouter.namespace(|| "sorted"), sorted, self.config.bits)?;
+
+        let idx = (p as usize * (sorted.len() - 1)) / 100;
+        layouter.assign_region(
+            || format!("value == sorted[{}]", idx),
+            |mut region| region.constrain_equal(sorted[idx].0.cell(), value.0.cell()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 sorted...，最后一个是 value
+    #[derive(Clone, Default)]
+    struct Percentile {
+        p: u64,
+    }
+
+    impl TestGadget<Fp> for Percentile {
+        type Config = PercentileConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PercentileConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            PercentileChip::configure(meta, advice, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: PercentileConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (value, sorted) = inputs.split_last().unwrap();
+            PercentileChip::construct(config).assert_percentile(layouter, sorted, self.p, value)?;
+            Ok(vec![])
+        }
+    }
+
+    fn percentile(sorted: &[u64], p: u64) -> u64 {
+        sorted[(p as usize * (sorted.len() - 1)) / 100]
+    }
+
+    fn inputs(sorted: &[u64], value: u64) -> Vec<Fp> {
+        sorted
+            .iter()
+            .chain([&value])
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    #[test]
+    fn percentile_matches_native() {
+        let sorted = [1u64, 3, 3, 8, 13, 21, 34, 55, 89, 144, 233];
+        // 第 0 百分位是最小值，第 100 百分位是最大值
+        for p in [0u64, 10, 25, 50, 75, 99, 100] {
+            let value = percentile(&sorted, p);
+            assert_eq!(
+                run(7, Percentile { p }, &inputs(&sorted, value), &[]),
+                Ok(()),
+                "p = {}",
+                p
+            );
+        }
+        assert_eq!(
+            run(7, Percentile { p: 50 }, &inputs(&[42], 42), &[]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn percentile_rejects_wrong_value() {
+        let sorted = [1u64, 3, 3, 8, 13];
+        // idx = floor(50 * 4 / 100) = 2，不是向上取整之后的 sorted[3]
+        assert!(run(7, Percentile { p: 50 }, &inputs(&sorted, 8), &[]).is_err());
+        assert!(run(7, Percentile { p: 100 }, &inputs(&sorted, 8), &[]).is_err());
+    }
+
+    #[test]
+    fn percentile_rejects_unsorted_input() {
+        // value 对得上 sorted[2]，但是数组不是有序的
+        assert!(run(7, Percentile { p: 50 }, &inputs(&[9, 1, 5, 2, 3], 5), &[]).is_err());
+        assert!(synthesis_fails(
+            7,
+            Percentile { p: 101 },
+            &inputs(&[1, 2], 2),
+            &[]
+        ));
+        assert!(synthesis_fails(
+            7,
+            Percentile { p: 50 },
+            &inputs(&[], 0),
+            &[]
+        ));
+    }
+}
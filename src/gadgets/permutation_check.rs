@@ -0,0 +1,215 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// 证明两个数组是彼此的permutation（作为multiset相等）
+// 经典的grand product做法：Π (a_i + gamma) == Π (b_i + gamma)
+// gamma 应该是一个prover事先不知道的随机数（比如由verifier通过instance给出），
+// 如果prover能提前知道 gamma，就有可能构造出碰撞
+//
+//  value | gamma | acc                       | q_first | q_step
+//   v_0  |   g   | v_0 + g                   |    1    |   0
+//   v_1  |   g   | acc_prev * (v_1 + g)      |    0    |   1
+//
+#[derive(Debug, Clone)]
+pub struct PermutationCheckConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_first: Selector,
+    pub q_step: Selector,
+}
+
+pub struct PermutationCheckChip<F: FieldExt> {
+    config: PermutationCheckConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PermutationCheckChip<F> {
+    pub fn construct(config: PermutationCheckConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> PermutationCheckConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("grand product first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let value = meta.query_advice(advice[0], Rotation::cur());
+            let gamma = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![q_first * (acc - (value + gamma))]
+        });
+
+        meta.create_gate("grand product step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let value = meta.query_advice(advice[0], Rotation::cur());
+            let gamma = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[2], Rotation::prev());
+
+            vec![q_step * (acc - acc_prev * (value + gamma))]
+        });
+
+        PermutationCheckConfig {
+            advice,
+            q_first,
+            q_step,
+        }
+    }
+
+    // Π (v_i + gamma)，values 不能是空的
+    pub fn grand_product(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+        gamma: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        if values.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "grand product",
+            |mut region| {
+                let mut acc_val = Some(F::one());
+                let mut acc = None;
+
+                for (row, value) in values.iter().enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    value
+                        .0
+                        .copy_advice(|| "value", &mut region, self.config.advice[0], row)?;
+                    gamma
+                        .0
+                        .copy_advice(|| "gamma", &mut region, self.config.advice[1], row)?;
+
+                    acc_val = acc_val
+                        .zip(value.0.value())
+                        .zip(gamma.0.value())
+                        .map(|((acc, v), g)| acc * (*v + *g));
+                    acc = Some(
+                        region
+                            .assign_advice(
+                                || "acc",
+                                self.config.advice[2],
+                                row,
+                                || acc_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?,
+                    );
+                }
+
+                acc.ok_or(Error::Synthesis)
+            },
+        )
+    }
+
+    pub fn assert_permutation(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[ACell<F>],
+        b: &[ACell<F>],
+        gamma: &ACell<F>,
+    ) -> Result<(), Error> {
+        if a.len() != b.len() {
+            return Err(Error::Synthesis);
+        }
+        if a.is_empty() {
+            return Ok(());
+        }
+
+        let prod_a = self.grand_product(layouter.namespace(|| "product a"), a, gamma)?;
+        let prod_b = self.grand_product(layouter.namespace(|| "product b"), b, gamma)?;
+
+        layouter.assign_region(
+            || "products equal",
+            |mut region| region.constrain_equal(prod_a.0.cell(), prod_b.0.cell()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 a ++ b ++ [gamma]，a 和 b 一样长
+    #[derive(Clone, Default)]
+    struct Permutation;
+
+    impl TestGadget<Fp> for Permutation {
+        type Config = PermutationCheckConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PermutationCheckConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            PermutationCheckChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: PermutationCheckConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (gamma, values) = inputs.split_last().unwrap();
+            let (a, b) = values.split_at(values.len() / 2);
+            PermutationCheckChip::construct(config).assert_permutation(layouter, a, b, gamma)?;
+            Ok(vec![])
+        }
+    }
+
+    fn inputs(a: &[u64], b: &[u64]) -> Vec<Fp> {
+        a.iter()
+            .chain(b.iter())
+            .map(|v| Fp::from(*v))
+            .chain([Fp::from(0x1234_5678_9abc)])
+            .collect()
+    }
+
+    #[test]
+    fn permutation_accepts_reordering() {
+        assert_eq!(
+            run(5, Permutation, &inputs(&[1, 2, 3, 3], &[3, 1, 3, 2]), &[]),
+            Ok(())
+        );
+        assert_eq!(run(5, Permutation, &inputs(&[7], &[7]), &[]), Ok(()));
+        assert_eq!(run(5, Permutation, &inputs(&[], &[]), &[]), Ok(()));
+    }
+
+    #[test]
+    fn permutation_rejects_different_multiset() {
+        assert!(run(5, Permutation, &inputs(&[1, 2, 3], &[1, 2, 4]), &[]).is_err());
+        // 元素一样但是重数不一样
+        assert!(run(5, Permutation, &inputs(&[1, 1, 2], &[1, 2, 2]), &[]).is_err());
+    }
+
+    #[test]
+    fn permutation_rejects_length_mismatch() {
+        // 3 个value会被拆成 [1] 和 [2, 2]
+        assert!(synthesis_fails(5, Permutation, &inputs(&[1, 2], &[2]), &[]));
+    }
+}
@@ -0,0 +1,198 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// 多项式求值：p(x) = c_0 + c_1 * x + ... + c_n * x^n
+// 用 Horner 从最高次项开始：acc = acc_prev * x + c_i，最后一行的 acc 就是 p(x)
+// x 每一行都copy一次，这样gate里面只需要 Rotation::cur() 的 x
+//
+//  coeff   |  acc  | x | q_first | q_step
+//  c_n     |  c_n  | x |    1    |   0
+//  c_{n-1} |  ...  | x |    0    |   1
+//  c_0     | p(x)  | x |    0    |   1
+//
+#[derive(Debug, Clone)]
+pub struct PolyEvalConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_first: Selector,
+    pub q_step: Selector,
+}
+
+pub struct PolyEvalChip<F: FieldExt> {
+    config: PolyEvalConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PolyEvalChip<F> {
+    pub fn construct(config: PolyEvalConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> PolyEvalConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("poly eval first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let coeff = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![q_first * (acc - coeff)]
+        });
+
+        meta.create_gate("poly eval step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let coeff = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let x = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[1], Rotation::prev());
+
+            vec![q_step * (acc - (acc_prev * x + coeff))]
+        });
+
+        PolyEvalConfig {
+            advice,
+            q_first,
+            q_step,
+        }
+    }
+
+    // coeffs[i] 是 x^i 的系数
+    pub fn eval(
+        &self,
+        mut layouter: impl Layouter<F>,
+        coeffs: &[ACell<F>],
+        x: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        if coeffs.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "poly eval",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+                let mut acc = None;
+
+                for (row, coeff) in coeffs.iter().rev().enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    coeff
+                        .0
+                        .copy_advice(|| "coeff", &mut region, self.config.advice[0], row)?;
+                    x.0.copy_advice(|| "x", &mut region, self.config.advice[2], row)?;
+
+                    acc_val = acc_val
+                        .zip(x.0.value())
+                        .zip(coeff.0.value())
+                        .map(|((acc, x), c)| acc * x + c);
+                    acc = Some(
+                        region
+                            .assign_advice(
+                                || "acc",
+                                self.config.advice[1],
+                                row,
+                                || acc_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?,
+                    );
+                }
+
+                Ok(acc.unwrap())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 coeffs...，最后一个是 x
+    #[derive(Clone, Default)]
+    struct PolyEval;
+
+    impl TestGadget<Fp> for PolyEval {
+        type Config = PolyEvalConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PolyEvalConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            PolyEvalChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: PolyEvalConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (x, coeffs) = inputs.split_last().unwrap();
+            let y = PolyEvalChip::construct(config).eval(layouter, coeffs, x)?;
+            Ok(vec![y])
+        }
+    }
+
+    // 直接按 Σ c_i * x^i 算，不用 Horner
+    fn eval(coeffs: &[u64], x: u64) -> Fp {
+        let mut power = Fp::one();
+        let mut y = Fp::zero();
+        for c in coeffs {
+            y += Fp::from(*c) * power;
+            power *= Fp::from(x);
+        }
+        y
+    }
+
+    fn inputs(coeffs: &[u64], x: u64) -> Vec<Fp> {
+        coeffs.iter().chain([&x]).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn poly_eval_matches_native() {
+        for (coeffs, x) in [
+            (vec![7u64], 3u64),
+            (vec![1, 2, 3], 0),
+            (vec![1, 2, 3], 1),
+            (vec![5, 0, 0, 1], 10),
+            (vec![u64::MAX, u64::MAX, u64::MAX], u64::MAX),
+        ] {
+            assert_eq!(
+                run(5, PolyEval, &inputs(&coeffs, x), &[eval(&coeffs, x)]),
+                Ok(()),
+                "{:?} at {}",
+                coeffs,
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn poly_eval_rejects_wrong_value() {
+        // 系数顺序反了：3 + 2 * 2 + 1 * 4 = 11，正确的是 1 + 2 * 2 + 3 * 4 = 17
+        assert!(run(5, PolyEval, &inputs(&[1, 2, 3], 2), &[Fp::from(11)]).is_err());
+        assert!(synthesis_fails(5, PolyEval, &inputs(&[], 2), &[Fp::zero()]));
+    }
+}
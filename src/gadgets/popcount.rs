@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    prefix_sum::{PrefixSumChip, PrefixSumConfig},
+};
+use crate::ACell;
+
+// popcount：x 的二进制表示里面 1 的个数
+// 用 DecomposeChip 拆成bits（同时断言 x < 2^bits），再用 PrefixSumChip 把所有bit加起来
+#[derive(Debug, Clone)]
+pub struct PopcountConfig {
+    pub decompose: DecomposeConfig,
+    pub prefix_sum: PrefixSumConfig,
+}
+
+pub struct PopcountChip<F: FieldExt> {
+    config: PopcountConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PopcountChip<F> {
+    pub fn construct(config: PopcountConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> PopcountConfig {
+        PopcountConfig {
+            decompose: DecomposeChip::configure(meta, advice),
+            prefix_sum: PrefixSumChip::configure(meta, advice),
+        }
+    }
+
+    pub fn popcount(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let x_bits = decompose.decompose(layouter.namespace(|| "x bits"), x, bits)?;
+        let x_bits: Vec<_> = x_bits.into_iter().map(|bit| bit.0).collect();
+
+        let prefix_sum = PrefixSumChip::construct(self.config.prefix_sum.clone());
+        let sums = prefix_sum.prefix_sum(layouter.namespace(|| "sum of bits"), &x_bits)?;
+
+        Ok(sums.last().unwrap().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Popcount;
+
+    impl TestGadget<Fp> for Popcount {
+        type Config = PopcountConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PopcountConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            PopcountChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: PopcountConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let count = PopcountChip::construct(config).popcount(layouter, &inputs[0], 8)?;
+            Ok(vec![count])
+        }
+    }
+
+    #[test]
+    fn popcount_matches_native() {
+        for x in [0u8, 1, 0x80, 0x55, 0xf0, 0xff] {
+            assert_eq!(
+                run(
+                    6,
+                    Popcount,
+                    &[Fp::from(x as u64)],
+                    &[Fp::from(x.count_ones() as u64)]
+                ),
+                Ok(()),
+                "x = {:#04x}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn popcount_rejects_wrong_count() {
+        assert!(run(6, Popcount, &[Fp::from(0x55)], &[Fp::from(5)]).is_err());
+        // 0x100 放不进 8 个bit
+        assert!(run(6, Popcount, &[Fp::from(0x100)], &[Fp::zero()]).is_err());
+    }
+}
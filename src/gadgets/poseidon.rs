@@ -0,0 +1,238 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// 宽度为 3 的 Poseidon 风格的置换，用来做 2 -> 1 的hash
+// 每一轮都是 full round：state_i <- Σ_j MDS[i][j] * (state_j + rc_j)^5
+//
+// 注意：这里的 round constant 和 MDS 都不是标准的 Poseidon 参数（也没有 partial rounds），
+// 只是为了演示 hash 在电路里面怎么组合，不要拿去做真正需要安全性的东西
+//
+//  s0 | s1 | s2 | rc0 rc1 rc2 (fixed) | q_round
+//  a  | b  | 0  |   rc[0]             |    1
+//  .. | .. | .. |   rc[r]             |    1
+//  out|    |    |                     |    0      <- 第 ROUNDS 行
+//
+pub const ROUNDS: usize = 8;
+pub const WIDTH: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct PoseidonConfig {
+    pub advice: [Column<Advice>; WIDTH],
+    pub round_constants: [Column<Fixed>; WIDTH],
+    pub q_round: Selector,
+}
+
+pub struct PoseidonChip<F: FieldExt> {
+    config: PoseidonConfig,
+    _marker: PhantomData<F>,
+}
+
+// Cauchy 矩阵 MDS[i][j] = 1 / (x_i + y_j)，x = (0, 1, 2)，y = (3, 4, 5)，一定是可逆的
+fn mds<F: FieldExt>() -> [[F; WIDTH]; WIDTH] {
+    let mut m = [[F::zero(); WIDTH]; WIDTH];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            *entry = F::from((i + WIDTH + j) as u64).invert().unwrap();
+        }
+    }
+    m
+}
+
+// 随便选的、确定性的 round constants
+fn round_constant<F: FieldExt>(round: usize, i: usize) -> F {
+    F::from((round * WIDTH + i + 1) as u64).pow_vartime([7])
+}
+
+// 电路外面的参考实现：一轮
+fn apply_round<F: FieldExt>(state: [F; WIDTH], round: usize) -> [F; WIDTH] {
+    let mds = mds::<F>();
+    let sboxed: Vec<F> = (0..WIDTH)
+        .map(|i| (state[i] + round_constant::<F>(round, i)).pow_vartime([5]))
+        .collect();
+
+    let mut next = [F::zero(); WIDTH];
+    for (i, n) in next.iter_mut().enumerate() {
+        *n = (0..WIDTH).fold(F::zero(), |acc, j| acc + mds[i][j] * sboxed[j]);
+    }
+    next
+}
+
+// 整个置换，witness生成之外也可以在电路外面算期望的hash值
+pub fn permute<F: FieldExt>(state: [F; WIDTH]) -> [F; WIDTH] {
+    (0..ROUNDS).fold(state, apply_round)
+}
+
+pub fn hash<F: FieldExt>(a: F, b: F) -> F {
+    permute([a, b, F::zero()])[0]
+}
+
+impl<F: FieldExt> PoseidonChip<F> {
+    pub fn construct(config: PoseidonConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // round_constants[0] 同时也被用来放常数（capacity 那一格的 0）
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; WIDTH],
+        round_constants: [Column<Fixed>; WIDTH],
+    ) -> PoseidonConfig {
+        let q_round = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_constant(round_constants[0]);
+
+        meta.create_gate("poseidon round", |meta| {
+            let q_round = meta.query_selector(q_round);
+            let mds = mds::<F>();
+
+            let sboxed: Vec<Expression<F>> = (0..WIDTH)
+                .map(|i| {
+                    let s = meta.query_advice(advice[i], Rotation::cur());
+                    let rc = meta.query_fixed(round_constants[i], Rotation::cur());
+                    let x = s + rc;
+                    x.clone() * x.clone() * x.clone() * x.clone() * x
+                })
+                .collect();
+
+            (0..WIDTH)
+                .map(|i| {
+                    let next = meta.query_advice(advice[i], Rotation::next());
+                    let mixed = (0..WIDTH)
+                        .map(|j| sboxed[j].clone() * mds[i][j])
+                        .reduce(|acc, term| acc + term)
+                        .unwrap();
+                    q_round.clone() * (mixed - next)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        PoseidonConfig {
+            advice,
+            round_constants,
+            q_round,
+        }
+    }
+
+    pub fn hash(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "poseidon",
+            |mut region| {
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+                region.assign_advice_from_constant(
+                    || "capacity",
+                    self.config.advice[2],
+                    0,
+                    F::zero(),
+                )?;
+
+                let mut state =
+                    a.0.value()
+                        .zip(b.0.value())
+                        .map(|(a, b)| [*a, *b, F::zero()]);
+                let mut out = None;
+
+                for round in 0..ROUNDS {
+                    self.config.q_round.enable(&mut region, round)?;
+                    for i in 0..WIDTH {
+                        region.assign_fixed(
+                            || format!("rc {} {}", round, i),
+                            self.config.round_constants[i],
+                            round,
+                            || Ok(round_constant::<F>(round, i)),
+                        )?;
+                    }
+
+                    state = state.map(|s| apply_round(s, round));
+
+                    for i in 0..WIDTH {
+                        let cell = region.assign_advice(
+                            || format!("state {} {}", round + 1, i),
+                            self.config.advice[i],
+                            round + 1,
+                            || state.map(|s| s[i]).ok_or(Error::Synthesis),
+                        )?;
+                        if i == 0 {
+                            out = Some(cell);
+                        }
+                    }
+                }
+
+                Ok(ACell(out.unwrap()))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Poseidon;
+
+    impl TestGadget<Fp> for Poseidon {
+        type Config = PoseidonConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PoseidonConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let round_constants = [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ];
+            PoseidonChip::configure(meta, advice, round_constants)
+        }
+
+        fn synthesize(
+            &self,
+            config: PoseidonConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                PoseidonChip::construct(config).hash(layouter, &inputs[0], &inputs[1])?
+            ])
+        }
+    }
+
+    #[test]
+    fn poseidon_matches_native() {
+        for (a, b) in [
+            (Fp::zero(), Fp::zero()),
+            (Fp::from(1), Fp::from(2)),
+            (-Fp::one(), Fp::from(12345)),
+        ] {
+            assert_eq!(run(5, Poseidon, &[a, b], &[hash(a, b)]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn poseidon_rejects_wrong_digest() {
+        let (a, b) = (Fp::from(1), Fp::from(2));
+        // 交换输入顺序得到的是另一个hash
+        assert!(run(5, Poseidon, &[a, b], &[hash(b, a)]).is_err());
+        assert!(run(5, Poseidon, &[a, b], &[hash(a, b) + Fp::one()]).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::decompose::{DecomposeChip, DecomposeConfig};
+use crate::ACell;
+
+// 简化版的 proof-of-work 难度检查：bits 位的 hash 至少有 d 个前导 0
+// 用 DecomposeChip 拆成 bits 个bit（同时断言 hash < 2^bits），再把最高的 d 个bit都约束成 0
+// d = 0 的时候只剩range check；d == bits 的时候就是 hash == 0
+#[derive(Debug, Clone)]
+pub struct PowDifficultyConfig {
+    pub decompose: DecomposeConfig,
+}
+
+pub struct PowDifficultyChip<F: FieldExt> {
+    config: PowDifficultyConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PowDifficultyChip<F> {
+    pub fn construct(config: PowDifficultyConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 用来放高位要等于的常数 0
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        fixed: Column<Fixed>,
+    ) -> PowDifficultyConfig {
+        meta.enable_constant(fixed);
+
+        PowDifficultyConfig {
+            decompose: DecomposeChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_difficulty(
+        &self,
+        mut layouter: impl Layouter<F>,
+        hash: &ACell<F>,
+        d: usize,
+        bits: usize,
+    ) -> Result<(), Error> {
+        if d > bits {
+            return Err(Error::Synthesis);
+        }
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let hash_bits = decompose.decompose(layouter.namespace(|| "hash bits"), hash, bits)?;
+
+        layouter.assign_region(
+            || "leading zeros",
+            |mut region| {
+                for bit in &hash_bits[bits - d..] {
+                    region.constrain_constant(bit.0 .0.cell(), F::zero())?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    const BITS: usize = 16;
+
+    #[derive(Clone, Default)]
+    struct PowDifficulty {
+        d: usize,
+    }
+
+    impl TestGadget<Fp> for PowDifficulty {
+        type Config = PowDifficultyConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PowDifficultyConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            let fixed = meta.fixed_column();
+            PowDifficultyChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: PowDifficultyConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            PowDifficultyChip::construct(config)
+                .assert_difficulty(layouter, &inputs[0], self.d, BITS)?;
+            Ok(vec![])
+        }
+    }
+
+    fn meets(hash: u64, d: usize) -> bool {
+        hash.leading_zeros() as usize >= 64 - BITS + d
+    }
+
+    #[test]
+    fn difficulty_matches_native() {
+        for hash in [0u64, 1, 0x00ff, 0x0fff, 0x1000, 0x7fff, 0xffff] {
+            for d in [0, 1, 4, 8, 12, BITS] {
+                let result = run(6, PowDifficulty { d }, &[Fp::from(hash)], &[]);
+                assert_eq!(
+                    result.is_ok(),
+                    meets(hash, d),
+                    "hash {:#06x}, d = {}",
+                    hash,
+                    d
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn difficulty_edge_cases() {
+        // d = 0 的时候只要在 2^bits 以内就行，d == bits 的时候只有 0
+        assert_eq!(
+            run(6, PowDifficulty { d: 0 }, &[Fp::from(0xffff)], &[]),
+            Ok(())
+        );
+        assert_eq!(
+            run(6, PowDifficulty { d: BITS }, &[Fp::zero()], &[]),
+            Ok(())
+        );
+        assert!(run(6, PowDifficulty { d: BITS }, &[Fp::one()], &[]).is_err());
+    }
+
+    #[test]
+    fn difficulty_rejects_out_of_range_hash() {
+        // 2^16 的低 16 位全是 0，但是它放不进 16 个bit
+        assert!(run(6, PowDifficulty { d: 0 }, &[Fp::from(1 << BITS)], &[]).is_err());
+        assert!(synthesis_fails(
+            6,
+            PowDifficulty { d: BITS + 1 },
+            &[Fp::zero()],
+            &[]
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// 前缀和：out_i = v_0 + v_1 + ... + v_i
+//
+//  value | acc             | q_first | q_step
+//   v_0  | v_0             |    1    |   0
+//   v_1  | acc_prev + v_1  |    0    |   1
+//
+#[derive(Debug, Clone)]
+pub struct PrefixSumConfig {
+    pub advice: [Column<Advice>; 2],
+    pub q_first: Selector,
+    pub q_step: Selector,
+}
+
+pub struct PrefixSumChip<F: FieldExt> {
+    config: PrefixSumConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PrefixSumChip<F> {
+    pub fn construct(config: PrefixSumConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> PrefixSumConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("prefix sum first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let value = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![q_first * (acc - value)]
+        });
+
+        // 第一行没有 acc_prev，所以单独一个gate
+        meta.create_gate("prefix sum step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let value = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[1], Rotation::prev());
+
+            vec![q_step * (acc - acc_prev - value)]
+        });
+
+        PrefixSumConfig {
+            advice,
+            q_first,
+            q_step,
+        }
+    }
+
+    pub fn prefix_sum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+    ) -> Result<Vec<ACell<F>>, Error> {
+        if values.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "prefix sum",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+                let mut out = Vec::with_capacity(values.len());
+
+                for (row, value) in values.iter().enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+
+                    value
+                        .0
+                        .copy_advice(|| "value", &mut region, self.config.advice[0], row)?;
+
+                    acc_val = acc_val.zip(value.0.value()).map(|(acc, v)| acc + *v);
+                    let acc = region
+                        .assign_advice(
+                            || "acc",
+                            self.config.advice[1],
+                            row,
+                            || acc_val.ok_or(Error::Synthesis),
+                        )
+                        .map(ACell)?;
+                    out.push(acc);
+                }
+
+                Ok(out)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct PrefixSum;
+
+    impl TestGadget<Fp> for PrefixSum {
+        type Config = PrefixSumConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PrefixSumConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            PrefixSumChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: PrefixSumConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            PrefixSumChip::construct(config).prefix_sum(layouter, inputs)
+        }
+    }
+
+    #[test]
+    fn prefix_sum_matches_native() {
+        for values in [vec![7u64], vec![1, 2, 3, 4], vec![0, 5, 0, 5]] {
+            let expected: Vec<_> = values
+                .iter()
+                .scan(0, |acc, v| {
+                    *acc += v;
+                    Some(Fp::from(*acc))
+                })
+                .collect();
+            let inputs: Vec<_> = values.iter().map(|v| Fp::from(*v)).collect();
+            assert_eq!(run(4, PrefixSum, &inputs, &expected), Ok(()));
+        }
+    }
+
+    #[test]
+    fn prefix_sum_rejects_wrong_sum() {
+        let inputs = [Fp::from(1), Fp::from(2), Fp::from(3)];
+        let expected = [Fp::from(1), Fp::from(3), Fp::from(7)];
+        assert!(run(4, PrefixSum, &inputs, &expected).is_err());
+    }
+
+    #[test]
+    fn prefix_sum_rejects_empty_input() {
+        assert!(synthesis_fails(4, PrefixSum, &[], &[]));
+    }
+}
@@ -0,0 +1,279 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::decompose::{DecomposeChip, DecomposeConfig};
+use crate::ACell;
+
+// 优先编码器：idx = x 的最高位 1 的位置，x = 0 的时候返回哨兵值 bits
+// 先用 DecomposeChip 拆成bits（也就是断言 x < 2^bits），然后从最高位往下扫：
+//   z_i = 1 当且仅当第 i 位以及更高的位全是 0，z_i = z_{i+1} * (1 - b_i)，z_bits = 1
+//   e_i = z_{i+1} * b_i 只在最高位的 1 那里是 1（它上面全是 0，它自己是 1）
+//   idx = sum(i * e_i)
+// 最后再多放一行，bit 固定是 1，label 是 bits：x = 0 的时候 z_0 = 1，这一行就贡献了哨兵值
+//
+//  bit     |  z  |  acc  | label(fixed) | q_first | q_step
+//  b_{n-1} | ... |  ...  |     n - 1    |    1    |   0
+//  ...     | ... |  ...  |     ...      |    0    |   1
+//  b_0     | z_0 |  ...  |       0      |    0    |   1
+//  1       |  0  |  idx  |       n      |    0    |   1
+//
+#[derive(Debug, Clone)]
+pub struct PriorityEncoderConfig {
+    pub advice: [Column<Advice>; 3],
+    pub label: Column<Fixed>,
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct PriorityEncoderChip<F: FieldExt> {
+    config: PriorityEncoderConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PriorityEncoderChip<F> {
+    pub fn construct(config: PriorityEncoderConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 同时用来放每一行的 label 和最后一行的常数 1
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        label: Column<Fixed>,
+    ) -> PriorityEncoderConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_constant(label);
+
+        meta.create_gate("priority encoder first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let bit = meta.query_advice(advice[0], Rotation::cur());
+            let z = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let label = meta.query_fixed(label, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_first.clone() * (z - (one - bit.clone())),
+                q_first * (acc - label * bit),
+            ]
+        });
+
+        meta.create_gate("priority encoder step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let bit = meta.query_advice(advice[0], Rotation::cur());
+            let z = meta.query_advice(advice[1], Rotation::cur());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+            let z_prev = meta.query_advice(advice[1], Rotation::prev());
+            let acc_prev = meta.query_advice(advice[2], Rotation::prev());
+            let label = meta.query_fixed(label, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_step.clone() * (z - z_prev.clone() * (one - bit.clone())),
+                q_step * (acc - (acc_prev + label * z_prev * bit)),
+            ]
+        });
+
+        PriorityEncoderConfig {
+            advice,
+            label,
+            q_first,
+            q_step,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn encode(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let x_bits = decompose.decompose(layouter.namespace(|| "x bits"), x, bits)?;
+
+        layouter.assign_region(
+            || "priority encoder",
+            |mut region| {
+                let mut z_val = Some(F::one());
+                let mut acc_val = Some(F::zero());
+                let mut acc = None;
+
+                // 从最高位往下，最后是那一行哨兵
+                for row in 0..=bits {
+                    let label = if row == bits { bits } else { bits - 1 - row };
+
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+                    region.assign_fixed(
+                        || "label",
+                        self.config.label,
+                        row,
+                        || Ok(F::from(label as u64)),
+                    )?;
+
+                    let bit_val = if row == bits {
+                        region.assign_advice_from_constant(
+                            || "sentinel bit",
+                            self.config.advice[0],
+                            row,
+                            F::one(),
+                        )?;
+                        Some(F::one())
+                    } else {
+                        let bit = &x_bits[label].0;
+                        bit.0
+                            .copy_advice(|| "bit", &mut region, self.config.advice[0], row)?;
+                        bit.0.value().copied()
+                    };
+
+                    acc_val = acc_val
+                        .zip(z_val)
+                        .zip(bit_val)
+                        .map(|((acc, z), b)| acc + F::from(label as u64) * z * b);
+                    z_val = z_val.zip(bit_val).map(|(z, b)| z * (F::one() - b));
+
+                    region.assign_advice(
+                        || "z",
+                        self.config.advice[1],
+                        row,
+                        || z_val.ok_or(Error::Synthesis),
+                    )?;
+                    acc = Some(
+                        region
+                            .assign_advice(
+                                || "acc",
+                                self.config.advice[2],
+                                row,
+                                || acc_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?,
+                    );
+                }
+
+                Ok(acc.unwrap())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct PriorityEncoder {
+        bits: usize,
+    }
+
+    impl TestGadget<Fp> for PriorityEncoder {
+        type Config = PriorityEncoderConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PriorityEncoderConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let label = meta.fixed_column();
+            PriorityEncoderChip::configure(meta, advice, label)
+        }
+
+        fn synthesize(
+            &self,
+            config: PriorityEncoderConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let idx =
+                PriorityEncoderChip::construct(config).encode(layouter, &inputs[0], self.bits)?;
+            Ok(vec![idx])
+        }
+    }
+
+    // x = 0 的时候是哨兵值 bits
+    fn msb(x: u64, bits: usize) -> u64 {
+        if x == 0 {
+            bits as u64
+        } else {
+            x.ilog2() as u64
+        }
+    }
+
+    #[test]
+    fn priority_encoder_matches_native() {
+        let bits = 8;
+        for x in [0u64, 1, 2, 3, 64, 127, 128, 129, 255] {
+            assert_eq!(
+                run(
+                    6,
+                    PriorityEncoder { bits },
+                    &[Fp::from(x)],
+                    &[Fp::from(msb(x, bits))]
+                ),
+                Ok(()),
+                "x = {}",
+                x
+            );
+        }
+        // 只有一位的时候也一样
+        for (x, idx) in [(0u64, 1u64), (1, 0)] {
+            assert_eq!(
+                run(
+                    6,
+                    PriorityEncoder { bits: 1 },
+                    &[Fp::from(x)],
+                    &[Fp::from(idx)]
+                ),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn priority_encoder_rejects_wrong_index() {
+        let bits = 8;
+        // 最低位的 1，或者 x = 0 的时候返回 0
+        for (x, wrong) in [(0b1010_0000u64, 5u64), (0, 0), (1, 8), (255, 0)] {
+            assert!(
+                run(
+                    6,
+                    PriorityEncoder { bits },
+                    &[Fp::from(x)],
+                    &[Fp::from(wrong)]
+                )
+                .is_err(),
+                "x = {}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn priority_encoder_rejects_out_of_range_input() {
+        // 256 放不进 8 个bit
+        assert!(run(
+            6,
+            PriorityEncoder { bits: 8 },
+            &[Fp::from(256)],
+            &[Fp::from(8)]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    div::{DivChip, DivConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    sub::{SubChip, SubConfig},
+};
+use crate::ACell;
+
+// 量化：q = round(x / step) * step，0.5 的时候向上取整（round half up）
+// 先用 DivChip 得到 x = d * step + r，然后
+//   up = !(r < ceil(step / 2))      也就是 2r >= step
+//   q  = (d + up) * step
+#[derive(Debug, Clone)]
+pub struct QuantizeConfig {
+    pub div: DivConfig,
+    pub constant: ConstantConfig,
+    pub less_than: LessThanConfig,
+    pub sub: SubConfig,
+    pub add: AddConfig,
+    pub mul_const: MulConstConfig,
+}
+
+pub struct QuantizeChip<F: FieldExt> {
+    config: QuantizeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> QuantizeChip<F> {
+    pub fn construct(config: QuantizeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> QuantizeConfig {
+        QuantizeConfig {
+            div: DivChip::configure(meta, advice, fixed),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            sub: SubChip::configure(meta, advice),
+            add: AddChip::configure(meta, advice),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+        }
+    }
+
+    pub fn quantize(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        step: u64,
+    ) -> Result<ACell<F>, Error> {
+        let div = DivChip::construct(self.config.div.clone());
+        let (d, r) = div.div_rem(layouter.namespace(|| "x / step"), x, step)?;
+
+        // r < step，threshold <= step，所以都在 bits 位以内
+        let bits = 64 - step.leading_zeros() as usize;
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let threshold = constant.load_constant(
+            layouter.namespace(|| "ceil(step / 2)"),
+            F::from(step.div_ceil(2)),
+        )?;
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let down = less_than.less_than(
+            layouter.namespace(|| "r < ceil(step / 2)"),
+            &r,
+            &threshold,
+            bits,
+        )?;
+
+        let one = constant.load_constant(layouter.namespace(|| "one"), F::one())?;
+        let sub = SubChip::construct(self.config.sub.clone());
+        let up = sub.sub(layouter.namespace(|| "up"), &one, &down.0)?;
+
+        let add = AddChip::construct(self.config.add.clone());
+        let rounded = add.add(layouter.namespace(|| "d + up"), &d, &up)?;
+
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        mul_const.mul_const(layouter.namespace(|| "* step"), &rounded, F::from(step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Quantize {
+        step: u64,
+    }
+
+    impl TestGadget<Fp> for Quantize {
+        type Config = QuantizeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> QuantizeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            QuantizeChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: QuantizeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                QuantizeChip::construct(config).quantize(layouter, &inputs[0], self.step)?
+            ])
+        }
+    }
+
+    // round half up
+    fn quantize(x: u64, step: u64) -> u64 {
+        (x + step / 2) / step * step
+    }
+
+    #[test]
+    fn quantize_matches_native() {
+        for step in [1u64, 2, 4, 5, 10] {
+            for x in 0..=25 {
+                assert_eq!(
+                    run(
+                        8,
+                        Quantize { step },
+                        &[Fp::from(x)],
+                        &[Fp::from(quantize(x, step))]
+                    ),
+                    Ok(()),
+                    "x = {}, step = {}",
+                    x,
+                    step
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn quantize_rejects_wrong_rounding() {
+        // 15 / 10 刚好是一半，向上取整到 20
+        assert!(run(8, Quantize { step: 10 }, &[Fp::from(15)], &[Fp::from(10)]).is_err());
+        // 14 / 10 向下取整到 10
+        assert!(run(8, Quantize { step: 10 }, &[Fp::from(14)], &[Fp::from(20)]).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    less_than::{LessThanChip, LessThanConfig},
+    prefix_sum::{PrefixSumChip, PrefixSumConfig},
+};
+use crate::ACell;
+
+// rank：values 里面有多少个 < target
+// 每个value都和 target 比一次，再用 PrefixSumChip 把所有 lt flag 加起来
+// target 比所有的都小的时候 rank = 0，比所有的都大的时候 rank = values.len()
+// 所有value（包括 target）都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct RankConfig {
+    pub less_than: LessThanConfig,
+    pub prefix_sum: PrefixSumConfig,
+    pub bits: usize,
+}
+
+pub struct RankChip<F: FieldExt> {
+    config: RankConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> RankChip<F> {
+    pub fn construct(config: RankConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> RankConfig {
+        RankConfig {
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            prefix_sum: PrefixSumChip::configure(meta, [advice[0], advice[1]]),
+            bits,
+        }
+    }
+
+    pub fn rank(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+        target: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        if values.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let mut flags = Vec::with_capacity(values.len());
+        for (i, value) in values.iter().enumerate() {
+            let lt = less_than.less_than(
+                layouter.namespace(|| format!("values[{}] < target", i)),
+                value,
+                target,
+                self.config.bits,
+            )?;
+            flags.push(lt.0);
+        }
+
+        let prefix_sum = PrefixSumChip::construct(self.config.prefix_sum.clone());
+        let counts = prefix_sum.prefix_sum(layouter.namespace(|| "count"), &flags)?;
+
+        Ok(counts.last().unwrap().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 values...，最后一个是 target
+    #[derive(Clone, Default)]
+    struct Rank;
+
+    impl TestGadget<Fp> for Rank {
+        type Config = RankConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> RankConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            RankChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: RankConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (target, values) = inputs.split_last().unwrap();
+            let rank = RankChip::construct(config).rank(layouter, values, target)?;
+            Ok(vec![rank])
+        }
+    }
+
+    fn inputs(values: &[u64], target: u64) -> Vec<Fp> {
+        values
+            .iter()
+            .chain([&target])
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    #[test]
+    fn rank_matches_native() {
+        let values = [9u64, 3, 27, 3, 100, 0];
+        // 比所有的都小，等于其中几个，比所有的都大
+        for target in [0u64, 3, 4, 27, 100, 255] {
+            let rank = values.iter().filter(|v| **v < target).count() as u64;
+            assert_eq!(
+                run(7, Rank, &inputs(&values, target), &[Fp::from(rank)]),
+                Ok(()),
+                "target = {}",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn rank_rejects_wrong_rank() {
+        let values = [9u64, 3, 27, 3];
+        // 把等于 target 的也算进去了
+        assert!(run(7, Rank, &inputs(&values, 9), &[Fp::from(3)]).is_err());
+        assert!(run(7, Rank, &inputs(&values, 255), &[Fp::from(3)]).is_err());
+        assert!(synthesis_fails(7, Rank, &inputs(&[], 1), &[Fp::zero()]));
+    }
+}
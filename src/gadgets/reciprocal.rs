@@ -0,0 +1,219 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    abs::{AbsChip, AbsConfig},
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    mul::{MulChip, MulConfig},
+    pow2,
+    sub::{SubChip, SubConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// 定点数的倒数：x 和 out 都是带 scale 位小数的定点数，out ≈ 1 / x，也就是 out ≈ 2^(2 * scale) / x
+// witness out = floor(2^(2 * scale) / x)，然后证明误差有界：
+//   |out * x - 2^(2 * scale)| < x
+// out * x 用 MulChip，差用 SubChip，绝对值用 AbsChip，最后 LessThanChip 断言 < x
+// x 和 out 都range check到 2 * scale + 1 位，这样 out * x < 2^(4 * scale + 2) 不会在field里面绕回来，
+// 否则prover可以找一个很大的 out 让 out * x 在模 p 下恰好凑到 2^(2 * scale) 附近；这里限制 scale <= 62
+// x = 2^scale（也就是 1.0）的时候 out = 2^scale，误差是 0；x = 0 的时候没有合法的 out
+#[derive(Debug, Clone)]
+pub struct ReciprocalConfig {
+    pub advice: Column<Advice>,
+    pub constant: ConstantConfig,
+    pub decompose: DecomposeConfig,
+    pub mul: MulConfig,
+    pub sub: SubConfig,
+    pub abs: AbsConfig,
+    pub less_than: LessThanConfig,
+}
+
+pub struct ReciprocalChip<F: FieldExt> {
+    config: ReciprocalConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ReciprocalChip<F> {
+    pub fn construct(config: ReciprocalConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ReciprocalConfig {
+        meta.enable_equality(advice[0]);
+
+        ReciprocalConfig {
+            advice: advice[0],
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            mul: MulChip::configure(meta, advice),
+            sub: SubChip::configure(meta, advice),
+            abs: AbsChip::configure(meta, advice),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn reciprocal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        scale: usize,
+    ) -> Result<ACell<F>, Error> {
+        if scale > 62 {
+            return Err(Error::Synthesis);
+        }
+        let bits = 2 * scale + 1;
+
+        let out = layouter.assign_region(
+            || "witness out",
+            |mut region| {
+                let out_val =
+                    x.0.value()
+                        .and_then(|x| (1u128 << (2 * scale)).checked_div(to_u128(x)))
+                        .map(F::from_u128);
+
+                region
+                    .assign_advice(
+                        || "out",
+                        self.config.advice,
+                        0,
+                        || out_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check x"), x, bits)?;
+        decompose.decompose(layouter.namespace(|| "range check out"), &out, bits)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let one =
+            constant.load_constant(layouter.namespace(|| "2^(2 * scale)"), pow2(2 * scale))?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let product = mul.mul(layouter.namespace(|| "out * x"), &out, x)?;
+
+        let sub = SubChip::construct(self.config.sub.clone());
+        let diff = sub.sub(
+            layouter.namespace(|| "out * x - 2^(2 * scale)"),
+            &product,
+            &one,
+        )?;
+
+        let abs = AbsChip::construct(self.config.abs.clone());
+        let error = abs.abs(layouter.namespace(|| "|error|"), &diff, bits)?;
+
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let lt = less_than.less_than(layouter.namespace(|| "|error| < x"), &error, x, bits)?;
+
+        layouter.assign_region(
+            || "bounded error",
+            |mut region| region.constrain_constant(lt.0 .0.cell(), F::one()),
+        )?;
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    const SCALE: usize = 8;
+
+    // 输入是 [x]，输出 out ≈ 2^(2 * SCALE) / x
+    #[derive(Clone, Default)]
+    struct Reciprocal;
+
+    impl TestGadget<Fp> for Reciprocal {
+        type Config = ReciprocalConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ReciprocalConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ReciprocalChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ReciprocalConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                ReciprocalChip::construct(config).reciprocal(layouter, &inputs[0], SCALE)?
+            ])
+        }
+    }
+
+    // 定点数 1 / x：先把 x 转成小数，取倒数，再乘回 2^SCALE 截断
+    fn native_reciprocal(x: u64) -> u64 {
+        let one = (1u64 << SCALE) as f64;
+        (one / (x as f64 / one)).floor() as u64
+    }
+
+    fn check(x: u64, out: u64) -> bool {
+        run(8, Reciprocal, &[Fp::from(x)], &[Fp::from(out)]).is_ok()
+    }
+
+    #[test]
+    fn reciprocal_matches_native() {
+        let one = 1u64 << (2 * SCALE);
+        for x in [
+            1,
+            2,
+            3,
+            7,
+            100,
+            255,
+            256,
+            257,
+            1000,
+            40000,
+            (1 << (2 * SCALE + 1)) - 1,
+        ] {
+            let out = native_reciprocal(x);
+            assert_eq!(out, one / x);
+            // 误差的界
+            assert!((out * x).abs_diff(one) < x);
+            assert!(check(x, out), "1 / {}", x);
+        }
+    }
+
+    #[test]
+    fn reciprocal_of_one() {
+        // x = 1.0 的时候 out = 1.0，误差是 0
+        assert!(check(1 << SCALE, 1 << SCALE));
+    }
+
+    #[test]
+    fn reciprocal_rejects_wrong_output() {
+        assert!(!check(3, 65536 / 3 + 1));
+        assert!(!check(256, 255));
+    }
+
+    #[test]
+    fn reciprocal_rejects_bad_input() {
+        // x = 0 没有倒数；x >= 2^(2 * SCALE + 1) 的时候range check过不去
+        assert!(synthesis_fails(8, Reciprocal, &[Fp::zero()], &[Fp::zero()]));
+        assert!(!check(1 << (2 * SCALE + 1), 0));
+    }
+}
@@ -0,0 +1,267 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::decompose::{DecomposeChip, DecomposeConfig};
+use crate::ACell;
+
+// BaseBChip 反过来：digits 是已经有的cell，算出 x = sum(d_i * base^i)
+// 每个 digit 也同样要 < base（digit + slack + 1 = base，两个都range check），否则就不是一个合法的表示
+//
+//  digit     |  acc  | slack | base(fixed) | q_first | q_step
+//  d_{n-1}   | d_n-1 |  ...  |    base     |    1    |   0
+//  ...       |  ...  |  ...  |    base     |    0    |   1
+//  d_0       |   x   |  ...  |    base     |    0    |   1
+//
+// 注意：base^digits.len() 要 < 2^128，否则结果就可能在field里面绕回来
+#[derive(Debug, Clone)]
+pub struct RecomposeConfig {
+    pub advice: [Column<Advice>; 3],
+    pub base: Column<Fixed>,
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct RecomposeChip<F: FieldExt> {
+    config: RecomposeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> RecomposeChip<F> {
+    pub fn construct(config: RecomposeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        base: Column<Fixed>,
+    ) -> RecomposeConfig {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("recompose first", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let digit = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let slack = meta.query_advice(advice[2], Rotation::cur());
+            let base = meta.query_fixed(base, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_first.clone() * (digit.clone() + slack + one - base),
+                q_first * (acc - digit),
+            ]
+        });
+
+        meta.create_gate("recompose step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let digit = meta.query_advice(advice[0], Rotation::cur());
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let slack = meta.query_advice(advice[2], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[1], Rotation::prev());
+            let base = meta.query_fixed(base, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_step.clone() * (digit.clone() + slack + one - base.clone()),
+                q_step * (acc - (acc_prev * base + digit)),
+            ]
+        });
+
+        RecomposeConfig {
+            advice,
+            base,
+            q_first,
+            q_step,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    // digits 是 little-endian 的，也就是 digits[0] 是最低位
+    pub fn recompose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        digits: &[ACell<F>],
+        base: u64,
+    ) -> Result<ACell<F>, Error> {
+        if base < 2 || digits.is_empty() {
+            return Err(Error::Synthesis);
+        }
+        if (base as u128).checked_pow(digits.len() as u32).is_none() {
+            return Err(Error::Synthesis);
+        }
+
+        let (x, slacks) = layouter.assign_region(
+            || "recompose",
+            |mut region| {
+                let mut acc_val = Some(F::zero());
+                let mut acc = None;
+                let mut slacks = Vec::with_capacity(digits.len());
+
+                // 从最高位开始放
+                for (row, digit) in digits.iter().rev().enumerate() {
+                    if row == 0 {
+                        self.config.q_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                    }
+                    region.assign_fixed(|| "base", self.config.base, row, || Ok(F::from(base)))?;
+
+                    digit
+                        .0
+                        .copy_advice(|| "digit", &mut region, self.config.advice[0], row)?;
+
+                    acc_val = acc_val
+                        .zip(digit.0.value())
+                        .map(|(acc, d)| acc * F::from(base) + *d);
+                    acc = Some(
+                        region
+                            .assign_advice(
+                                || "acc",
+                                self.config.advice[1],
+                                row,
+                                || acc_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?,
+                    );
+
+                    let slack = region
+                        .assign_advice(
+                            || "base - 1 - digit",
+                            self.config.advice[2],
+                            row,
+                            || {
+                                digit
+                                    .0
+                                    .value()
+                                    .map(|d| F::from(base - 1) - *d)
+                                    .ok_or(Error::Synthesis)
+                            },
+                        )
+                        .map(ACell)?;
+                    slacks.push(slack);
+                }
+
+                Ok((acc.unwrap(), slacks))
+            },
+        )?;
+
+        // digit 和 base - 1 - digit 都落在 [0, 2^digit_bits) 里面，才能保证 digit <= base - 1
+        let digit_bits = 64 - (base - 1).leading_zeros() as usize;
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        for (i, (digit, slack)) in digits.iter().rev().zip(slacks.iter()).enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("range check digit {}", i)),
+                digit,
+                digit_bits,
+            )?;
+            decompose.decompose(
+                layouter.namespace(|| format!("range check slack {}", i)),
+                slack,
+                digit_bits,
+            )?;
+        }
+
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 little-endian 的 digits，输出 x
+    #[derive(Clone, Default)]
+    struct Recompose {
+        base: u64,
+    }
+
+    impl TestGadget<Fp> for Recompose {
+        type Config = RecomposeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> RecomposeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let base = meta.fixed_column();
+            RecomposeChip::configure(meta, advice, base)
+        }
+
+        fn synthesize(
+            &self,
+            config: RecomposeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let x = RecomposeChip::construct(config).recompose(layouter, inputs, self.base)?;
+            Ok(vec![x])
+        }
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn recompose_matches_native() {
+        for (base, digits) in [
+            (2u64, vec![1u64, 0, 1, 1]),
+            (10, vec![4, 3, 2, 1]),
+            (16, vec![15, 15, 0, 0]),
+            (7, vec![6]),
+        ] {
+            let x = digits.iter().rev().fold(0, |acc, d| acc * base + d);
+            assert_eq!(
+                run(8, Recompose { base }, &fp(&digits), &[Fp::from(x)]),
+                Ok(()),
+                "base {} digits {:?}",
+                base,
+                digits
+            );
+        }
+    }
+
+    #[test]
+    fn recompose_rejects_out_of_range_digit() {
+        // 12 在十进制里不是一个digit，虽然 12 + 10 * 1 = 22 也能凑出来
+        assert!(run(8, Recompose { base: 10 }, &fp(&[12, 1]), &[Fp::from(22)]).is_err());
+        assert!(run(8, Recompose { base: 10 }, &fp(&[3, 2]), &[Fp::from(32)]).is_err());
+    }
+
+    #[test]
+    fn recompose_rejects_bad_parameters() {
+        assert!(synthesis_fails(
+            8,
+            Recompose { base: 1 },
+            &fp(&[0]),
+            &[Fp::zero()]
+        ));
+        assert!(synthesis_fails(
+            8,
+            Recompose { base: 10 },
+            &[],
+            &[Fp::zero()]
+        ));
+        // 2^129 放不进 u128
+        assert!(synthesis_fails(
+            8,
+            Recompose { base: 2 },
+            &fp(&[0; 129]),
+            &[Fp::zero()]
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+};
+use crate::ACell;
+
+// 证明 run-length encoding 的 (value, count) 列表展开之后就是 expanded
+//
+// count 是witness，所以不能根据 count 来决定电路的形状（keygen的时候是没有值的）
+// 这里用前缀和 S_i = count_0 + ... + count_i 来定位：
+//   第 j 个输出属于第 i 对  <=>  S_{i-1} <= j < S_i
+// 令 g_i = (S_i <= j)，g_{-1} = 1，那么 g_{i-1} - g_i 就是这个条件（count 为 0 的对自然就是 0）
+// 对每一个 j 做一遍条件写入：acc = acc_prev + (g_prev - g) * value，最后的 acc 就是 expanded[j]
+//
+//  g_{-1} = 1 |       | 0    |
+//  g_0        | v_0   | acc  | q_step
+//  ...
+//  g_{m-1}    | v_m-1 | acc  | q_step    <- acc == expanded[j]
+//
+// 另外约束 S_{m-1} == expanded.len()，并且每个count都range check到 bits 位（不能是负数）
+// bits 直接由 expanded.len() 决定，所以不需要放在config里
+#[derive(Debug, Clone)]
+pub struct RleConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_step: Selector,
+    pub add: AddConfig,
+    pub constant: ConstantConfig,
+    pub decompose: DecomposeConfig,
+    pub less_than_or_equal: LessThanOrEqualConfig,
+}
+
+pub struct RleChip<F: FieldExt> {
+    config: RleConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> RleChip<F> {
+    pub fn construct(config: RleConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> RleConfig {
+        let q_step = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("rle select", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let g_prev = meta.query_advice(advice[0], Rotation::prev());
+            let g = meta.query_advice(advice[0], Rotation::cur());
+            let value = meta.query_advice(advice[1], Rotation::cur());
+            let acc_prev = meta.query_advice(advice[2], Rotation::prev());
+            let acc = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![q_step * (acc - acc_prev - (g_prev - g) * value)]
+        });
+
+        RleConfig {
+            advice,
+            q_step,
+            add: AddChip::configure(meta, advice),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn assert_rle(
+        &self,
+        mut layouter: impl Layouter<F>,
+        pairs: &[(ACell<F>, ACell<F>)],
+        expanded: &[ACell<F>],
+    ) -> Result<(), Error> {
+        let n = expanded.len();
+        if pairs.is_empty() {
+            return if n == 0 {
+                Ok(())
+            } else {
+                Err(Error::Synthesis)
+            };
+        }
+
+        // n < 2^bits
+        let bits = ((usize::BITS - n.leading_zeros()) as usize).max(1);
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let add = AddChip::construct(self.config.add.clone());
+
+        // 前缀和，每个count都先range check一下
+        let mut prefix: Vec<ACell<F>> = Vec::with_capacity(pairs.len());
+        for (i, (_, count)) in pairs.iter().enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("range check count {}", i)),
+                count,
+                bits,
+            )?;
+            let sum = match prefix.last() {
+                Some(prev) => add.add(layouter.namespace(|| format!("S_{}", i)), prev, count)?,
+                None => count.clone(),
+            };
+            prefix.push(sum);
+        }
+
+        // 所有count加起来必须正好是 expanded 的长度
+        let total = prefix.last().unwrap();
+        layouter.assign_region(
+            || "total count",
+            |mut region| region.constrain_constant(total.0.cell(), F::from(n as u64)),
+        )?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+
+        for (j, out) in expanded.iter().enumerate() {
+            let j_cell = constant.load_constant(
+                layouter.namespace(|| format!("j = {}", j)),
+                F::from(j as u64),
+            )?;
+
+            let mut flags = Vec::with_capacity(pairs.len());
+            for (i, sum) in prefix.iter().enumerate() {
+                let g = less_than_or_equal.less_than_or_equal(
+                    layouter.namespace(|| format!("S_{} <= {}", i, j)),
+                    sum,
+                    &j_cell,
+                    bits,
+                )?;
+                flags.push(g);
+            }
+
+            layouter.assign_region(
+                || format!("expand {}", j),
+                |mut region| {
+                    region.assign_advice_from_constant(
+                        || "g_-1",
+                        self.config.advice[0],
+                        0,
+                        F::one(),
+                    )?;
+                    region.assign_advice_from_constant(
+                        || "acc",
+                        self.config.advice[2],
+                        0,
+                        F::zero(),
+                    )?;
+
+                    let mut g_prev = Some(F::one());
+                    let mut acc_val = Some(F::zero());
+                    let mut acc = None;
+
+                    for (i, ((value, _), g)) in pairs.iter().zip(flags.iter()).enumerate() {
+                        let row = i + 1;
+                        self.config.q_step.enable(&mut region, row)?;
+
+                        let g = &g.0;
+                        g.0.copy_advice(|| "g", &mut region, self.config.advice[0], row)?;
+                        value
+                            .0
+                            .copy_advice(|| "value", &mut region, self.config.advice[1], row)?;
+
+                        let g_val = g.0.value().copied();
+                        acc_val = acc_val
+                            .zip(g_prev)
+                            .zip(g_val)
+                            .zip(value.0.value())
+                            .map(|(((acc, g_prev), g), v)| acc + (g_prev - g) * *v);
+                        g_prev = g_val;
+
+                        acc = Some(region.assign_advice(
+                            || "acc",
+                            self.config.advice[2],
+                            row,
+                            || acc_val.ok_or(Error::Synthesis),
+                        )?);
+                    }
+
+                    region.constrain_equal(acc.unwrap().cell(), out.0.cell())
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 value_0, count_0, value_1, count_1, ...，后面接着 expanded
+    #[derive(Clone, Default)]
+    struct Rle {
+        pairs: usize,
+    }
+
+    impl TestGadget<Fp> for Rle {
+        type Config = RleConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> RleConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            RleChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: RleConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (pairs, expanded) = inputs.split_at(2 * self.pairs);
+            let pairs: Vec<_> = pairs
+                .chunks(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect();
+            RleChip::construct(config).assert_rle(layouter, &pairs, expanded)?;
+            Ok(vec![])
+        }
+    }
+
+    fn expand(pairs: &[(u64, u64)]) -> Vec<u64> {
+        pairs
+            .iter()
+            .flat_map(|(value, count)| std::iter::repeat_n(*value, *count as usize))
+            .collect()
+    }
+
+    fn inputs(pairs: &[(Fp, Fp)], expanded: &[u64]) -> Vec<Fp> {
+        pairs
+            .iter()
+            .flat_map(|(value, count)| [*value, *count])
+            .chain(expanded.iter().map(|v| Fp::from(*v)))
+            .collect()
+    }
+
+    fn field_pairs(pairs: &[(u64, u64)]) -> Vec<(Fp, Fp)> {
+        pairs
+            .iter()
+            .map(|(value, count)| (Fp::from(*value), Fp::from(*count)))
+            .collect()
+    }
+
+    #[test]
+    fn rle_matches_native() {
+        for pairs in [
+            vec![(5u64, 2u64), (7, 0), (9, 3)],
+            vec![(1, 1), (2, 1), (3, 1)],
+            vec![(4, 0), (6, 5), (8, 0)],
+            // 所有count都是0，展开是空的
+            vec![(4, 0), (6, 0), (8, 0)],
+        ] {
+            let expanded = expand(&pairs);
+            assert_eq!(
+                run(
+                    8,
+                    Rle { pairs: pairs.len() },
+                    &inputs(&field_pairs(&pairs), &expanded),
+                    &[]
+                ),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn rle_rejects_wrong_expansion() {
+        let pairs = field_pairs(&[(5, 2), (7, 0), (9, 3)]);
+        for expanded in [
+            vec![5u64, 9, 9, 9, 9],
+            vec![5, 5, 7, 9, 9],
+            vec![5, 5, 9, 9, 5],
+            // 长度对不上
+            vec![5, 5, 9, 9],
+            vec![5, 5, 9, 9, 9, 9],
+        ] {
+            assert!(run(8, Rle { pairs: 3 }, &inputs(&pairs, &expanded), &[]).is_err());
+        }
+    }
+
+    #[test]
+    fn rle_rejects_negative_count() {
+        // -1 + 4 = 3，总数是对的，但是 count 不能是负数
+        let pairs = [(Fp::from(5), -Fp::one()), (Fp::from(7), Fp::from(4))];
+        assert!(run(8, Rle { pairs: 2 }, &inputs(&pairs, &[7, 7, 7]), &[]).is_err());
+    }
+}
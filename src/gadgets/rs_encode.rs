@@ -0,0 +1,132 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::poly_eval::{PolyEvalChip, PolyEvalConfig};
+use crate::ACell;
+
+// Reed-Solomon（evaluation code）的一个codeword symbol：message 当作多项式的系数，
+// symbol = p(eval_point)，message[i] 是 x^i 的系数
+// 在 0 处求值就是 message[0]，在 1 处求值就是所有系数的和，都不需要特殊处理
+#[derive(Debug, Clone)]
+pub struct RsEncodeConfig {
+    pub poly_eval: PolyEvalConfig,
+}
+
+pub struct RsEncodeChip<F: FieldExt> {
+    config: RsEncodeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> RsEncodeChip<F> {
+    pub fn construct(config: RsEncodeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> RsEncodeConfig {
+        RsEncodeConfig {
+            poly_eval: PolyEvalChip::configure(meta, advice),
+        }
+    }
+
+    pub fn encode_symbol(
+        &self,
+        layouter: impl Layouter<F>,
+        message: &[ACell<F>],
+        eval_point: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let poly_eval = PolyEvalChip::construct(self.config.poly_eval.clone());
+        poly_eval.eval(layouter, message, eval_point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 message...，最后一个是 eval_point
+    #[derive(Clone, Default)]
+    struct RsEncode;
+
+    impl TestGadget<Fp> for RsEncode {
+        type Config = RsEncodeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> RsEncodeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            RsEncodeChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: RsEncodeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (point, message) = inputs.split_last().unwrap();
+            let symbol = RsEncodeChip::construct(config).encode_symbol(layouter, message, point)?;
+            Ok(vec![symbol])
+        }
+    }
+
+    // 对每个 eval point 都编码一次，得到整个 codeword
+    fn encode(message: &[u64], points: &[u64]) -> Vec<Fp> {
+        points
+            .iter()
+            .map(|x| {
+                message
+                    .iter()
+                    .rev()
+                    .fold(Fp::zero(), |acc, c| acc * Fp::from(*x) + Fp::from(*c))
+            })
+            .collect()
+    }
+
+    fn inputs(message: &[u64], point: u64) -> Vec<Fp> {
+        message
+            .iter()
+            .chain([&point])
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    #[test]
+    fn rs_encode_matches_native() {
+        let message = [3u64, 1, 4, 1, 5];
+        let points = [0u64, 1, 2, 7, 100];
+        let codeword = encode(&message, &points);
+        // 0 处就是 message[0]，1 处就是系数的和
+        assert_eq!(codeword[0], Fp::from(3));
+        assert_eq!(codeword[1], Fp::from(14));
+        for (point, symbol) in points.iter().zip(codeword) {
+            assert_eq!(
+                run(5, RsEncode, &inputs(&message, *point), &[symbol]),
+                Ok(()),
+                "point {}",
+                point
+            );
+        }
+    }
+
+    #[test]
+    fn rs_encode_rejects_corrupted_symbol() {
+        let message = [3u64, 1, 4, 1, 5];
+        let symbol = encode(&message, &[2])[0];
+        assert!(run(5, RsEncode, &inputs(&message, 2), &[symbol + Fp::one()]).is_err());
+        // 别的 eval point 上的 symbol
+        let other = encode(&message, &[3])[0];
+        assert!(run(5, RsEncode, &inputs(&message, 2), &[other]).is_err());
+    }
+}
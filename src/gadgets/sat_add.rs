@@ -0,0 +1,156 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    mux::{MuxChip, MuxConfig},
+    pow2,
+};
+use crate::ACell;
+
+// 饱和加法：out = min(a + b, 2^bits - 1)
+//   sum = a + b
+//   overflow = (2^bits - 1 < sum)
+//   out = overflow ? 2^bits - 1 : sum
+// a, b 都需要 < 2^bits，所以 sum < 2^(bits + 1)，比较的时候用 bits + 1 位
+// sum 正好是 2^bits - 1 的时候不算溢出，结果一样
+#[derive(Debug, Clone)]
+pub struct SaturatingAddConfig {
+    pub add: AddConfig,
+    pub constant: ConstantConfig,
+    pub less_than: LessThanConfig,
+    pub mux: MuxConfig,
+}
+
+pub struct SaturatingAddChip<F: FieldExt> {
+    config: SaturatingAddConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SaturatingAddChip<F> {
+    pub fn construct(config: SaturatingAddConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> SaturatingAddConfig {
+        SaturatingAddConfig {
+            add: AddChip::configure(meta, advice),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            mux: MuxChip::configure(meta, advice),
+        }
+    }
+
+    pub fn sat_add(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        let add = AddChip::construct(self.config.add.clone());
+        let sum = add.add(layouter.namespace(|| "a + b"), a, b)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let max = constant.load_constant(
+            layouter.namespace(|| "2^bits - 1"),
+            pow2::<F>(bits) - F::one(),
+        )?;
+
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        let overflow =
+            less_than.less_than(layouter.namespace(|| "overflow"), &max, &sum, bits + 1)?;
+
+        let mux = MuxChip::construct(self.config.mux.clone());
+        mux.mux(layouter.namespace(|| "saturate"), &overflow, &max, &sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [a, b]
+    #[derive(Clone, Default)]
+    struct SatAdd;
+
+    const BITS: usize = 8;
+
+    impl TestGadget<Fp> for SatAdd {
+        type Config = SaturatingAddConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> SaturatingAddConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            SaturatingAddChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: SaturatingAddConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let out = SaturatingAddChip::construct(config)
+                .sat_add(layouter, &inputs[0], &inputs[1], BITS)?;
+            Ok(vec![out])
+        }
+    }
+
+    fn inputs(a: u8, b: u8) -> Vec<Fp> {
+        vec![Fp::from(a as u64), Fp::from(b as u64)]
+    }
+
+    #[test]
+    fn sat_add_matches_native() {
+        for (a, b) in [
+            (0u8, 0u8),
+            (1, 2),
+            // 正好是 2^bits - 1，不算溢出
+            (200, 55),
+            (128, 127),
+            // 溢出
+            (200, 56),
+            (255, 255),
+            (255, 0),
+        ] {
+            assert_eq!(
+                run(
+                    6,
+                    SatAdd,
+                    &inputs(a, b),
+                    &[Fp::from(a.saturating_add(b) as u64)]
+                ),
+                Ok(()),
+                "{} + {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn sat_add_rejects_wrong_output() {
+        // 没有饱和，直接回绕，或者不该饱和的时候饱和了
+        assert!(run(6, SatAdd, &inputs(200, 56), &[Fp::zero()]).is_err());
+        assert!(run(6, SatAdd, &inputs(200, 56), &[Fp::from(256)]).is_err());
+        assert!(run(6, SatAdd, &inputs(100, 54), &[Fp::from(255)]).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    lookup_range::{LookupRangeCheckChip, LookupRangeCheckConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// witness生成用的是 u128，所以 s 和 order 都需要 < 2^128，否则 reduce 直接返回 Error::Synthesis
+// 也就是说 ~255 位的曲线阶（比如 pasta 的 p / q）这里是处理不了的
+pub const SCALAR_BITS: usize = 128;
+
+// pasta 的 field 都 > 2^254，k * n + r < 2^254 就一定不会绕回来
+const FIELD_SAFE_BITS: usize = 254;
+
+// 把标量 s 约化到曲线的阶 n 以内：s = k * n + r，0 <= r < n
+// 和 ModChip 一样，r < n 是通过同时range check r 和 t = n - 1 - r 来保证的，
+// 只不过这里的range check走的是 LookupRangeCheckChip（n 可以比 2^64 大很多）
+// k range check到 254 - n_bits 位：k <= 2^(254 - n_bits) - 1，n <= 2^n_bits，r < n，
+// 所以 k * n + r < (k + 1) * n <= 2^254，不会在field里面绕回来（否则 s 可以有两种分解）
+// s < 2^128 的时候 k = s / n < 2^(129 - n_bits)，在这个范围以内
+// s < n 的时候 k = 0，r 就是 s
+//
+//  s | k | r | n(fixed) | selector
+//  t |   |   |          |
+//
+#[derive(Debug, Clone)]
+pub struct ScalarReduceConfig {
+    pub advice: [Column<Advice>; 3],
+    pub order: Column<Fixed>,
+    pub selector: Selector,
+    pub lookup_range: LookupRangeCheckConfig,
+}
+
+pub struct ScalarReduceChip<F: FieldExt> {
+    config: ScalarReduceConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ScalarReduceChip<F> {
+    pub fn construct(config: ScalarReduceConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        order: Column<Fixed>,
+    ) -> ScalarReduceConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("scalar reduce", |meta| {
+            let s = meta.query_selector(selector);
+            let scalar = meta.query_advice(advice[0], Rotation::cur());
+            let k = meta.query_advice(advice[1], Rotation::cur());
+            let r = meta.query_advice(advice[2], Rotation::cur());
+            let t = meta.query_advice(advice[0], Rotation::next());
+            let n = meta.query_fixed(order, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * (k * n.clone() + r.clone() - scalar),
+                s * (r + t + one - n),
+            ]
+        });
+
+        ScalarReduceConfig {
+            advice,
+            order,
+            selector,
+            lookup_range: LookupRangeCheckChip::configure(meta, [advice[0], advice[1]], order),
+        }
+    }
+
+    pub fn load(&self, layouter: impl Layouter<F>) -> Result<(), Error> {
+        LookupRangeCheckChip::construct(self.config.lookup_range.clone()).load(layouter)
+    }
+
+    pub fn reduce(
+        &self,
+        mut layouter: impl Layouter<F>,
+        s: &ACell<F>,
+        order: F,
+    ) -> Result<ACell<F>, Error> {
+        let n = to_u128(&order);
+        if n == 0 || F::from_u128(n) != order {
+            return Err(Error::Synthesis);
+        }
+
+        let (k, r, t) = layouter.assign_region(
+            || "scalar reduce",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                s.0.copy_advice(|| "s", &mut region, self.config.advice[0], 0)?;
+                region.assign_fixed(|| "n", self.config.order, 0, || Ok(order))?;
+
+                let s_val = s.0.value().map(to_u128);
+                if let (Some(s_u128), Some(s_f)) = (s_val, s.0.value()) {
+                    if F::from_u128(s_u128) != *s_f {
+                        return Err(Error::Synthesis);
+                    }
+                }
+                let k_val = s_val.map(|s| F::from_u128(s / n));
+                let r_val = s_val.map(|s| F::from_u128(s % n));
+                let t_val = r_val.map(|r| order - F::one() - r);
+
+                let k = region
+                    .assign_advice(
+                        || "k",
+                        self.config.advice[1],
+                        0,
+                        || k_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+                let r = region
+                    .assign_advice(
+                        || "r",
+                        self.config.advice[2],
+                        0,
+                        || r_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+                let t = region
+                    .assign_advice(
+                        || "n - 1 - r",
+                        self.config.advice[0],
+                        1,
+                        || t_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                Ok((k, r, t))
+            },
+        )?;
+
+        // r 和 n - 1 - r 都要落在 [0, 2^n_bits) 里面，这样才能保证 r <= n - 1
+        let n_bits = (128 - (n - 1).leading_zeros() as usize).max(1);
+        let lookup_range = LookupRangeCheckChip::construct(self.config.lookup_range.clone());
+        lookup_range.range_check(
+            layouter.namespace(|| "range check k"),
+            &k,
+            FIELD_SAFE_BITS - n_bits,
+        )?;
+        lookup_range.range_check(layouter.namespace(|| "range check r"), &r, n_bits)?;
+        lookup_range.range_check(layouter.namespace(|| "range check n - 1 - r"), &t, n_bits)?;
+
+        Ok(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Reduce {
+        order: u128,
+    }
+
+    // 输入是 [s]，输出是 s mod order
+    impl TestGadget<Fp> for Reduce {
+        type Config = ScalarReduceConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let advice = [(); 3].map(|_| meta.advice_column());
+            let order = meta.fixed_column();
+            ScalarReduceChip::configure(meta, advice, order)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let chip = ScalarReduceChip::construct(config);
+            chip.load(layouter.namespace(|| "table"))?;
+            let r = chip.reduce(
+                layouter.namespace(|| "reduce"),
+                &inputs[0],
+                Fp::from_u128(self.order),
+            )?;
+            Ok(vec![r])
+        }
+    }
+
+    const K: u32 = 10;
+
+    #[test]
+    fn reduce_matches_native() {
+        let cases: [(u128, u128); 7] = [
+            (5, 7),
+            (0, 7),
+            (6, 7),
+            (7, 7),
+            (1000, 7),
+            (u128::MAX, 1),
+            (u128::MAX, (1 << 127) + 12345),
+        ];
+        for (s, order) in cases {
+            assert_eq!(
+                run(
+                    K,
+                    Reduce { order },
+                    &[Fp::from_u128(s)],
+                    &[Fp::from_u128(s % order)]
+                ),
+                Ok(()),
+                "{} mod {}",
+                s,
+                order
+            );
+        }
+    }
+
+    #[test]
+    fn reduce_rejects_wrong_remainder() {
+        // r + n 也满足 s = k * n + r 的形状（k 少一），但是 r < n 的检查过不去
+        assert!(run(
+            K,
+            Reduce { order: 7 },
+            &[Fp::from(1000)],
+            &[Fp::from(1000 % 7 + 7)]
+        )
+        .is_err());
+        assert!(run(K, Reduce { order: 7 }, &[Fp::from(1000)], &[Fp::from(0)]).is_err());
+    }
+
+    #[test]
+    fn reduce_rejects_bad_order_and_wide_scalar() {
+        assert!(synthesis_fails(
+            K,
+            Reduce { order: 0 },
+            &[Fp::from(5)],
+            &[Fp::zero()]
+        ));
+
+        // s >= 2^128 超出了witness生成的范围
+        let wide = Fp::from_u128(u128::MAX) + Fp::one();
+        assert!(synthesis_fails(
+            K,
+            Reduce { order: 7 },
+            &[wide],
+            &[Fp::zero()]
+        ));
+    }
+}
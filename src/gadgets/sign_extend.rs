@@ -0,0 +1,175 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    pow2,
+};
+use crate::ACell;
+
+// 补码的符号扩展：把一个 from_bits 位的 x 扩展成 to_bits 位
+// 用 DecomposeChip 拆出 x 的bits（同时断言 x < 2^from_bits），最高位就是符号位 s
+// 高出来的 to_bits - from_bits 位全部填成 s，recompose 之后就是：
+//   out = x + s * (2^to_bits - 2^from_bits)
+// 正数 s = 0，out 就是 x；from_bits == to_bits 的时候系数是 0，out 也就是 x
+#[derive(Debug, Clone)]
+pub struct SignExtendConfig {
+    pub decompose: DecomposeConfig,
+    pub mul_const: MulConstConfig,
+    pub add: AddConfig,
+}
+
+pub struct SignExtendChip<F: FieldExt> {
+    config: SignExtendConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SignExtendChip<F> {
+    pub fn construct(config: SignExtendConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> SignExtendConfig {
+        SignExtendConfig {
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            add: AddChip::configure(meta, advice),
+        }
+    }
+
+    pub fn sign_extend(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        from_bits: usize,
+        to_bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        if from_bits == 0 || from_bits > to_bits {
+            return Err(Error::Synthesis);
+        }
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let bits = decompose.decompose(layouter.namespace(|| "x bits"), x, from_bits)?;
+        let sign = &bits[from_bits - 1];
+
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let high = mul_const.mul_const(
+            layouter.namespace(|| "high bits"),
+            &sign.0,
+            pow2::<F>(to_bits) - pow2::<F>(from_bits),
+        )?;
+
+        let add = AddChip::construct(self.config.add.clone());
+        add.add(layouter.namespace(|| "x + high bits"), x, &high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct SignExtend {
+        from_bits: usize,
+        to_bits: usize,
+    }
+
+    impl TestGadget<Fp> for SignExtend {
+        type Config = SignExtendConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> SignExtendConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            SignExtendChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: SignExtendConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let out = SignExtendChip::construct(config).sign_extend(
+                layouter,
+                &inputs[0],
+                self.from_bits,
+                self.to_bits,
+            )?;
+            Ok(vec![out])
+        }
+    }
+
+    #[test]
+    fn sign_extend_matches_native() {
+        let g = SignExtend {
+            from_bits: 8,
+            to_bits: 16,
+        };
+        for x in [0u8, 1, 0x7f, 0x80, 0xfe, 0xff] {
+            let expected = x as i8 as i16 as u16;
+            assert_eq!(
+                run(
+                    6,
+                    g.clone(),
+                    &[Fp::from(x as u64)],
+                    &[Fp::from(expected as u64)]
+                ),
+                Ok(()),
+                "x = {:#04x}",
+                x
+            );
+        }
+
+        let g = SignExtend {
+            from_bits: 4,
+            to_bits: 4,
+        };
+        for x in [0u64, 7, 8, 15] {
+            assert_eq!(run(6, g.clone(), &[Fp::from(x)], &[Fp::from(x)]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn sign_extend_rejects_wrong_output() {
+        let g = SignExtend {
+            from_bits: 8,
+            to_bits: 16,
+        };
+        // 负数被零扩展了，正数被填了 1
+        assert!(run(6, g.clone(), &[Fp::from(0x80)], &[Fp::from(0x80)]).is_err());
+        assert!(run(6, g.clone(), &[Fp::from(0x7f)], &[Fp::from(0xff7f)]).is_err());
+        // 0x180 不是一个 8-bit 的值
+        assert!(run(6, g, &[Fp::from(0x180)], &[Fp::from(0xff80)]).is_err());
+    }
+
+    #[test]
+    fn sign_extend_rejects_bad_widths() {
+        let g = SignExtend {
+            from_bits: 8,
+            to_bits: 4,
+        };
+        assert!(synthesis_fails(6, g, &[Fp::one()], &[Fp::one()]));
+        let g = SignExtend {
+            from_bits: 0,
+            to_bits: 4,
+        };
+        assert!(synthesis_fails(6, g, &[Fp::zero()], &[Fp::zero()]));
+    }
+}
@@ -0,0 +1,248 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// 把byte流重新切成 6-bit 的chunk（base64的做法）
+// 每 3 个byte（24 bits）对应 4 个chunk：
+//   b0 * 2^16 + b1 * 2^8 + b2 = c0 * 2^18 + c1 * 2^12 + c2 * 2^6 + c3
+// 再把 byte range check 到 8 bits、chunk range check 到 6 bits，切法就是唯一的
+//
+//  b0 | b1 | b2 | q_group | q_zero_b1 | q_zero_b2
+//  c0 | c1 | c2 |
+//  c3 |    |    |
+//
+// 长度不是 3 的倍数的时候，最后一组用 0 补齐（q_zero_* 约束补进来的byte必须是0），
+// 和base64一样：剩 1 个byte输出 2 个chunk，剩 2 个byte输出 3 个chunk
+#[derive(Debug, Clone)]
+pub struct SixBitChunkConfig {
+    pub advice: [Column<Advice>; 3],
+    pub q_group: Selector,
+    pub q_zero_b1: Selector,
+    pub q_zero_b2: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct SixBitChunkChip<F: FieldExt> {
+    config: SixBitChunkConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SixBitChunkChip<F> {
+    pub fn construct(config: SixBitChunkConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> SixBitChunkConfig {
+        let q_group = meta.selector();
+        let q_zero_b1 = meta.selector();
+        let q_zero_b2 = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("six bit chunk", |meta| {
+            let q_group = meta.query_selector(q_group);
+            let q_zero_b1 = meta.query_selector(q_zero_b1);
+            let q_zero_b2 = meta.query_selector(q_zero_b2);
+            let b0 = meta.query_advice(advice[0], Rotation::cur());
+            let b1 = meta.query_advice(advice[1], Rotation::cur());
+            let b2 = meta.query_advice(advice[2], Rotation::cur());
+            let c0 = meta.query_advice(advice[0], Rotation::next());
+            let c1 = meta.query_advice(advice[1], Rotation::next());
+            let c2 = meta.query_advice(advice[2], Rotation::next());
+            let c3 = meta.query_advice(advice[0], Rotation(2));
+            let constant = |n: u64| Expression::Constant(F::from(n));
+
+            let bytes = b0 * constant(1 << 16) + b1.clone() * constant(1 << 8) + b2.clone();
+            let chunks =
+                c0 * constant(1 << 18) + c1 * constant(1 << 12) + c2 * constant(1 << 6) + c3;
+
+            vec![q_group * (bytes - chunks), q_zero_b1 * b1, q_zero_b2 * b2]
+        });
+
+        SixBitChunkConfig {
+            advice,
+            q_group,
+            q_zero_b1,
+            q_zero_b2,
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn chunk(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[ACell<F>],
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let mut out = Vec::with_capacity((bytes.len() * 4).div_ceil(3));
+
+        for (i, group) in bytes.chunks(3).enumerate() {
+            let (group_bytes, chunks) = layouter.assign_region(
+                || "six bit group",
+                |mut region| {
+                    self.config.q_group.enable(&mut region, 0)?;
+
+                    let mut group_bytes = Vec::with_capacity(3);
+                    let mut value = Some(0u32);
+                    for col in 0..3 {
+                        let byte = match group.get(col) {
+                            Some(byte) => byte.0.copy_advice(
+                                || "byte",
+                                &mut region,
+                                self.config.advice[col],
+                                0,
+                            )?,
+                            None => {
+                                // 补齐用的byte
+                                if col == 1 {
+                                    self.config.q_zero_b1.enable(&mut region, 0)?;
+                                } else {
+                                    self.config.q_zero_b2.enable(&mut region, 0)?;
+                                }
+                                region.assign_advice(
+                                    || "padding",
+                                    self.config.advice[col],
+                                    0,
+                                    || Ok(F::zero()),
+                                )?
+                            }
+                        };
+                        value = value
+                            .zip(byte.value())
+                            .map(|(acc, b)| (acc << 8) | to_u128(b) as u32);
+                        group_bytes.push(ACell(byte));
+                    }
+
+                    let mut chunks = Vec::with_capacity(4);
+                    for j in 0..4 {
+                        let chunk_val = value.map(|v| F::from(((v >> (18 - 6 * j)) & 0x3f) as u64));
+                        let (col, row) = if j < 3 { (j, 1) } else { (0, 2) };
+                        let chunk = region
+                            .assign_advice(
+                                || format!("chunk {}", j),
+                                self.config.advice[col],
+                                row,
+                                || chunk_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?;
+                        chunks.push(chunk);
+                    }
+
+                    Ok((group_bytes, chunks))
+                },
+            )?;
+
+            for byte in group_bytes.iter().take(group.len()) {
+                decompose.decompose(
+                    layouter.namespace(|| format!("byte range group {}", i)),
+                    byte,
+                    8,
+                )?;
+            }
+            for chunk in chunks.iter() {
+                decompose.decompose(
+                    layouter.namespace(|| format!("chunk range group {}", i)),
+                    chunk,
+                    6,
+                )?;
+            }
+
+            // 剩 n 个byte的时候只有前 n + 1 个chunk是有意义的
+            out.extend(chunks.into_iter().take(group.len() + 1));
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct SixBit;
+
+    impl TestGadget<Fp> for SixBit {
+        type Config = SixBitChunkConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> SixBitChunkConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            SixBitChunkChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: SixBitChunkConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            SixBitChunkChip::construct(config).chunk(layouter, inputs)
+        }
+    }
+
+    // base64 的下标（不带 '=' padding）
+    fn native(bytes: &[u8]) -> Vec<Fp> {
+        bytes
+            .chunks(3)
+            .flat_map(|group| {
+                let v = group
+                    .iter()
+                    .chain([0u8, 0].iter())
+                    .take(3)
+                    .fold(0u32, |acc, b| (acc << 8) | *b as u32);
+                (0..=group.len()).map(move |j| Fp::from(((v >> (18 - 6 * j)) & 0x3f) as u64))
+            })
+            .collect()
+    }
+
+    fn fp(bytes: &[u8]) -> Vec<Fp> {
+        bytes.iter().map(|b| Fp::from(*b as u64)).collect()
+    }
+
+    #[test]
+    fn chunk_matches_native_base64() {
+        // "Man" -> TWFu，"Ma" -> TWE，"M" -> TQ
+        for bytes in [&b"Man"[..], b"Ma", b"M", b"\xff\xff\xff\x00", b""] {
+            assert_eq!(run(9, SixBit, &fp(bytes), &native(bytes)), Ok(()));
+        }
+        let base64 = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let indices: Vec<u8> = b"TWFu"
+            .iter()
+            .map(|c| base64.iter().position(|x| x == c).unwrap() as u8)
+            .collect();
+        assert_eq!(native(b"Man"), fp(&indices));
+    }
+
+    #[test]
+    fn chunk_rejects_wrong_chunks() {
+        let mut expected = native(b"Man");
+        expected.swap(0, 1);
+        assert!(run(9, SixBit, &fp(b"Man"), &expected).is_err());
+    }
+
+    #[test]
+    fn chunk_rejects_non_byte_input() {
+        assert!(run(9, SixBit, &[Fp::from(256), Fp::zero(), Fp::zero()], &[]).is_err());
+    }
+}
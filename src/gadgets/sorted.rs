@@ -0,0 +1,163 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use super::decompose::{DecomposeChip, DecomposeConfig};
+use crate::ACell;
+
+// 证明一个数组是非递减的（sorted）
+// 把所有value放在同一个column的连续行里面，相邻两行的差 d_i = v_{i+1} - v_i
+// 再把每个 d_i range check 到 bits 位，这样就保证了 d_i >= 0
+//
+//  value | diff          | selector
+//   v_0  | v_1 - v_0     |    1
+//   v_1  | v_2 - v_1     |    1
+//   v_n  |               |    0
+//
+#[derive(Debug, Clone)]
+pub struct SortedConfig {
+    pub advice: [Column<Advice>; 2],
+    pub selector: Selector,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct SortedChip<F: FieldExt> {
+    config: SortedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SortedChip<F> {
+    pub fn construct(config: SortedConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> SortedConfig {
+        let selector = meta.selector();
+
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        meta.create_gate("sorted", |meta| {
+            let s = meta.query_selector(selector);
+            let cur = meta.query_advice(advice[0], Rotation::cur());
+            let next = meta.query_advice(advice[0], Rotation::next());
+            let diff = meta.query_advice(advice[1], Rotation::cur());
+
+            vec![s * (next - cur - diff)]
+        });
+
+        SortedConfig {
+            advice,
+            selector,
+            decompose: DecomposeChip::configure(meta, advice),
+        }
+    }
+
+    // 所有value都需要 < 2^bits；空数组和只有一个元素的数组都是sorted的
+    pub fn assert_sorted(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+        bits: usize,
+    ) -> Result<(), Error> {
+        if values.len() < 2 {
+            return Ok(());
+        }
+
+        let diffs = layouter.assign_region(
+            || "sorted",
+            |mut region| {
+                let mut diffs = Vec::with_capacity(values.len() - 1);
+
+                for (row, value) in values.iter().enumerate() {
+                    value
+                        .0
+                        .copy_advice(|| "value", &mut region, self.config.advice[0], row)?;
+
+                    if let Some(next) = values.get(row + 1) {
+                        self.config.selector.enable(&mut region, row)?;
+
+                        let diff_val = value
+                            .0
+                            .value()
+                            .zip(next.0.value())
+                            .map(|(cur, next)| *next - *cur);
+                        let diff = region
+                            .assign_advice(
+                                || "diff",
+                                self.config.advice[1],
+                                row,
+                                || diff_val.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)?;
+                        diffs.push(diff);
+                    }
+                }
+
+                Ok(diffs)
+            },
+        )?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        for (i, diff) in diffs.iter().enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("range check diff {}", i)),
+                diff,
+                bits,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Sorted;
+
+    impl TestGadget<Fp> for Sorted {
+        type Config = SortedConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> SortedConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            SortedChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: SortedConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            SortedChip::construct(config).assert_sorted(layouter, inputs, 8)?;
+            Ok(vec![])
+        }
+    }
+
+    fn fp(v: &[u64]) -> Vec<Fp> {
+        v.iter().map(|x| Fp::from(*x)).collect()
+    }
+
+    #[test]
+    fn sorted_accepts_non_decreasing() {
+        for values in [&[][..], &[5], &[0, 0, 1, 255], &[3, 3, 3]] {
+            assert_eq!(run(7, Sorted, &fp(values), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn sorted_rejects_descent() {
+        assert!(run(7, Sorted, &fp(&[1, 3, 2]), &[]).is_err());
+        // 差是 p - 1，一个"很大"的正数，range check 要挡住
+        assert!(run(7, Sorted, &fp(&[1, 0]), &[]).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 给定 y，witness 一个 x 使得 x * x == y
+// x 和 -x 都是合法的平方根，witness的时候总是取"比较小"的那一个
+// 如果 canonical_bits 是 Some(bits)，还会把 x range check 到 bits 位，
+// 这样对于整数的完全平方数来说，x 就只能是那个小的整数根（-x = p - x 非常大）
+// y 不是二次剩余的时候没有平方根，witness生成会直接返回 Error::Synthesis
+#[derive(Debug, Clone)]
+pub struct SqrtConfig {
+    pub advice: Column<Advice>,
+    pub mul: MulConfig,
+    pub is_equal: IsEqualConfig,
+    pub decompose: DecomposeConfig,
+    pub canonical_bits: Option<usize>,
+}
+
+pub struct SqrtChip<F: FieldExt> {
+    config: SqrtConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SqrtChip<F> {
+    pub fn construct(config: SqrtConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        canonical_bits: Option<usize>,
+    ) -> SqrtConfig {
+        meta.enable_equality(advice[0]);
+
+        SqrtConfig {
+            advice: advice[0],
+            mul: MulChip::configure(meta, advice),
+            is_equal: IsEqualChip::configure(meta, advice),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            canonical_bits,
+        }
+    }
+
+    pub fn sqrt(&self, mut layouter: impl Layouter<F>, y: &ACell<F>) -> Result<ACell<F>, Error> {
+        let x = layouter.assign_region(
+            || "witness sqrt",
+            |mut region| {
+                let x_val =
+                    y.0.value()
+                        .map(|y| Option::<F>::from(y.sqrt()).map(|x| std::cmp::min(x, -x)));
+
+                region
+                    .assign_advice(
+                        || "x",
+                        self.config.advice,
+                        0,
+                        || x_val.flatten().ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let x2 = mul.mul(layouter.namespace(|| "x * x"), &x, &x)?;
+
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        is_equal.assert_equal(layouter.namespace(|| "x * x == y"), &x2, y)?;
+
+        if let Some(bits) = self.config.canonical_bits {
+            let decompose = DecomposeChip::construct(self.config.decompose.clone());
+            decompose.decompose(layouter.namespace(|| "canonical root"), &x, bits)?;
+        }
+
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Sqrt {
+        canonical_bits: Option<usize>,
+    }
+
+    impl TestGadget<Fp> for Sqrt {
+        type Config = SqrtConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> SqrtConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            // canonical_bits 是电路形状的一部分，测试里固定成 8
+            SqrtChip::configure(meta, advice, Some(8))
+        }
+
+        fn synthesize(
+            &self,
+            mut config: SqrtConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            config.canonical_bits = self.canonical_bits;
+            Ok(vec![SqrtChip::construct(config).sqrt(layouter, &inputs[0])?])
+        }
+    }
+
+    #[test]
+    fn sqrt_matches_native() {
+        for x in [0u64, 1, 2, 15, 16, 255] {
+            let g = Sqrt {
+                canonical_bits: Some(8),
+            };
+            assert_eq!(run(6, g, &[Fp::from(x * x)], &[Fp::from(x)]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn sqrt_rejects_wrong_root() {
+        // -x 也是平方根，但是witness取的是小的那个
+        assert!(run(6, Sqrt::default(), &[Fp::from(49)], &[-Fp::from(7)]).is_err());
+        assert!(run(6, Sqrt::default(), &[Fp::from(49)], &[Fp::from(8)]).is_err());
+    }
+
+    #[test]
+    fn sqrt_rejects_root_wider_than_canonical_bits() {
+        let g = Sqrt {
+            canonical_bits: Some(8),
+        };
+        assert!(run(6, g, &[Fp::from(256 * 256)], &[Fp::from(256)]).is_err());
+        assert_eq!(
+            run(6, Sqrt::default(), &[Fp::from(256 * 256)], &[Fp::from(256)]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn sqrt_rejects_non_residue() {
+        let y = (2u64..)
+            .map(Fp::from)
+            .find(|y| Option::<Fp>::from(y.sqrt()).is_none())
+            .unwrap();
+        assert!(synthesis_fails(6, Sqrt::default(), &[y], &[Fp::zero()]));
+    }
+}
@@ -0,0 +1,129 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*, poly::Rotation};
+
+use crate::ACell;
+
+// c = a - b
+//
+//  a | b | c | selector
+//
+#[derive(Debug, Clone)]
+pub struct SubConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+pub struct SubChip<F: FieldExt> {
+    config: SubConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SubChip<F> {
+    pub fn construct(config: SubConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> SubConfig {
+        let selector = meta.selector();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("sub", |meta| {
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            vec![s * (a - b - c)]
+        });
+
+        SubConfig { advice, selector }
+    }
+
+    pub fn sub(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "sub",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = a.0.value().zip(b.0.value()).map(|(a, b)| *a - *b);
+
+                region
+                    .assign_advice(
+                        || "c",
+                        self.config.advice[2],
+                        0,
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Sub;
+
+    impl TestGadget<Fp> for Sub {
+        type Config = SubConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> SubConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            SubChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: SubConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Ok(vec![
+                SubChip::construct(config).sub(layouter, &inputs[0], &inputs[1])?
+            ])
+        }
+    }
+
+    #[test]
+    fn sub_matches_native() {
+        assert_eq!(
+            run(4, Sub, &[Fp::from(7), Fp::from(3)], &[Fp::from(4)]),
+            Ok(())
+        );
+        // 小减大在field里面是 p - 4
+        assert_eq!(
+            run(4, Sub, &[Fp::from(3), Fp::from(7)], &[-Fp::from(4)]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn sub_rejects_wrong_difference() {
+        assert!(run(4, Sub, &[Fp::from(7), Fp::from(3)], &[Fp::from(10)]).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    base_b::{BaseBChip, BaseBConfig},
+    constant::{ConstantChip, ConstantConfig},
+    permutation_check::{PermutationCheckChip, PermutationCheckConfig},
+    sub::{SubChip, SubConfig},
+};
+use crate::ACell;
+
+// 数独的一行（或者一列、一个宫）：9 个cell刚好是 1..9 的一个排列
+// * 每个cell都在 [1, 9] 里面：cell - 1 能拆成 1 个 9 进制的digit
+// * 和常数集合 {1, ..., 9} 做 PermutationCheckChip，保证没有重复
+// 其实第二条已经蕴含了第一条，range check主要是为了让出错的时候更容易定位
+#[derive(Debug, Clone)]
+pub struct SudokuLineConfig {
+    pub constant: ConstantConfig,
+    pub sub: SubConfig,
+    pub base_b: BaseBConfig,
+    pub permutation_check: PermutationCheckConfig,
+}
+
+pub struct SudokuLineChip<F: FieldExt> {
+    config: SudokuLineConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SudokuLineChip<F> {
+    pub fn construct(config: SudokuLineConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> SudokuLineConfig {
+        SudokuLineConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            sub: SubChip::configure(meta, advice),
+            base_b: BaseBChip::configure(meta, advice, fixed),
+            permutation_check: PermutationCheckChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_valid_line(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cells: &[ACell<F>; 9],
+        gamma: &ACell<F>,
+    ) -> Result<(), Error> {
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let digits = (1..=9)
+            .map(|d| {
+                constant.load_constant(layouter.namespace(|| format!("digit {}", d)), F::from(d))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let sub = SubChip::construct(self.config.sub.clone());
+        let base_b = BaseBChip::construct(self.config.base_b.clone());
+        for (i, cell) in cells.iter().enumerate() {
+            let offset = sub.sub(
+                layouter.namespace(|| format!("cell {} - 1", i)),
+                cell,
+                &digits[0],
+            )?;
+            base_b.decompose(
+                layouter.namespace(|| format!("cell {} in [1, 9]", i)),
+                &offset,
+                9,
+                1,
+            )?;
+        }
+
+        let permutation_check =
+            PermutationCheckChip::construct(self.config.permutation_check.clone());
+        permutation_check.assert_permutation(
+            layouter.namespace(|| "permutation of 1..9"),
+            cells,
+            &digits,
+            gamma,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 9 个cell，最后一个是 gamma
+    #[derive(Clone, Default)]
+    struct SudokuLine;
+
+    impl TestGadget<Fp> for SudokuLine {
+        type Config = SudokuLineConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> SudokuLineConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            SudokuLineChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: SudokuLineConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let cells: [ACell<Fp>; 9] = inputs[..9].to_vec().try_into().unwrap();
+            SudokuLineChip::construct(config).assert_valid_line(layouter, &cells, &inputs[9])?;
+            Ok(vec![])
+        }
+    }
+
+    fn inputs(cells: [u64; 9]) -> Vec<Fp> {
+        cells
+            .iter()
+            .map(|v| Fp::from(*v))
+            .chain([Fp::from(0x1234_5678_9abc_def0)])
+            .collect()
+    }
+
+    #[test]
+    fn sudoku_line_accepts_permutations() {
+        for cells in [
+            [1u64, 2, 3, 4, 5, 6, 7, 8, 9],
+            [5, 3, 4, 6, 7, 8, 9, 1, 2],
+            [9, 8, 7, 6, 5, 4, 3, 2, 1],
+        ] {
+            assert_eq!(run(8, SudokuLine, &inputs(cells), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn sudoku_line_rejects_invalid_lines() {
+        for cells in [
+            // 重复的数字
+            [1u64, 1, 3, 4, 5, 6, 7, 8, 9],
+            [5, 3, 4, 6, 7, 8, 9, 1, 5],
+            // 超出 [1, 9]
+            [0, 2, 3, 4, 5, 6, 7, 8, 9],
+            [10, 2, 3, 4, 5, 6, 7, 8, 9],
+            // 和是对的（45），但不是排列
+            [5, 5, 5, 5, 5, 5, 5, 5, 5],
+        ] {
+            assert!(run(8, SudokuLine, &inputs(cells), &[]).is_err());
+        }
+    }
+}
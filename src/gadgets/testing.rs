@@ -0,0 +1,134 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::*,
+    dev::{MockProver, VerifyFailure},
+    plonk::*,
+};
+
+use crate::ACell;
+
+// 测试里用的通用电路：私有输入放进一列advice，交给gadget算，gadget的输出再expose到instance
+// 这样每个gadget的测试只需要写 configure + synthesize，期望的输出就是public input
+// 输出对不上（或者约束不满足）的时候 MockProver::verify 就会报错
+pub(crate) trait TestGadget<F: FieldExt>: Clone + Default {
+    type Config: Clone;
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config;
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        layouter: impl Layouter<F>,
+        inputs: &[ACell<F>],
+    ) -> Result<Vec<ACell<F>>, Error>;
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TestConfig<C> {
+    gadget: C,
+    input: Column<Advice>,
+    instance: Column<Instance>,
+}
+
+#[derive(Default)]
+pub(crate) struct TestCircuit<F: FieldExt, G: TestGadget<F>> {
+    gadget: G,
+    inputs: Vec<Option<F>>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, G: TestGadget<F>> Circuit<F> for TestCircuit<F, G> {
+    type Config = TestConfig<G::Config>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            gadget: self.gadget.clone(),
+            inputs: vec![None; self.inputs.len()],
+            _marker: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let input = meta.advice_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(input);
+        meta.enable_equality(instance);
+
+        TestConfig {
+            gadget: G::configure(meta),
+            input,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let inputs = layouter.assign_region(
+            || "inputs",
+            |mut region| {
+                self.inputs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        region
+                            .assign_advice(
+                                || "input",
+                                config.input,
+                                i,
+                                || v.ok_or(Error::Synthesis),
+                            )
+                            .map(ACell)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        let outputs =
+            self.gadget
+                .synthesize(config.gadget, layouter.namespace(|| "gadget"), &inputs)?;
+
+        for (row, out) in outputs.iter().enumerate() {
+            layouter.constrain_instance(out.0.cell(), config.instance, row)?;
+        }
+
+        Ok(())
+    }
+}
+
+// 跑一遍 MockProver，expected 就是gadget输出应该等于的值（一般是在电路外面算出来的）
+pub(crate) fn run<F: FieldExt, G: TestGadget<F>>(
+    k: u32,
+    gadget: G,
+    inputs: &[F],
+    expected: &[F],
+) -> Result<(), Vec<VerifyFailure>> {
+    let circuit = TestCircuit {
+        gadget,
+        inputs: inputs.iter().map(|v| Some(*v)).collect(),
+        _marker: PhantomData,
+    };
+    MockProver::run(k, &circuit, vec![expected.to_vec()])
+        .expect("synthesis should succeed")
+        .verify()
+}
+
+// synthesize 本身就报错的情况（比如witness根本算不出来，或者参数不合法）
+pub(crate) fn synthesis_fails<F: FieldExt, G: TestGadget<F>>(
+    k: u32,
+    gadget: G,
+    inputs: &[F],
+    expected: &[F],
+) -> bool {
+    let circuit = TestCircuit {
+        gadget,
+        inputs: inputs.iter().map(|v| Some(*v)).collect(),
+        _marker: PhantomData,
+    };
+    MockProver::run(k, &circuit, vec![expected.to_vec()]).is_err()
+}
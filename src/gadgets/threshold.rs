@@ -0,0 +1,165 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    prefix_sum::{PrefixSumChip, PrefixSumConfig},
+    Boolean,
+};
+
+// k-of-n 门限（多签）：flags 里面至少有 k 个是 1
+// 用 PrefixSumChip 把所有flag加起来，再断言 k <= sum
+// sum <= n，所以比较的位数直接由 max(n, k) 决定；k = 0 的时候什么都不用检查
+#[derive(Debug, Clone)]
+pub struct ThresholdConfig {
+    pub prefix_sum: PrefixSumConfig,
+    pub constant: ConstantConfig,
+    pub less_than_or_equal: LessThanOrEqualConfig,
+}
+
+pub struct ThresholdChip<F: FieldExt> {
+    config: ThresholdConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ThresholdChip<F> {
+    pub fn construct(config: ThresholdConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ThresholdConfig {
+        ThresholdConfig {
+            prefix_sum: PrefixSumChip::configure(meta, [advice[0], advice[1]]),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn assert_threshold(
+        &self,
+        mut layouter: impl Layouter<F>,
+        flags: &[Boolean<F>],
+        k: u64,
+    ) -> Result<(), Error> {
+        if k == 0 {
+            return Ok(());
+        }
+        if flags.is_empty() {
+            return Err(Error::Synthesis);
+        }
+
+        let flags: Vec<_> = flags.iter().map(|flag| flag.0.clone()).collect();
+        let prefix_sum = PrefixSumChip::construct(self.config.prefix_sum.clone());
+        let sums = prefix_sum.prefix_sum(layouter.namespace(|| "approvals"), &flags)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let k_cell = constant.load_constant(layouter.namespace(|| "k"), F::from(k))?;
+
+        let bits = (64 - k.max(flags.len() as u64).leading_zeros()) as usize;
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+        less_than_or_equal.assert_less_than_or_equal(
+            layouter.namespace(|| "k <= approvals"),
+            &k_cell,
+            sums.last().unwrap(),
+            bits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::{
+        gadgets::testing::{run, TestGadget},
+        ACell,
+    };
+
+    // 输入是 flags，没有输出：门限不满足的时候约束就过不去
+    #[derive(Clone, Default)]
+    struct Threshold {
+        k: u64,
+    }
+
+    impl TestGadget<Fp> for Threshold {
+        type Config = ThresholdConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ThresholdConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ThresholdChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ThresholdConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let flags: Vec<_> = inputs.iter().cloned().map(Boolean).collect();
+            ThresholdChip::construct(config).assert_threshold(layouter, &flags, self.k)?;
+            Ok(vec![])
+        }
+    }
+
+    fn flags(bits: &[u64]) -> Vec<Fp> {
+        bits.iter().map(|b| Fp::from(*b)).collect()
+    }
+
+    #[test]
+    fn threshold_matches_native() {
+        let all = [
+            [0u64, 0, 0, 0],
+            [1, 0, 0, 0],
+            [0, 1, 1, 0],
+            [1, 0, 1, 1],
+            [1, 1, 1, 1],
+        ];
+        for bits in all {
+            let set: u64 = bits.iter().sum();
+            for k in 0..=5 {
+                assert_eq!(
+                    run(6, Threshold { k }, &flags(&bits), &[]).is_ok(),
+                    set >= k,
+                    "{:?} k = {}",
+                    bits,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn threshold_exactly_k_passes() {
+        assert_eq!(
+            run(6, Threshold { k: 2 }, &flags(&[1, 0, 0, 1, 0]), &[]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn threshold_zero_always_passes() {
+        assert_eq!(run(6, Threshold { k: 0 }, &flags(&[]), &[]), Ok(()));
+        assert_eq!(run(6, Threshold { k: 0 }, &flags(&[0, 0, 0]), &[]), Ok(()));
+    }
+
+    #[test]
+    fn threshold_rejects_one_short() {
+        assert!(run(6, Threshold { k: 3 }, &flags(&[1, 1, 0, 0]), &[]).is_err());
+    }
+}
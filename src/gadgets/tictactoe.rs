@@ -0,0 +1,231 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    base_b::{BaseBChip, BaseBConfig},
+    constant::{ConstantChip, ConstantConfig},
+    index_select::{IndexSelectChip, IndexSelectConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    mul::{MulChip, MulConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 8 条能连成一线的位置（3 行、3 列、2 条对角线），棋盘按行展开成 0..9
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+// 证明井字棋棋盘上 player 1 至少有一条线
+// * 每个cell都在 {0, 1, 2} 里面：拆成 1 个 3 进制的digit
+// * is_one_i = (cell_i == 1)，每条线 win = is_one_a * is_one_b * is_one_c
+// * witness 一个 one-hot 向量选中其中一条线（多条线都赢的时候选第一条），
+//   IndexSelectChip 会检查它是 one-hot 的，被选中的那条线的 win 必须是 1
+#[derive(Debug, Clone)]
+pub struct TicTacToeConfig {
+    pub advice: Column<Advice>,
+    pub base_b: BaseBConfig,
+    pub constant: ConstantConfig,
+    pub is_equal: IsEqualConfig,
+    pub mul: MulConfig,
+    pub index_select: IndexSelectConfig,
+}
+
+pub struct TicTacToeChip<F: FieldExt> {
+    config: TicTacToeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> TicTacToeChip<F> {
+    pub fn construct(config: TicTacToeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> TicTacToeConfig {
+        meta.enable_equality(advice[0]);
+
+        TicTacToeConfig {
+            advice: advice[0],
+            base_b: BaseBChip::configure(meta, advice, fixed),
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            is_equal: IsEqualChip::configure(meta, advice),
+            mul: MulChip::configure(meta, advice),
+            index_select: IndexSelectChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_win(
+        &self,
+        mut layouter: impl Layouter<F>,
+        board: &[ACell<F>; 9],
+    ) -> Result<(), Error> {
+        let base_b = BaseBChip::construct(self.config.base_b.clone());
+        for (i, cell) in board.iter().enumerate() {
+            base_b.decompose(
+                layouter.namespace(|| format!("cell {} in {{0, 1, 2}}", i)),
+                cell,
+                3,
+                1,
+            )?;
+        }
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let one = constant.load_constant(layouter.namespace(|| "one"), F::one())?;
+
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        let is_one = board
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                is_equal.is_equal(
+                    layouter.namespace(|| format!("cell {} == 1", i)),
+                    cell,
+                    &one,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let mut wins = Vec::with_capacity(LINES.len());
+        for (i, [a, b, c]) in LINES.iter().enumerate() {
+            let ab = mul.mul(
+                layouter.namespace(|| format!("line {}", i)),
+                &is_one[*a].0,
+                &is_one[*b].0,
+            )?;
+            wins.push(mul.mul(
+                layouter.namespace(|| format!("line {}", i)),
+                &ab,
+                &is_one[*c].0,
+            )?);
+        }
+
+        // 选中第一条赢了的线
+        let chosen = layouter.assign_region(
+            || "choose winning line",
+            |mut region| {
+                let first_win = wins
+                    .iter()
+                    .map(|win| win.0.value().map(|v| *v == F::one()))
+                    .collect::<Option<Vec<_>>>()
+                    .map(|wins| wins.iter().position(|win| *win));
+
+                (0..LINES.len())
+                    .map(|i| {
+                        region
+                            .assign_advice(
+                                || format!("choose line {}", i),
+                                self.config.advice,
+                                i,
+                                || {
+                                    first_win
+                                        .map(|first| F::from(first == Some(i)))
+                                        .ok_or(Error::Synthesis)
+                                },
+                            )
+                            .map(|cell| Boolean(ACell(cell)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        let index_select = IndexSelectChip::construct(self.config.index_select.clone());
+        let selected =
+            index_select.select(layouter.namespace(|| "selected line"), &chosen, &wins)?;
+
+        layouter.assign_region(
+            || "selected line is a win",
+            |mut region| region.constrain_constant(selected.0.cell(), F::one()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct TicTacToe;
+
+    impl TestGadget<Fp> for TicTacToe {
+        type Config = TicTacToeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TicTacToeConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            TicTacToeChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: TicTacToeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let board: [ACell<Fp>; 9] = inputs.to_vec().try_into().unwrap();
+            TicTacToeChip::construct(config).assert_win(layouter, &board)?;
+            Ok(vec![])
+        }
+    }
+
+    fn board(cells: [u64; 9]) -> Vec<Fp> {
+        cells.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    fn player_one_wins(cells: [u64; 9]) -> bool {
+        LINES.iter().any(|line| line.iter().all(|i| cells[*i] == 1))
+    }
+
+    #[test]
+    fn tictactoe_matches_native() {
+        for cells in [
+            // 一行 / 一列 / 对角线
+            [1u64, 1, 1, 2, 2, 0, 0, 0, 0],
+            [2, 1, 0, 2, 1, 0, 0, 1, 0],
+            [0, 2, 1, 2, 1, 0, 1, 0, 2],
+            // 同时赢两条线
+            [1, 1, 1, 1, 2, 2, 1, 2, 2],
+            // 没有赢
+            [0, 0, 0, 0, 0, 0, 0, 0, 0],
+            [1, 2, 1, 2, 1, 2, 2, 1, 2],
+            // player 2 赢了不算
+            [2, 2, 2, 1, 1, 0, 1, 0, 0],
+        ] {
+            assert_eq!(
+                run(9, TicTacToe, &board(cells), &[]).is_ok(),
+                player_one_wins(cells),
+                "board {:?}",
+                cells
+            );
+        }
+    }
+
+    #[test]
+    fn tictactoe_rejects_cells_outside_0_1_2() {
+        let mut cells = board([1, 1, 1, 0, 0, 0, 0, 0, 0]);
+        cells[8] = Fp::from(3);
+        assert!(run(9, TicTacToe, &cells, &[]).is_err());
+    }
+}
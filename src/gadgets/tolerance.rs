@@ -0,0 +1,167 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    abs::{AbsChip, AbsConfig},
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    sub::{SubChip, SubConfig},
+};
+use crate::ACell;
+
+// 近似相等：|x - target| <= target * tol / 100
+// 为了不做除法，两边同时乘 100：100 * |x - target| <= tol * target
+// （|x - target| 是整数，所以和向下取整的 target * tol / 100 是等价的）
+//
+// x 和 target 都需要 < 2^bits，tol 是 u64，所以两边都 < 2^(bits + 64)，比较的时候用这个位数
+#[derive(Debug, Clone)]
+pub struct ToleranceConfig {
+    pub sub: SubConfig,
+    pub abs: AbsConfig,
+    pub mul_const: MulConstConfig,
+    pub less_than_or_equal: LessThanOrEqualConfig,
+}
+
+pub struct ToleranceChip<F: FieldExt> {
+    config: ToleranceConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ToleranceChip<F> {
+    pub fn construct(config: ToleranceConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ToleranceConfig {
+        ToleranceConfig {
+            sub: SubChip::configure(meta, advice),
+            abs: AbsChip::configure(meta, advice),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn assert_within_tolerance(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        target: &ACell<F>,
+        tol_percent: u64,
+        bits: usize,
+    ) -> Result<(), Error> {
+        let sub = SubChip::construct(self.config.sub.clone());
+        let diff = sub.sub(layouter.namespace(|| "x - target"), x, target)?;
+
+        let abs = AbsChip::construct(self.config.abs.clone());
+        let distance = abs.abs(layouter.namespace(|| "|x - target|"), &diff, bits)?;
+
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let lhs = mul_const.mul_const(
+            layouter.namespace(|| "100 * |x - target|"),
+            &distance,
+            F::from(100),
+        )?;
+        let rhs = mul_const.mul_const(
+            layouter.namespace(|| "tol * target"),
+            target,
+            F::from(tol_percent),
+        )?;
+
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+        less_than_or_equal.assert_less_than_or_equal(
+            layouter.namespace(|| "within tolerance"),
+            &lhs,
+            &rhs,
+            bits + 64,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Tolerance {
+        tol_percent: u64,
+    }
+
+    impl TestGadget<Fp> for Tolerance {
+        type Config = ToleranceConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ToleranceConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ToleranceChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ToleranceConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            ToleranceChip::construct(config).assert_within_tolerance(
+                layouter,
+                &inputs[0],
+                &inputs[1],
+                self.tol_percent,
+                8,
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    fn within_tolerance(x: u64, target: u64, tol_percent: u64) -> bool {
+        100 * x.abs_diff(target) <= tol_percent * target
+    }
+
+    #[test]
+    fn tolerance_matches_native() {
+        for (x, target, tol_percent) in [
+            // 完全相等
+            (100u64, 100u64, 0u64),
+            (0, 0, 5),
+            // 刚好在边界上：|x - target| = 10 = 200 * 5 / 100
+            (210, 200, 5),
+            (190, 200, 5),
+            // 刚好出界
+            (211, 200, 5),
+            (189, 200, 5),
+            (101, 100, 0),
+            (255, 1, 100),
+            (2, 1, 100),
+        ] {
+            let result = run(
+                8,
+                Tolerance { tol_percent },
+                &[Fp::from(x), Fp::from(target)],
+                &[],
+            );
+            assert_eq!(
+                result.is_ok(),
+                within_tolerance(x, target, tol_percent),
+                "x = {}, target = {}, tol = {}%",
+                x,
+                target,
+                tol_percent
+            );
+        }
+    }
+}
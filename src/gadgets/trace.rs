@@ -0,0 +1,153 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    diagonal::{DiagonalChip, DiagonalConfig},
+    prefix_sum::{PrefixSumChip, PrefixSumConfig},
+};
+use crate::ACell;
+
+// 方阵的迹：tr(M) = M[0][0] + M[1][1] + ... + M[n-1][n-1]
+// 先用 DiagonalChip 把对角线取到新的cell里面（顺便检查 M 是方阵），再用 PrefixSumChip 累加，最后一个前缀和就是迹
+// 1x1 的时候迹就是 M[0][0]；空矩阵返回 Error::Synthesis
+#[derive(Debug, Clone)]
+pub struct TraceConfig {
+    pub diagonal: DiagonalConfig,
+    pub prefix_sum: PrefixSumConfig,
+}
+
+pub struct TraceChip<F: FieldExt> {
+    config: TraceConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> TraceChip<F> {
+    pub fn construct(config: TraceConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 2]) -> TraceConfig {
+        TraceConfig {
+            diagonal: DiagonalChip::configure(meta, advice),
+            prefix_sum: PrefixSumChip::configure(meta, advice),
+        }
+    }
+
+    pub fn trace(
+        &self,
+        mut layouter: impl Layouter<F>,
+        m: &[Vec<ACell<F>>],
+    ) -> Result<ACell<F>, Error> {
+        let size = m.len();
+        if size == 0 || m.iter().any(|row| row.len() != size) {
+            return Err(Error::Synthesis);
+        }
+
+        let diagonal = DiagonalChip::construct(self.config.diagonal.clone());
+        let d = diagonal.diagonal(layouter.namespace(|| "diagonal"), m)?;
+
+        let prefix_sum = PrefixSumChip::construct(self.config.prefix_sum.clone());
+        let sums = prefix_sum.prefix_sum(layouter.namespace(|| "sum"), &d)?;
+
+        sums.last().cloned().ok_or(Error::Synthesis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 M 按行展开（rows * cols 个），输出 tr(M)
+    #[derive(Clone, Default)]
+    struct Trace {
+        rows: usize,
+        cols: usize,
+    }
+
+    impl TestGadget<Fp> for Trace {
+        type Config = TraceConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TraceConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            TraceChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: TraceConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let m: Vec<_> = inputs.chunks(self.cols).map(|row| row.to_vec()).collect();
+            assert_eq!(m.len(), self.rows);
+            Ok(vec![TraceChip::construct(config).trace(layouter, &m)?])
+        }
+    }
+
+    fn flatten(m: &[&[u64]]) -> Vec<Fp> {
+        m.iter()
+            .flat_map(|row| row.iter())
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    fn native_trace(m: &[&[u64]]) -> u64 {
+        m.iter().enumerate().map(|(i, row)| row[i]).sum()
+    }
+
+    #[test]
+    fn trace_matches_native() {
+        let matrices: [&[&[u64]]; 5] = [
+            &[&[7]],
+            &[&[0, 0], &[0, 0]],
+            &[&[1, 2], &[3, 4]],
+            &[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]],
+            &[&[9, 0, 0, 1], &[0, 8, 0, 2], &[0, 0, 7, 3], &[4, 5, 6, 100]],
+        ];
+        for m in matrices {
+            let size = m.len();
+            assert_eq!(
+                run(
+                    5,
+                    Trace {
+                        rows: size,
+                        cols: size
+                    },
+                    &flatten(m),
+                    &[Fp::from(native_trace(m))]
+                ),
+                Ok(()),
+                "{:?}",
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn trace_rejects_wrong_sum() {
+        let m: &[&[u64]] = &[&[1, 2, 3], &[4, 5, 6], &[10, 8, 9]];
+        let g = Trace { rows: 3, cols: 3 };
+        assert_eq!(run(5, g.clone(), &flatten(m), &[Fp::from(15)]), Ok(()));
+        // 把所有元素加起来，或者加的是反对角线
+        assert!(run(5, g.clone(), &flatten(m), &[Fp::from(48)]).is_err());
+        assert!(run(5, g, &flatten(m), &[Fp::from(3 + 5 + 10)]).is_err());
+    }
+
+    #[test]
+    fn trace_rejects_non_square() {
+        let m: &[&[u64]] = &[&[1, 2, 3], &[4, 5, 6]];
+        assert!(synthesis_fails(
+            5,
+            Trace { rows: 2, cols: 3 },
+            &flatten(m),
+            &[Fp::from(6)]
+        ));
+    }
+}
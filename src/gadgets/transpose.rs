@@ -0,0 +1,180 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use crate::ACell;
+
+// 证明 B 是 A 的转置：B[j][i] == A[i][j]
+// 不需要任何custom gate，把两个矩阵按行展开放进region，同一行里面 A[i][j] 和 B[j][i] 加一个copy constraint
+//
+//  A[i][j] | B[j][i]
+//
+#[derive(Debug, Clone)]
+pub struct TransposeConfig {
+    pub advice: [Column<Advice>; 2],
+}
+
+pub struct TransposeChip<F: FieldExt> {
+    config: TransposeConfig,
+    _marker: PhantomData<F>,
+}
+
+// 返回 (rows, cols)，不是矩形的话返回 None
+fn dims<F: FieldExt>(m: &[Vec<ACell<F>>]) -> Option<(usize, usize)> {
+    let cols = m.first().map_or(0, |row| row.len());
+    m.iter()
+        .all(|row| row.len() == cols)
+        .then_some((m.len(), cols))
+}
+
+impl<F: FieldExt> TransposeChip<F> {
+    pub fn construct(config: TransposeConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+    ) -> TransposeConfig {
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+
+        TransposeConfig { advice }
+    }
+
+    pub fn assert_transpose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[Vec<ACell<F>>],
+        b: &[Vec<ACell<F>>],
+    ) -> Result<(), Error> {
+        let (rows, cols) = dims(a).ok_or(Error::Synthesis)?;
+        let (b_rows, b_cols) = dims(b).ok_or(Error::Synthesis)?;
+        // 空矩阵（包括 n x 0）的转置也是空矩阵
+        let matches = if rows * cols == 0 {
+            b_rows * b_cols == 0
+        } else {
+            (b_rows, b_cols) == (cols, rows)
+        };
+        if !matches {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "transpose",
+            |mut region| {
+                for i in 0..rows {
+                    for j in 0..cols {
+                        let row = i * cols + j;
+                        let a_ij = a[i][j].0.copy_advice(
+                            || "a[i][j]",
+                            &mut region,
+                            self.config.advice[0],
+                            row,
+                        )?;
+                        let b_ji = b[j][i].0.copy_advice(
+                            || "b[j][i]",
+                            &mut region,
+                            self.config.advice[1],
+                            row,
+                        )?;
+                        region.constrain_equal(a_ij.cell(), b_ji.cell())?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 A（rows x cols）按行展开，后面接 B（b_rows x b_cols）按行展开
+    #[derive(Clone, Default)]
+    struct Transpose {
+        a: (usize, usize),
+        b: (usize, usize),
+    }
+
+    impl TestGadget<Fp> for Transpose {
+        type Config = TransposeConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TransposeConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            TransposeChip::configure(meta, advice)
+        }
+
+        fn synthesize(
+            &self,
+            config: TransposeConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (a, b) = inputs.split_at(self.a.0 * self.a.1);
+            let a: Vec<Vec<_>> = a.chunks(self.a.1.max(1)).map(|r| r.to_vec()).collect();
+            let b: Vec<Vec<_>> = b.chunks(self.b.1.max(1)).map(|r| r.to_vec()).collect();
+            TransposeChip::construct(config).assert_transpose(layouter, &a, &b)?;
+            Ok(vec![])
+        }
+    }
+
+    fn matrix(rows: usize, cols: usize) -> Vec<Vec<u64>> {
+        (0..rows)
+            .map(|i| (0..cols).map(|j| (10 * i + j) as u64).collect())
+            .collect()
+    }
+
+    fn native_transpose(m: &[Vec<u64>]) -> Vec<Vec<u64>> {
+        (0..m[0].len())
+            .map(|j| m.iter().map(|row| row[j]).collect())
+            .collect()
+    }
+
+    fn inputs(a: &[Vec<u64>], b: &[Vec<u64>]) -> Vec<Fp> {
+        a.iter().chain(b).flatten().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn transpose_accepts_native_transpose() {
+        for (rows, cols) in [(1, 1), (2, 3), (3, 2), (1, 4)] {
+            let a = matrix(rows, cols);
+            let b = native_transpose(&a);
+            let g = Transpose {
+                a: (rows, cols),
+                b: (cols, rows),
+            };
+            assert_eq!(run(6, g, &inputs(&a, &b), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn transpose_rejects_wrong_entry() {
+        let a = matrix(2, 3);
+        let mut b = native_transpose(&a);
+        b[2][0] += 1;
+        let g = Transpose {
+            a: (2, 3),
+            b: (3, 2),
+        };
+        assert!(run(6, g, &inputs(&a, &b), &[]).is_err());
+    }
+
+    #[test]
+    fn transpose_rejects_wrong_shape() {
+        // 2x3 的转置不可能是 2x3
+        let a = matrix(2, 3);
+        let g = Transpose {
+            a: (2, 3),
+            b: (2, 3),
+        };
+        assert!(synthesis_fails(6, g, &inputs(&a, &a), &[]));
+    }
+}
@@ -0,0 +1,192 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    div::{DivChip, DivConfig},
+    mul::{MulChip, MulConfig},
+    to_u128,
+};
+use crate::ACell;
+
+// k 最多 32 bits，这样 k * (k + 1) 不会在field里面溢出，
+// 否则 k^2 + k = 2x 在field里面可能有一个很大的"假"解
+pub const K_BITS: usize = 32;
+
+// 三角数：x == k * (k + 1) / 2
+// witness k，用 MulChip / AddChip 算 k * (k + 1)，再用 DivChip 除以 2：余数必须是 0，商必须等于 x
+// x = 0 (k = 0) 和 x = 1 (k = 1) 都没有什么特别的
+#[derive(Debug, Clone)]
+pub struct TriangularConfig {
+    pub advice: Column<Advice>,
+    pub constant: ConstantConfig,
+    pub add: AddConfig,
+    pub mul: MulConfig,
+    pub div: DivConfig,
+    pub decompose: DecomposeConfig,
+}
+
+pub struct TriangularChip<F: FieldExt> {
+    config: TriangularConfig,
+    _marker: PhantomData<F>,
+}
+
+// 整数平方根（向下取整）
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+impl<F: FieldExt> TriangularChip<F> {
+    pub fn construct(config: TriangularConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> TriangularConfig {
+        meta.enable_equality(advice[0]);
+
+        TriangularConfig {
+            advice: advice[0],
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            add: AddChip::configure(meta, advice),
+            mul: MulChip::configure(meta, advice),
+            div: DivChip::configure(meta, advice, fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+        }
+    }
+
+    pub fn assert_triangular(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+    ) -> Result<(), Error> {
+        // k = floor((sqrt(8x + 1) - 1) / 2)，x 不是三角数的时候约束自然过不了
+        let k = layouter.assign_region(
+            || "witness k",
+            |mut region| {
+                let k_val = x.0.value().map(|x| {
+                    F::from_u128((isqrt(to_u128(x).saturating_mul(8).saturating_add(1)) - 1) / 2)
+                });
+
+                region
+                    .assign_advice(
+                        || "k",
+                        self.config.advice,
+                        0,
+                        || k_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)
+            },
+        )?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        decompose.decompose(layouter.namespace(|| "range check k"), &k, K_BITS)?;
+
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let one = constant.load_constant(layouter.namespace(|| "one"), F::one())?;
+        let add = AddChip::construct(self.config.add.clone());
+        let k_plus_one = add.add(layouter.namespace(|| "k + 1"), &k, &one)?;
+        let mul = MulChip::construct(self.config.mul.clone());
+        let product = mul.mul(layouter.namespace(|| "k * (k + 1)"), &k, &k_plus_one)?;
+
+        let div = DivChip::construct(self.config.div.clone());
+        let (q, r) = div.div_rem(layouter.namespace(|| "/ 2"), &product, 2)?;
+
+        layouter.assign_region(
+            || "x == k * (k + 1) / 2",
+            |mut region| {
+                region.constrain_constant(r.0.cell(), F::zero())?;
+                region.constrain_equal(q.0.cell(), x.0.cell())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Triangular;
+
+    impl TestGadget<Fp> for Triangular {
+        type Config = TriangularConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TriangularConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            TriangularChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: TriangularConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            TriangularChip::construct(config).assert_triangular(layouter, &inputs[0])?;
+            Ok(vec![])
+        }
+    }
+
+    fn is_triangular(x: u64) -> bool {
+        (0..)
+            .map(|k: u64| k * (k + 1) / 2)
+            .take_while(|t| *t <= x)
+            .any(|t| t == x)
+    }
+
+    #[test]
+    fn isqrt_matches_native() {
+        for n in [0u128, 1, 2, 3, 4, 15, 16, 17, 1 << 64, u64::MAX as u128] {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({})", n);
+        }
+    }
+
+    #[test]
+    fn triangular_matches_native() {
+        for x in [0u64, 1, 2, 3, 4, 6, 9, 10, 15, 20, 21, 5050, 5051] {
+            assert_eq!(
+                run(8, Triangular, &[Fp::from(x)], &[]).is_ok(),
+                is_triangular(x),
+                "x = {}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn triangular_accepts_largest_k() {
+        let k = (1u64 << K_BITS) - 1;
+        assert_eq!(
+            run(8, Triangular, &[Fp::from(k * (k + 1) / 2)], &[]),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    add::{AddChip, AddConfig},
+    dot_product::{DotProductChip, DotProductConfig},
+    index_select::{IndexSelectChip, IndexSelectConfig},
+    is_equal::{IsEqualChip, IsEqualConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// two-sum：存在两个不同的下标 i, j 使得 values[i] + values[j] == target
+// i, j 由两个 one-hot 向量给出，用 IndexSelectChip 取出对应的value（它也会检查 one-hot）
+// i != j：两个 one-hot 向量的点积就是 [i == j]，所以约束它等于 0
+#[derive(Debug, Clone)]
+pub struct TwoSumConfig {
+    pub index_select: IndexSelectConfig,
+    pub dot_product: DotProductConfig,
+    pub add: AddConfig,
+    pub is_equal: IsEqualConfig,
+}
+
+pub struct TwoSumChip<F: FieldExt> {
+    config: TwoSumConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> TwoSumChip<F> {
+    pub fn construct(config: TwoSumConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 用来放点积要等于的常数 0
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> TwoSumConfig {
+        meta.enable_constant(fixed);
+
+        TwoSumConfig {
+            index_select: IndexSelectChip::configure(meta, advice),
+            dot_product: DotProductChip::configure(meta, advice),
+            add: AddChip::configure(meta, advice),
+            is_equal: IsEqualChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_two_sum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+        onehot_i: &[Boolean<F>],
+        onehot_j: &[Boolean<F>],
+        target: &ACell<F>,
+    ) -> Result<(), Error> {
+        let index_select = IndexSelectChip::construct(self.config.index_select.clone());
+        let v_i = index_select.select(layouter.namespace(|| "values[i]"), onehot_i, values)?;
+        let v_j = index_select.select(layouter.namespace(|| "values[j]"), onehot_j, values)?;
+
+        let dot_product = DotProductChip::construct(self.config.dot_product.clone());
+        let flags_i: Vec<_> = onehot_i.iter().map(|flag| flag.0.clone()).collect();
+        let flags_j: Vec<_> = onehot_j.iter().map(|flag| flag.0.clone()).collect();
+        let same = dot_product.dot_product(layouter.namespace(|| "i == j"), &flags_i, &flags_j)?;
+        layouter.assign_region(
+            || "i != j",
+            |mut region| region.constrain_constant(same.0.cell(), F::zero()),
+        )?;
+
+        let add = AddChip::construct(self.config.add.clone());
+        let sum = add.add(layouter.namespace(|| "values[i] + values[j]"), &v_i, &v_j)?;
+
+        let is_equal = IsEqualChip::construct(self.config.is_equal.clone());
+        is_equal.assert_equal(layouter.namespace(|| "sum == target"), &sum, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const N: usize = 5;
+
+    // 输入是 values（N 个），onehot_i（N 个），onehot_j（N 个），最后一个是 target
+    #[derive(Clone, Default)]
+    struct TwoSum;
+
+    impl TestGadget<Fp> for TwoSum {
+        type Config = TwoSumConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TwoSumConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            TwoSumChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: TwoSumConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let flags = |range: std::ops::Range<usize>| -> Vec<_> {
+                inputs[range]
+                    .iter()
+                    .map(|cell| Boolean(cell.clone()))
+                    .collect()
+            };
+            TwoSumChip::construct(config).assert_two_sum(
+                layouter,
+                &inputs[..N],
+                &flags(N..2 * N),
+                &flags(2 * N..3 * N),
+                &inputs[3 * N],
+            )?;
+            Ok(vec![])
+        }
+    }
+
+    const VALUES: [u64; N] = [2, 7, 11, 15, 4];
+
+    fn onehot(i: usize) -> Vec<u64> {
+        (0..N).map(|k| (k == i) as u64).collect()
+    }
+
+    fn inputs(i: &[u64], j: &[u64], target: u64) -> Vec<Fp> {
+        VALUES
+            .iter()
+            .chain(i)
+            .chain(j)
+            .chain([&target])
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    #[test]
+    fn two_sum_accepts_valid_witnesses() {
+        for (i, j) in [(0, 1), (1, 0), (2, 4), (0, 3)] {
+            let target = VALUES[i] + VALUES[j];
+            assert_eq!(
+                run(7, TwoSum, &inputs(&onehot(i), &onehot(j), target), &[]),
+                Ok(()),
+                "({}, {})",
+                i,
+                j
+            );
+        }
+    }
+
+    #[test]
+    fn two_sum_rejects_wrong_sum() {
+        assert!(run(7, TwoSum, &inputs(&onehot(0), &onehot(1), 10), &[]).is_err());
+    }
+
+    #[test]
+    fn two_sum_rejects_same_index() {
+        // 2 + 2 = 4 = values[4]，但是 i == j 不行
+        assert!(run(7, TwoSum, &inputs(&onehot(0), &onehot(0), 4), &[]).is_err());
+    }
+
+    #[test]
+    fn two_sum_rejects_invalid_onehot() {
+        // 两个 flag 都是 1，选出来的是 2 + 7 = 9，加上 values[4] 就是 13
+        let both = [1u64, 1, 0, 0, 0];
+        assert!(run(7, TwoSum, &inputs(&both, &onehot(4), 13), &[]).is_err());
+        // 全是 0，选出来的是 0
+        assert!(run(7, TwoSum, &inputs(&[0; N], &onehot(2), 11), &[]).is_err());
+    }
+}
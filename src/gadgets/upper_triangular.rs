@@ -0,0 +1,151 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::is_zero::{IsZeroChip, IsZeroConfig};
+use crate::ACell;
+
+// 上三角矩阵：对角线下面的entry全是 0，也就是 i > j 的时候 M[i][j] == 0
+// 每个 M[i][j] (i > j) 用 IsZeroChip 判断，再把结果约束成 1（和 NimChip 一样）
+// 对角线和对角线上面的entry不做任何约束；1x1 的矩阵没有对角线下面的entry，直接通过
+// M 不是方阵的时候返回 Error::Synthesis
+#[derive(Debug, Clone)]
+pub struct UpperTriangularConfig {
+    pub is_zero: IsZeroConfig,
+}
+
+pub struct UpperTriangularChip<F: FieldExt> {
+    config: UpperTriangularConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> UpperTriangularChip<F> {
+    pub fn construct(config: UpperTriangularConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 用来放 is_zero 的结果要等于的常数 1
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        fixed: Column<Fixed>,
+    ) -> UpperTriangularConfig {
+        meta.enable_constant(fixed);
+
+        UpperTriangularConfig {
+            is_zero: IsZeroChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_upper_triangular(
+        &self,
+        mut layouter: impl Layouter<F>,
+        m: &[Vec<ACell<F>>],
+    ) -> Result<(), Error> {
+        let size = m.len();
+        if m.iter().any(|row| row.len() != size) {
+            return Err(Error::Synthesis);
+        }
+
+        let is_zero = IsZeroChip::construct(self.config.is_zero.clone());
+        for (i, row) in m.iter().enumerate() {
+            for (j, m_ij) in row.iter().enumerate().take(i) {
+                let zero = is_zero
+                    .is_zero(layouter.namespace(|| format!("M[{}][{}] == 0", i, j)), m_ij)?;
+
+                layouter.assign_region(
+                    || format!("M[{}][{}] below diagonal", i, j),
+                    |mut region| region.constrain_constant(zero.0 .0.cell(), F::one()),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 M 按行展开（rows * cols 个），没有输出
+    #[derive(Clone, Default)]
+    struct UpperTriangular {
+        cols: usize,
+    }
+
+    impl TestGadget<Fp> for UpperTriangular {
+        type Config = UpperTriangularConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> UpperTriangularConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            let fixed = meta.fixed_column();
+            UpperTriangularChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: UpperTriangularConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let m: Vec<_> = inputs.chunks(self.cols).map(|row| row.to_vec()).collect();
+            UpperTriangularChip::construct(config).assert_upper_triangular(layouter, &m)?;
+            Ok(vec![])
+        }
+    }
+
+    fn native_upper_triangular(m: &[u64], size: usize) -> bool {
+        (0..size).all(|i| (0..i).all(|j| m[i * size + j] == 0))
+    }
+
+    fn fp(values: &[u64]) -> Vec<Fp> {
+        values.iter().map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn upper_triangular_matches_native() {
+        // 从一个合法的上三角矩阵出发，每次只改一个位置（包括对角线和对角线上面）
+        let base = [1u64, 2, 3, 0, 4, 5, 0, 0, 6];
+        assert!(native_upper_triangular(&base, 3));
+        for pos in 0..9 {
+            let mut m = base;
+            m[pos] += 7;
+            assert_eq!(
+                run(6, UpperTriangular { cols: 3 }, &fp(&m), &[]).is_ok(),
+                native_upper_triangular(&m, 3),
+                "{:?}",
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn upper_triangular_allows_any_diagonal() {
+        // 对角线上是 0 也可以
+        let m = [0u64, 9, 9, 0, 0, 9, 0, 0, 0];
+        assert_eq!(run(6, UpperTriangular { cols: 3 }, &fp(&m), &[]), Ok(()));
+    }
+
+    #[test]
+    fn upper_triangular_one_by_one_passes() {
+        assert_eq!(run(6, UpperTriangular { cols: 1 }, &fp(&[5]), &[]), Ok(()));
+        assert_eq!(run(6, UpperTriangular { cols: 1 }, &fp(&[0]), &[]), Ok(()));
+    }
+
+    #[test]
+    fn upper_triangular_rejects_non_square() {
+        assert!(synthesis_fails(
+            6,
+            UpperTriangular { cols: 3 },
+            &fp(&[1, 2, 3, 0, 4, 5]),
+            &[]
+        ));
+    }
+}
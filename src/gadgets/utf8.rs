@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::decompose::{DecomposeChip, DecomposeConfig};
+use crate::ACell;
+
+// 证明一个byte是 UTF-8 的continuation byte，也就是 10xxxxxx
+// 把byte拆成 8 个bit（顺便range check了它确实是一个byte），再约束 bit7 = 1, bit6 = 0
+#[derive(Debug, Clone)]
+pub struct Utf8ContinuationConfig {
+    pub decompose: DecomposeConfig,
+}
+
+pub struct Utf8ContinuationChip<F: FieldExt> {
+    config: Utf8ContinuationConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Utf8ContinuationChip<F> {
+    pub fn construct(config: Utf8ContinuationConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // constant 用来放 0 和 1，和bit做copy constraint
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        constant: Column<Fixed>,
+    ) -> Utf8ContinuationConfig {
+        meta.enable_constant(constant);
+
+        Utf8ContinuationConfig {
+            decompose: DecomposeChip::configure(meta, advice),
+        }
+    }
+
+    pub fn assert_continuation(
+        &self,
+        mut layouter: impl Layouter<F>,
+        byte: &ACell<F>,
+    ) -> Result<(), Error> {
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        let bits = decompose.decompose(layouter.namespace(|| "decompose byte"), byte, 8)?;
+
+        layouter.assign_region(
+            || "top bits are 10",
+            |mut region| {
+                region.constrain_constant(bits[7].0 .0.cell(), F::one())?;
+                region.constrain_constant(bits[6].0 .0.cell(), F::zero())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct Continuation;
+
+    impl TestGadget<Fp> for Continuation {
+        type Config = Utf8ContinuationConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Utf8ContinuationConfig {
+            let advice = [meta.advice_column(), meta.advice_column()];
+            let fixed = meta.fixed_column();
+            Utf8ContinuationChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: Utf8ContinuationConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            Utf8ContinuationChip::construct(config).assert_continuation(layouter, &inputs[0])?;
+            Ok(vec![])
+        }
+    }
+
+    // 和标准库的判断对比：continuation byte 就是不在字符边界上的byte
+    #[test]
+    fn continuation_matches_native_for_all_bytes() {
+        for byte in 0..=255u8 {
+            let native = (byte as i8) < -0x40;
+            let result = run(5, Continuation, &[Fp::from(byte as u64)], &[]);
+            assert_eq!(result.is_ok(), native, "byte {:#x}", byte);
+        }
+    }
+
+    #[test]
+    fn continuation_bytes_of_multibyte_chars_pass() {
+        for byte in "é€😀".bytes().filter(|b| b & 0xc0 == 0x80) {
+            assert_eq!(run(5, Continuation, &[Fp::from(byte as u64)], &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn continuation_rejects_non_byte() {
+        // 低 8 位是 0x80，但是整体不是一个byte
+        assert!(run(5, Continuation, &[Fp::from(0x180)], &[]).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    constant::{ConstantChip, ConstantConfig},
+    decompose::{DecomposeChip, DecomposeConfig},
+    less_than::{LessThanChip, LessThanConfig},
+    masked_sum::{MaskedSumChip, MaskedSumConfig},
+    mul_const::{MulConstChip, MulConstConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 加权多数投票：yes_weight = Σ vote_i * weight_i，total_weight = Σ weight_i
+// 结果 = (total_weight < 2 * yes_weight)，也就是严格过半，正好一半（平局）的时候是 false
+// total_weight 也是用 MaskedSumChip 算的，只不过mask全部是常数 1
+// 和 CapacityChip 一样，每个weight都要range check，不然prover可以用一个"负数"weight（p - w）
+// 把 total_weight 压下去（比如 votes [1, 0]、weights [10, p - 5] 会得到 yes = 10 > total = 5）
+// weight 被限制在 bits - ceil(log2(n)) - 1 位，这样 2 * yes_weight <= 2 * total_weight < 2^bits
+#[derive(Debug, Clone)]
+pub struct WeightedMajorityConfig {
+    pub constant: ConstantConfig,
+    pub decompose: DecomposeConfig,
+    pub masked_sum: MaskedSumConfig,
+    pub mul_const: MulConstConfig,
+    pub less_than: LessThanConfig,
+    pub bits: usize,
+}
+
+pub struct WeightedMajorityChip<F: FieldExt> {
+    config: WeightedMajorityConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> WeightedMajorityChip<F> {
+    pub fn construct(config: WeightedMajorityConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> WeightedMajorityConfig {
+        WeightedMajorityConfig {
+            constant: ConstantChip::configure(meta, advice[0], fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            masked_sum: MaskedSumChip::configure(meta, advice),
+            mul_const: MulConstChip::configure(meta, [advice[0], advice[1]], fixed),
+            less_than: LessThanChip::configure(meta, advice, fixed),
+            bits,
+        }
+    }
+
+    pub fn majority(
+        &self,
+        mut layouter: impl Layouter<F>,
+        votes: &[Boolean<F>],
+        weights: &[ACell<F>],
+    ) -> Result<Boolean<F>, Error> {
+        // ceil(log2(n))
+        let len_bits = (usize::BITS - weights.len().saturating_sub(1).leading_zeros()) as usize;
+        let weight_bits = self
+            .config
+            .bits
+            .checked_sub(len_bits + 1)
+            .filter(|&bits| bits > 0)
+            .ok_or(Error::Synthesis)?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        for (i, weight) in weights.iter().enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("range check weight {}", i)),
+                weight,
+                weight_bits,
+            )?;
+        }
+
+        let masked_sum = MaskedSumChip::construct(self.config.masked_sum.clone());
+        let yes_weight =
+            masked_sum.masked_sum(layouter.namespace(|| "yes weight"), votes, weights)?;
+
+        // 常数 1 本身就是一个合法的 Boolean
+        let constant = ConstantChip::construct(self.config.constant.clone());
+        let one = Boolean(constant.load_constant(layouter.namespace(|| "one"), F::one())?);
+        let ones = vec![one; weights.len()];
+        let total_weight =
+            masked_sum.masked_sum(layouter.namespace(|| "total weight"), &ones, weights)?;
+
+        let mul_const = MulConstChip::construct(self.config.mul_const.clone());
+        let double_yes = mul_const.mul_const(
+            layouter.namespace(|| "2 * yes weight"),
+            &yes_weight,
+            F::from(2),
+        )?;
+
+        let less_than = LessThanChip::construct(self.config.less_than.clone());
+        less_than.less_than(
+            layouter.namespace(|| "total < 2 * yes"),
+            &total_weight,
+            &double_yes,
+            self.config.bits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入的前一半是 votes，后一半是 weights
+    #[derive(Clone, Default)]
+    struct WeightedMajority;
+
+    impl TestGadget<Fp> for WeightedMajority {
+        type Config = WeightedMajorityConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> WeightedMajorityConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            WeightedMajorityChip::configure(meta, advice, fixed, 10)
+        }
+
+        fn synthesize(
+            &self,
+            config: WeightedMajorityConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (votes, weights) = inputs.split_at(inputs.len() / 2);
+            let votes: Vec<_> = votes.iter().cloned().map(Boolean).collect();
+            Ok(vec![
+                WeightedMajorityChip::construct(config)
+                    .majority(layouter, &votes, weights)?
+                    .0,
+            ])
+        }
+    }
+
+    fn inputs(votes: &[u64], weights: &[u64]) -> Vec<Fp> {
+        votes.iter().chain(weights).map(|v| Fp::from(*v)).collect()
+    }
+
+    fn majority(votes: &[u64], weights: &[u64]) -> bool {
+        let yes: u64 = votes.iter().zip(weights).map(|(v, w)| v * w).sum();
+        let total: u64 = weights.iter().sum();
+        2 * yes > total
+    }
+
+    #[test]
+    fn weighted_majority_matches_native() {
+        let weights = [10u64, 20, 30, 40];
+        for votes in [
+            // 明显过半 / 明显不过半
+            [0u64, 0, 1, 1],
+            [1, 1, 0, 0],
+            // 平局：40 + 10 = 50 = 100 / 2
+            [1, 0, 0, 1],
+            [0, 1, 1, 0],
+            [0, 0, 0, 0],
+            [1, 1, 1, 1],
+        ] {
+            let expected = Fp::from(majority(&votes, &weights) as u64);
+            assert_eq!(
+                run(6, WeightedMajority, &inputs(&votes, &weights), &[expected]),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn weighted_majority_rejects_wrong_outcome() {
+        // 平局不算过半
+        assert!(run(
+            6,
+            WeightedMajority,
+            &inputs(&[1, 0, 0, 1], &[10, 20, 30, 40]),
+            &[Fp::one()]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn weighted_majority_rejects_non_boolean_vote() {
+        // vote = 2 会让 yes_weight 翻倍
+        assert!(run(
+            6,
+            WeightedMajority,
+            &inputs(&[2, 0, 0, 0], &[30, 20, 10, 10]),
+            &[Fp::one()]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn weighted_majority_rejects_negative_weight() {
+        // yes = 10，total = 10 + (p - 5) = 5，不做range check的话会被当成过半
+        let inputs = [Fp::one(), Fp::zero(), Fp::from(10), -Fp::from(5)];
+        assert!(run(6, WeightedMajority, &inputs, &[Fp::one()]).is_err());
+        assert!(run(6, WeightedMajority, &inputs, &[Fp::zero()]).is_err());
+    }
+
+    #[test]
+    fn weighted_majority_rejects_oversized_weight() {
+        // 4 个weight，bits = 10，每个weight要 < 2^(10 - 2 - 1) = 128
+        assert_eq!(
+            run(
+                6,
+                WeightedMajority,
+                &inputs(&[1, 0, 0, 0], &[127, 1, 1, 1]),
+                &[Fp::one()]
+            ),
+            Ok(())
+        );
+        assert!(run(
+            6,
+            WeightedMajority,
+            &inputs(&[1, 0, 0, 0], &[128, 1, 1, 1]),
+            &[Fp::one()]
+        )
+        .is_err());
+    }
+}
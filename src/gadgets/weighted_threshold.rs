@@ -0,0 +1,211 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    decompose::{DecomposeChip, DecomposeConfig},
+    less_than_or_equal::{LessThanOrEqualChip, LessThanOrEqualConfig},
+    masked_sum::{MaskedSumChip, MaskedSumConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// 按权重的门限（stake-weighted quorum）：sum(flag_i * weight_i) >= quorum
+// 用 MaskedSumChip 算出赞成的总权重，再用 LessThanOrEqualChip 断言 quorum <= 总权重
+// 没有人赞成的时候总权重是 0，只有 quorum = 0 才能通过
+// 每个weight都和 CapacityChip 一样range check，不然一个赞成的"负数"weight（p - w）可以把总权重凑成任意值
+// weight 被限制在 bits - ceil(log2(n)) 位，这样总权重一定 < 2^bits；quorum 也需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct WeightedThresholdConfig {
+    pub masked_sum: MaskedSumConfig,
+    pub less_than_or_equal: LessThanOrEqualConfig,
+    pub decompose: DecomposeConfig,
+    pub bits: usize,
+}
+
+pub struct WeightedThresholdChip<F: FieldExt> {
+    config: WeightedThresholdConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> WeightedThresholdChip<F> {
+    pub fn construct(config: WeightedThresholdConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> WeightedThresholdConfig {
+        WeightedThresholdConfig {
+            masked_sum: MaskedSumChip::configure(meta, advice),
+            less_than_or_equal: LessThanOrEqualChip::configure(meta, advice, fixed),
+            decompose: DecomposeChip::configure(meta, [advice[0], advice[1]]),
+            bits,
+        }
+    }
+
+    pub fn assert_quorum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        flags: &[Boolean<F>],
+        weights: &[ACell<F>],
+        quorum: &ACell<F>,
+    ) -> Result<(), Error> {
+        // ceil(log2(n))
+        let len_bits = (usize::BITS - weights.len().saturating_sub(1).leading_zeros()) as usize;
+        let weight_bits = self
+            .config
+            .bits
+            .checked_sub(len_bits)
+            .filter(|&bits| bits > 0)
+            .ok_or(Error::Synthesis)?;
+
+        let decompose = DecomposeChip::construct(self.config.decompose.clone());
+        for (i, weight) in weights.iter().enumerate() {
+            decompose.decompose(
+                layouter.namespace(|| format!("range check weight {}", i)),
+                weight,
+                weight_bits,
+            )?;
+        }
+
+        let masked_sum = MaskedSumChip::construct(self.config.masked_sum.clone());
+        let approved =
+            masked_sum.masked_sum(layouter.namespace(|| "approved weight"), flags, weights)?;
+
+        let less_than_or_equal =
+            LessThanOrEqualChip::construct(self.config.less_than_or_equal.clone());
+        less_than_or_equal.assert_less_than_or_equal(
+            layouter.namespace(|| "quorum <= approved weight"),
+            quorum,
+            &approved,
+            self.config.bits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    // 输入是 [quorum, flags..., weights...]，flags 和 weights 一样长；没有输出
+    #[derive(Clone, Default)]
+    struct WeightedThreshold;
+
+    impl TestGadget<Fp> for WeightedThreshold {
+        type Config = WeightedThresholdConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> WeightedThresholdConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            WeightedThresholdChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: WeightedThresholdConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (quorum, rest) = inputs.split_first().unwrap();
+            let (flags, weights) = rest.split_at(rest.len() / 2);
+            let flags: Vec<_> = flags.iter().cloned().map(Boolean).collect();
+            WeightedThresholdChip::construct(config)
+                .assert_quorum(layouter, &flags, weights, quorum)?;
+            Ok(vec![])
+        }
+    }
+
+    const WEIGHTS: [u64; 4] = [10, 20, 30, 40];
+
+    fn inputs(quorum: u64, flags: &[u64]) -> Vec<Fp> {
+        std::iter::once(&quorum)
+            .chain(flags)
+            .chain(&WEIGHTS)
+            .map(|v| Fp::from(*v))
+            .collect()
+    }
+
+    #[test]
+    fn weighted_threshold_matches_native() {
+        for flags in [
+            [0u64, 0, 0, 0],
+            [1, 0, 0, 0],
+            [0, 1, 1, 0],
+            [1, 0, 0, 1],
+            [1, 1, 1, 1],
+        ] {
+            let approved: u64 = flags.iter().zip(WEIGHTS).map(|(f, w)| f * w).sum();
+            for quorum in [0, 10, 49, 50, 51, 100] {
+                assert_eq!(
+                    run(7, WeightedThreshold, &inputs(quorum, &flags), &[]).is_ok(),
+                    approved >= quorum,
+                    "{:?} quorum = {}",
+                    flags,
+                    quorum
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_threshold_exact_quorum_passes() {
+        // 20 + 30 = 50
+        assert_eq!(
+            run(7, WeightedThreshold, &inputs(50, &[0, 1, 1, 0]), &[]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn weighted_threshold_no_approvals() {
+        assert_eq!(
+            run(7, WeightedThreshold, &inputs(0, &[0, 0, 0, 0]), &[]),
+            Ok(())
+        );
+        assert!(run(7, WeightedThreshold, &inputs(1, &[0, 0, 0, 0]), &[]).is_err());
+    }
+
+    #[test]
+    fn weighted_threshold_rejects_negative_weight() {
+        // 赞成的权重是 10 + 20 + (p - 25) = 5，不做range check的话 quorum = 5 就能通过
+        let inputs = [
+            Fp::from(5),
+            Fp::one(),
+            Fp::one(),
+            Fp::one(),
+            Fp::zero(),
+            Fp::from(10),
+            Fp::from(20),
+            -Fp::from(25),
+            Fp::from(40),
+        ];
+        assert!(run(7, WeightedThreshold, &inputs, &[]).is_err());
+    }
+
+    #[test]
+    fn weighted_threshold_rejects_oversized_weight() {
+        // 4 个weight，bits = 8，每个weight要 < 2^(8 - 2) = 64
+        let inputs: Vec<_> = [1u64, 1, 0, 0, 0, 63, 1, 1, 1]
+            .iter()
+            .map(|v| Fp::from(*v))
+            .collect();
+        assert_eq!(run(7, WeightedThreshold, &inputs, &[]), Ok(()));
+        let mut inputs = inputs;
+        inputs[5] = Fp::from(64);
+        assert!(run(7, WeightedThreshold, &inputs, &[]).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::minmax::{MinMaxChip, MinMaxConfig};
+use crate::ACell;
+
+// 滑动窗口最小值：对每一个长度为 w 的连续子数组，求它的 min
+// 做法很直接，每个窗口里面用 MinMaxChip 两两取min，一共 (n - w + 1) * (w - 1) 次比较
+#[derive(Debug, Clone)]
+pub struct WindowMinConfig {
+    pub min_max: MinMaxConfig,
+    pub bits: usize,
+}
+
+pub struct WindowMinChip<F: FieldExt> {
+    config: WindowMinConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> WindowMinChip<F> {
+    pub fn construct(config: WindowMinConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // 所有value都需要 < 2^bits
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> WindowMinConfig {
+        WindowMinConfig {
+            min_max: MinMaxChip::configure(meta, advice, fixed),
+            bits,
+        }
+    }
+
+    // 返回 n - w + 1 个cell，w = 1 的时候就是输入本身
+    pub fn window_min(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[ACell<F>],
+        w: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        if w == 0 || w > values.len() {
+            return Err(Error::Synthesis);
+        }
+
+        let min_max = MinMaxChip::construct(self.config.min_max.clone());
+
+        values
+            .windows(w)
+            .enumerate()
+            .map(|(i, window)| {
+                let mut min = window[0].clone();
+                for (j, value) in window.iter().enumerate().skip(1) {
+                    min = min_max.min(
+                        layouter.namespace(|| format!("window {} min {}", i, j)),
+                        &min,
+                        value,
+                        self.config.bits,
+                    )?;
+                }
+                Ok(min)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    #[derive(Clone, Default)]
+    struct WindowMin {
+        w: usize,
+    }
+
+    impl TestGadget<Fp> for WindowMin {
+        type Config = WindowMinConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> WindowMinConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            WindowMinChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: WindowMinConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            WindowMinChip::construct(config).window_min(layouter, inputs, self.w)
+        }
+    }
+
+    fn native(values: &[u64], w: usize) -> Vec<Fp> {
+        values
+            .windows(w)
+            .map(|window| Fp::from(*window.iter().min().unwrap()))
+            .collect()
+    }
+
+    fn fp(v: &[u64]) -> Vec<Fp> {
+        v.iter().map(|x| Fp::from(*x)).collect()
+    }
+
+    #[test]
+    fn window_min_matches_native() {
+        let values = [5u64, 3, 8, 1, 9, 2, 255, 0];
+        for w in 1..=values.len() {
+            assert_eq!(
+                run(10, WindowMin { w }, &fp(&values), &native(&values, w)),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn window_min_rejects_wrong_min() {
+        let values = [5u64, 3, 8];
+        let mut expected = native(&values, 2);
+        expected[1] = Fp::from(8);
+        assert!(run(8, WindowMin { w: 2 }, &fp(&values), &expected).is_err());
+    }
+
+    #[test]
+    fn window_min_rejects_bad_window_size() {
+        assert!(synthesis_fails(8, WindowMin { w: 0 }, &fp(&[1, 2]), &[]));
+        assert!(synthesis_fails(8, WindowMin { w: 3 }, &fp(&[1, 2]), &[]));
+    }
+}
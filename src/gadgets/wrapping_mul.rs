@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    modulo::{ModChip, ModConfig},
+    mul::{MulChip, MulConfig},
+};
+use crate::ACell;
+
+// 回绕乘法：out = (a * b) mod 2^bits，和 u32::wrapping_mul 之类的一样
+// 先用 MulChip 算出完整的乘积，再用 ModChip 对 2^bits 取余
+// ModChip 本身就保证了 out < 2^bits，商也会被range check到 QUOTIENT_BITS 位
+// a, b 都需要 < 2^bits，bits <= 63（这样商 < 2^bits，模数也 < 2^64）
+#[derive(Debug, Clone)]
+pub struct WrappingMulConfig {
+    pub mul: MulConfig,
+    pub modulo: ModConfig,
+}
+
+pub struct WrappingMulChip<F: FieldExt> {
+    config: WrappingMulConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> WrappingMulChip<F> {
+    pub fn construct(config: WrappingMulConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> WrappingMulConfig {
+        WrappingMulConfig {
+            mul: MulChip::configure(meta, advice),
+            modulo: ModChip::configure(meta, advice, fixed),
+        }
+    }
+
+    pub fn wrapping_mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        if bits == 0 || bits > 63 {
+            return Err(Error::Synthesis);
+        }
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        let product = mul.mul(layouter.namespace(|| "a * b"), a, b)?;
+
+        let modulo = ModChip::construct(self.config.modulo.clone());
+        modulo.modulo(layouter.namespace(|| "mod 2^bits"), &product, 1 << bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入是 [a, b]
+    #[derive(Clone, Default)]
+    struct WrappingMul {
+        bits: usize,
+    }
+
+    impl TestGadget<Fp> for WrappingMul {
+        type Config = WrappingMulConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> WrappingMulConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            WrappingMulChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: WrappingMulConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let out = WrappingMulChip::construct(config)
+                .wrapping_mul(layouter, &inputs[0], &inputs[1], self.bits)?;
+            Ok(vec![out])
+        }
+    }
+
+    fn inputs(a: u32, b: u32) -> Vec<Fp> {
+        vec![Fp::from(a as u64), Fp::from(b as u64)]
+    }
+
+    #[test]
+    fn wrapping_mul_matches_u32() {
+        for (a, b) in [
+            (0u32, 0xdead_beef_u32),
+            (0xdead_beef, 0),
+            (3, 7),
+            (0x1_0000, 0x1_0000),
+            (u32::MAX, u32::MAX),
+            (0x9e37_79b9, 0x85eb_ca6b),
+        ] {
+            assert_eq!(
+                run(
+                    8,
+                    WrappingMul { bits: 32 },
+                    &inputs(a, b),
+                    &[Fp::from(a.wrapping_mul(b) as u64)]
+                ),
+                Ok(()),
+                "{} * {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn wrapping_mul_rejects_unreduced_product() {
+        let (a, b) = (u32::MAX, 2u32);
+        let full = Fp::from(a as u64 * b as u64);
+        assert!(run(8, WrappingMul { bits: 32 }, &inputs(a, b), &[full]).is_err());
+        assert!(run(8, WrappingMul { bits: 32 }, &inputs(3, 7), &[Fp::from(22)]).is_err());
+    }
+
+    #[test]
+    fn wrapping_mul_rejects_bad_bits() {
+        assert!(synthesis_fails(
+            8,
+            WrappingMul { bits: 0 },
+            &inputs(1, 1),
+            &[Fp::zero()]
+        ));
+        assert!(synthesis_fails(
+            8,
+            WrappingMul { bits: 64 },
+            &inputs(1, 1),
+            &[Fp::one()]
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::bitwise::{BitwiseChip, BitwiseConfig};
+use crate::ACell;
+
+// XOR 链表：每个节点只存一个 link = prev ^ next（头的 prev 和尾的 next 都是 0）
+// 遍历的时候 next = prev ^ link：
+// * 头节点的 prev 是 0，所以 next = link_0，不用再走一遍 BitwiseChip
+// * 最后一个节点算出来的 next 必须是 0（链表在这里结束）
+// 所有地址都需要 < 2^bits
+#[derive(Debug, Clone)]
+pub struct XorListConfig {
+    pub bitwise: BitwiseConfig,
+    pub bits: usize,
+}
+
+pub struct XorListChip<F: FieldExt> {
+    config: XorListConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> XorListChip<F> {
+    pub fn construct(config: XorListConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    // fixed column 用来放结尾的 next 要等于的常数 0
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+        bits: usize,
+    ) -> XorListConfig {
+        meta.enable_constant(fixed);
+
+        XorListConfig {
+            bitwise: BitwiseChip::configure(meta, advice),
+            bits,
+        }
+    }
+
+    // nodes[i] 是第 i 个访问到的节点地址，links[i] 是这个节点里存的 link
+    pub fn assert_traversal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        nodes: &[ACell<F>],
+        links: &[ACell<F>],
+    ) -> Result<(), Error> {
+        if nodes.is_empty() || nodes.len() != links.len() {
+            return Err(Error::Synthesis);
+        }
+
+        let bitwise = BitwiseChip::construct(self.config.bitwise.clone());
+
+        for (i, link) in links.iter().enumerate() {
+            let next = if i == 0 {
+                link.clone()
+            } else {
+                bitwise.xor(
+                    layouter.namespace(|| format!("next of node {}", i)),
+                    &nodes[i - 1],
+                    link,
+                    self.config.bits,
+                )?
+            };
+
+            layouter.assign_region(
+                || format!("step {}", i),
+                |mut region| match nodes.get(i + 1) {
+                    Some(expected) => region.constrain_equal(next.0.cell(), expected.0.cell()),
+                    None => region.constrain_constant(next.0.cell(), F::zero()),
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, synthesis_fails, TestGadget};
+
+    // 输入的前一半是 nodes，后一半是 links
+    #[derive(Clone, Default)]
+    struct XorList;
+
+    impl TestGadget<Fp> for XorList {
+        type Config = XorListConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> XorListConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            XorListChip::configure(meta, advice, fixed, 8)
+        }
+
+        fn synthesize(
+            &self,
+            config: XorListConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let (nodes, links) = inputs.split_at(inputs.len() / 2);
+            XorListChip::construct(config).assert_traversal(layouter, nodes, links)?;
+            Ok(vec![])
+        }
+    }
+
+    // 按访问顺序给出的地址建一个 XOR 链表，返回每个节点存的 link
+    fn links(nodes: &[u64]) -> Vec<u64> {
+        (0..nodes.len())
+            .map(|i| {
+                let prev = if i == 0 { 0 } else { nodes[i - 1] };
+                prev ^ nodes.get(i + 1).copied().unwrap_or(0)
+            })
+            .collect()
+    }
+
+    // 从头节点开始沿着 link 走，直到 next = 0
+    fn traverse(head: u64, link_of: impl Fn(u64) -> u64) -> Vec<u64> {
+        let (mut prev, mut cur) = (0, head);
+        let mut visited = vec![];
+        while cur != 0 {
+            visited.push(cur);
+            let next = prev ^ link_of(cur);
+            prev = cur;
+            cur = next;
+        }
+        visited
+    }
+
+    fn inputs(nodes: &[u64], links: &[u64]) -> Vec<Fp> {
+        nodes.iter().chain(links).map(|v| Fp::from(*v)).collect()
+    }
+
+    #[test]
+    fn xor_list_matches_native() {
+        for nodes in [vec![17u64], vec![3, 200], vec![12, 7, 255, 1, 96]] {
+            let links = links(&nodes);
+            let link_of = |addr| links[nodes.iter().position(|n| *n == addr).unwrap()];
+            assert_eq!(traverse(nodes[0], link_of), nodes);
+            assert_eq!(run(8, XorList, &inputs(&nodes, &links), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn xor_list_rejects_wrong_traversal() {
+        let nodes = [12u64, 7, 255, 1];
+        let links = links(&nodes);
+
+        // 走错了一个节点
+        let wrong = [12u64, 7, 254, 1];
+        assert!(run(8, XorList, &inputs(&wrong, &links), &[]).is_err());
+
+        // 走到一半就停了，最后的 next 不是 0
+        assert!(run(8, XorList, &inputs(&nodes[..3], &links[..3]), &[]).is_err());
+    }
+
+    #[test]
+    fn xor_list_rejects_bad_lengths() {
+        assert!(synthesis_fails(8, XorList, &[], &[]));
+        assert!(synthesis_fails(8, XorList, &inputs(&[1, 2], &[2]), &[]));
+    }
+}
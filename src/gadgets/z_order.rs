@@ -0,0 +1,153 @@
+use std::marker::PhantomData;
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::*, plonk::*};
+
+use super::{
+    bounded::{BoundedChip, BoundedConfig},
+    morton_decode::{MortonDecodeChip, MortonDecodeConfig},
+    mul::{MulChip, MulConfig},
+    Boolean,
+};
+use crate::ACell;
+
+// Z-order 的范围查询：Morton 编码的点 (x, y) 是否在矩形 [x_lo, x_hi] x [y_lo, y_hi] 里面
+// MortonDecodeChip 先把点拆回 (x, y)，再分别用 BoundedChip 判断，最后两个 Boolean 相乘（AND）
+// 边界是闭的，落在矩形边上的点也算在里面
+// 坐标和矩形的边界都需要 < 2^bits（MortonDecodeChip 的限制是 bits <= 63）
+#[derive(Debug, Clone)]
+pub struct ZOrderContainsConfig {
+    pub morton_decode: MortonDecodeConfig,
+    pub bounded: BoundedConfig,
+    pub mul: MulConfig,
+}
+
+pub struct ZOrderContainsChip<F: FieldExt> {
+    config: ZOrderContainsConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ZOrderContainsChip<F> {
+    pub fn construct(config: ZOrderContainsConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        fixed: Column<Fixed>,
+    ) -> ZOrderContainsConfig {
+        ZOrderContainsConfig {
+            morton_decode: MortonDecodeChip::configure(meta, advice, fixed),
+            bounded: BoundedChip::configure(meta, advice, fixed),
+            mul: MulChip::configure(meta, advice),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn contains(
+        &self,
+        mut layouter: impl Layouter<F>,
+        morton: &ACell<F>,
+        x_lo: &ACell<F>,
+        x_hi: &ACell<F>,
+        y_lo: &ACell<F>,
+        y_hi: &ACell<F>,
+        bits: usize,
+    ) -> Result<Boolean<F>, Error> {
+        let morton_decode = MortonDecodeChip::construct(self.config.morton_decode.clone());
+        let (x, y) = morton_decode.deinterleave(layouter.namespace(|| "decode"), morton, bits)?;
+
+        let bounded = BoundedChip::construct(self.config.bounded.clone());
+        let x_in = bounded.is_bounded(layouter.namespace(|| "x in range"), &x, x_lo, x_hi, bits)?;
+        let y_in = bounded.is_bounded(layouter.namespace(|| "y in range"), &y, y_lo, y_hi, bits)?;
+
+        let mul = MulChip::construct(self.config.mul.clone());
+        mul.mul(layouter.namespace(|| "and"), &x_in.0, &y_in.0)
+            .map(Boolean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::gadgets::testing::{run, TestGadget};
+
+    const BITS: usize = 4;
+
+    // 输入是 [morton, x_lo, x_hi, y_lo, y_hi]，输出点是否在矩形里面
+    #[derive(Clone, Default)]
+    struct ZOrderContains;
+
+    impl TestGadget<Fp> for ZOrderContains {
+        type Config = ZOrderContainsConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> ZOrderContainsConfig {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let fixed = meta.fixed_column();
+            ZOrderContainsChip::configure(meta, advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: ZOrderContainsConfig,
+            layouter: impl Layouter<Fp>,
+            inputs: &[ACell<Fp>],
+        ) -> Result<Vec<ACell<Fp>>, Error> {
+            let inside = ZOrderContainsChip::construct(config).contains(
+                layouter, &inputs[0], &inputs[1], &inputs[2], &inputs[3], &inputs[4], BITS,
+            )?;
+            Ok(vec![inside.0])
+        }
+    }
+
+    fn native_morton(x: u64, y: u64) -> u64 {
+        (0..BITS).fold(0, |acc, i| {
+            acc | (((x >> i) & 1) << (2 * i)) | (((y >> i) & 1) << (2 * i + 1))
+        })
+    }
+
+    const RECT: [u64; 4] = [3, 10, 5, 12];
+
+    fn check(x: u64, y: u64, inside: bool) -> bool {
+        let inputs: Vec<_> = std::iter::once(native_morton(x, y))
+            .chain(RECT)
+            .map(Fp::from)
+            .collect();
+        run(9, ZOrderContains, &inputs, &[Fp::from(inside as u64)]).is_ok()
+    }
+
+    #[test]
+    fn z_order_contains_matches_native() {
+        let [x_lo, x_hi, y_lo, y_hi] = RECT;
+        for x in [0, 2, 3, 4, 10, 11, 15] {
+            for y in [0, 4, 5, 6, 12, 13, 15] {
+                let inside = (x_lo..=x_hi).contains(&x) && (y_lo..=y_hi).contains(&y);
+                assert!(check(x, y, inside), "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn z_order_contains_boundary_points() {
+        // 四个角和边上的点都算在里面
+        for (x, y) in [(3, 5), (3, 12), (10, 5), (10, 12), (7, 5), (3, 8)] {
+            assert!(check(x, y, true), "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn z_order_contains_rejects_wrong_answer() {
+        assert!(!check(7, 8, false));
+        assert!(!check(11, 8, true));
+        assert!(!check(7, 13, true));
+    }
+}
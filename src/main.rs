@@ -1,13 +1,23 @@
-use std::{marker::PhantomData, default, iter::Map};
+use std::marker::PhantomData;
 
 use halo2_proofs::{
     arithmetic::FieldExt,
     circuit::*,
-    plonk::*, poly::Rotation,
+    dev::MockProver,
     // 定义curve: https://docs.rs/pasta_curves/0.4.0/pasta_curves/index.html
-    pasta::Fp, dev::MockProver,
+    pasta::Fp,
+    plonk::*,
+    poly::Rotation,
 };
 
+// 可以复用的gadgets都放在 src/gadgets 下面
+mod debug;
+mod gadgets;
+mod prover;
+mod util;
+
+use util::fib_sequence;
+
 // 在region.assign_advice中，如果成功就返回AssignedCell，如果失败就返回Error
 #[derive(Debug, Clone)]
 struct ACell<F: FieldExt>(AssignedCell<F, F>);
@@ -18,6 +28,8 @@ struct FiboConfig {
     // 在这里定义advice column的数量
     pub advice: [Column<Advice>; 3],
     pub selector: Selector,
+    // 把每一个fibonacci数都expose到instance column里面
+    pub instance: Column<Instance>,
 }
 
 struct FiboChip<F: FieldExt> {
@@ -30,7 +42,10 @@ impl<F: FieldExt> FiboChip<F> {
     // 这是一个function（关联函数），返回实例自身
     // 传入FiboConfig struct，返回FiboChip
     pub fn construct(config: FiboConfig) -> Self {
-        Self { config, _marker: PhantomData }
+        Self {
+            config,
+            _marker: PhantomData,
+        }
     }
 
     // 输入ConstraintSystem，返回FiboConfig
@@ -38,7 +53,7 @@ impl<F: FieldExt> FiboChip<F> {
     // 不是方法的关联函数，常作为返回一个结构体新实例的构造函数
 
     // configure是实际写circuit的地方，我们在这里定义custom gate等
-    
+
     // * 注意：我们这里采用了第二种写法，把columns放到 MyCircuit 的 configure 函数里面定义
     // * 这样做的好处就是可以复用columns，传到不同的Chip里
     pub fn configure(
@@ -77,22 +92,23 @@ impl<F: FieldExt> FiboChip<F> {
         });
 
         // 写好circuit gate之后，就可以return了
-        FiboConfig { 
+        FiboConfig {
             advice: [col_a, col_b, col_c],
             selector,
             instance,
         }
-    // fn assign()
+        // fn assign()
     }
 
     // 这里定义的是在Fibochip impl context下的method
     // 输入两个table中的private input，就是a和b
+    #[allow(clippy::type_complexity)]
     pub fn assign_first_row(
         &self,
         mut layouter: impl Layouter<F>,
         a: Option<F>,
-        b: Option<F>
-    ) -> Result<(ACell<F>, ACell<F>, ACell<F>), Error>{
+        b: Option<F>,
+    ) -> Result<(ACell<F>, ACell<F>, ACell<F>), Error> {
         // layouter应该就是主要用来fed数据
         // * Layouter lays out regions in the table
         // * region可以理解为分配约束在table中使用的空间：https://docs.google.com/presentation/d/1HUJPHXaqbmVsnmI331mJn9nRuZkeHQZkIMpWBOJ1itk/edit#slide=id.p7
@@ -105,33 +121,39 @@ impl<F: FieldExt> FiboChip<F> {
 
                 // assign第一个a cell（就是a0）
                 // assign_advice最终返回assignedCell或者Error
-                let a_cell = region.assign_advice(
-                    // 命名
-                    || "a",
-                    // 第几个advice column
-                    self.config.advice[0],
-                    // 没有relative location
-                    0,
-                    // 错误处理
-                    || a.ok_or(Error::Synthesis),
-                ).map(ACell)?;
-
-                let b_cell = region.assign_advice(
-                    || "b",
-                    self.config.advice[1],
-                    0,
-                    || b.ok_or(Error::Synthesis),
-                ).map(ACell)?;
+                let a_cell = region
+                    .assign_advice(
+                        // 命名
+                        || "a",
+                        // 第几个advice column
+                        self.config.advice[0],
+                        // 没有relative location
+                        0,
+                        // 错误处理
+                        || a.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
+
+                let b_cell = region
+                    .assign_advice(
+                        || "b",
+                        self.config.advice[1],
+                        0,
+                        || b.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
 
                 // a + b = c
                 let c_val: Option<F> = a.and_then(|a| b.map(|b| a + b));
 
-                let c_cell = region.assign_advice(
-                    || "c",
-                    self.config.advice[2],
-                    0,
-                    || c_val.ok_or(Error::Synthesis),
-                ).map(ACell)?;
+                let c_cell = region
+                    .assign_advice(
+                        || "c",
+                        self.config.advice[2],
+                        0,
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
 
                 // 返回一个带值的tuple，就是最终assigned的region
                 Ok((a_cell, b_cell, c_cell))
@@ -139,7 +161,7 @@ impl<F: FieldExt> FiboChip<F> {
                 // * 所有copy constraint的作用在这里就格外明显
                 // * 我们只需要定义first row的cells，就可以复制粘贴给所有的rows
                 // * insert copy constraint
-            }
+            },
         )
     }
 
@@ -147,36 +169,40 @@ impl<F: FieldExt> FiboChip<F> {
         &self,
         mut layouter: impl Layouter<F>,
         prev_b: &ACell<F>,
-        prev_c: &ACell<F>
-    // 只需要return最后一个cell（c）
-    )-> Result<ACell<F>, Error> {
-            layouter.assign_region(
-                || "next row",
-                |mut region| {
-                    self.config.selector.enable(&mut region, 0);
-
-                    // 所以要copy之前的b和c，给后面的b和c（为什么少了a呢？）
-                    // 搞懂了，因为permutation的时候有一个置换，第一行的b变成了下一行的a
-                    prev_b.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
-                    prev_c.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
-                
-                    let c_val = prev_b.0.value().and_then(
-                        |b| {
-                            prev_c.0.value().map(|c| *b + *c)
-                        }
-                    );
-
-                    let c_cell = region.assign_advice(
+        prev_c: &ACell<F>, // 只需要return最后一个cell（c）
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "next row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // 所以要copy之前的b和c，给后面的b和c（为什么少了a呢？）
+                // 搞懂了，因为permutation的时候有一个置换，第一行的b变成了下一行的a
+                prev_b
+                    .0
+                    .copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                prev_c
+                    .0
+                    .copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = prev_b
+                    .0
+                    .value()
+                    .and_then(|b| prev_c.0.value().map(|c| *b + *c));
+
+                let c_cell = region
+                    .assign_advice(
                         || "c",
                         self.config.advice[2],
                         0,
-                        || c_val,
-                    ).map(ACell)?;
+                        || c_val.ok_or(Error::Synthesis),
+                    )
+                    .map(ACell)?;
 
-                    Ok(c_cell)
-                }
-            )
-    } 
+                Ok(c_cell)
+            },
+        )
+    }
 
     // * 这里貌似可以拿到一些assigned cell，然后做后续的constraint
     pub fn expose_public(
@@ -197,6 +223,19 @@ impl<F: FieldExt> FiboChip<F> {
 struct MyCircuit<F> {
     pub a: Option<F>,
     pub b: Option<F>,
+    // 一共证明多少个fibonacci数（至少会layout第一行的3个cell）
+    pub n: usize,
+}
+
+impl<F: FieldExt> MyCircuit<F> {
+    // 换witness的时候只需要重新构造circuit，pk可以继续用（见 prover::prove_with_pk）
+    pub fn with_witness(a: F, b: F, n: usize) -> Self {
+        Self {
+            a: Some(a),
+            b: Some(b),
+            n,
+        }
+    }
 }
 
 impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
@@ -204,44 +243,57 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
     type FloorPlanner = SimpleFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
-        Self::default()
+        // n 决定了电路的形状，所以不能丢掉
+        Self {
+            a: None,
+            b: None,
+            n: self.n,
+        }
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
         let col_a = meta.advice_column();
         let col_b = meta.advice_column();
         let col_c = meta.advice_column();
-        let selector = meta.selector();
-        FiboChip::configure(meta, [col_a, col_b, col_c], selector)
+        let instance = meta.instance_column();
+        FiboChip::configure(meta, [col_a, col_b, col_c], instance)
         // 这里就会返回FiboConfig -> Config -> FiboConfig
     }
 
-    fn synthesize(&self, config: Self::Config, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
         // 实例化？
         // 我们会复用这个chip，来design许多东西
         // construct里面主要是FibConfig，里面定义了我们需要的columns数量
         let chip = FiboChip::construct(config);
-        
+
         // assign
-        let ( prev_a, mut prev_b, prev_c) = chip.assign_first_row(
+        let (prev_a, mut prev_b, mut prev_c) = chip.assign_first_row(
             // namespace主要作用就是传入一个name
             // 在circuit::Layouter：https://docs.rs/halo2_proofs/0.2.0/halo2_proofs/circuit/trait.Layouter.html
             layouter.namespace(|| "first row"),
-            self.a, self.b,
-        );
+            self.a,
+            self.b,
+        )?;
+        let mut cells = vec![prev_a, prev_b.clone(), prev_c.clone()];
 
-        // Given f(0)=x, f(1)=y, we will prove f(9)=z
-        for _i in 3..10 {
+        // Given f(0)=x, f(1)=y, we will prove f(n-1)=z
+        for _i in 3..self.n {
             // 在这里可以把table的其余row都assign
-            let c_cell = chip.assign_row(
-                layouter.namespace(name_fn: || "next row"),
-                &prev_b,
-                &prev_c,
-            )
+            let c_cell = chip.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+            cells.push(c_cell.clone());
             prev_b = prev_c;
             prev_c = c_cell;
         }
 
+        // 每一个assigned cell都要和instance里面的参考值对上
+        for (row, cell) in cells.iter().take(self.n).enumerate() {
+            chip.expose_public(layouter.namespace(|| "expose"), cell, row)?;
+        }
+
         Ok(())
     }
 }
@@ -249,19 +301,69 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
 // 在这里实例化一个circuit
 // 可以传入一些真实值做测试
 fn main() {
-    let k = 4;
-    // Fp上的元素
-    let a = Fp::from(1);    // F[0]
-    let b = Fp::from(1);    // F[1]
-    let out = Fp::from(55); // F[9]
-
-    // 实例化一个circuit
-    let circuit = MyCircuit {
-        a: Some(a),
-        b: Some(b),
-    };
-
-    // 创造一个prover，用来做测试
-    let prover = MockProver::run(k, &circuit, vec![].unwrap());
+    // n 最大是12，再加上blinding rows，16行不够用
+    let k = 5;
+
+    // cargo run -- --dot | dot -Tpng > layout.png，只输出电路布局，不跑prover
+    if std::env::args().any(|arg| arg == "--dot") {
+        let circuit = MyCircuit::with_witness(Fp::from(1), Fp::from(1), 10);
+        print!("{}", debug::dot_layout(&circuit, k).unwrap());
+        return;
+    }
+
+    // 跑一个例子：f(0) = f(1) = 1，证明前 10 个fibonacci数
+    // 随机种子的测试在下面的 tests 里面
+    let (a, b, n) = (Fp::from(1), Fp::from(1), 10);
+    let circuit = MyCircuit::with_witness(a, b, n);
+
+    // public input 就是在电路外面算出来的数列
+    let public_input = fib_sequence(a, b, n);
+    let prover = MockProver::run(k, &circuit, vec![public_input.clone()]).unwrap();
     prover.assert_satisfied();
+
+    // 真正出一个proof；同一个pk给不同种子反复出proof的测试在 prover.rs 里面
+    let (params, pk) = prover::setup(k, &circuit).unwrap();
+    let proof = prover::prove_with_pk(&params, &pk, circuit, &public_input).unwrap();
+    prover::verify(&params, pk.get_vk(), &proof, &public_input).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{OsRng, RngCore};
+
+    use super::*;
+
+    // 种子是随机的，期望值都是用 fib_sequence 在电路外面算出来的
+    #[test]
+    fn fib_matches_native_sequence_for_random_seeds() {
+        for n in [3, 5, 10, 12] {
+            for _ in 0..4 {
+                let a = Fp::from(OsRng.next_u64());
+                let b = Fp::from(OsRng.next_u64());
+
+                let circuit = MyCircuit::with_witness(a, b, n);
+                let public_input = fib_sequence(a, b, n);
+                let prover = MockProver::run(5, &circuit, vec![public_input]).unwrap();
+                assert_eq!(
+                    prover.verify(),
+                    Ok(()),
+                    "seeds ({:?}, {:?}), n = {}",
+                    a,
+                    b,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fib_rejects_wrong_public_input() {
+        let (a, b) = (Fp::from(OsRng.next_u64()), Fp::from(OsRng.next_u64()));
+        let circuit = MyCircuit::with_witness(a, b, 10);
+
+        let mut public_input = fib_sequence(a, b, 10);
+        public_input[9] += Fp::one();
+        let prover = MockProver::run(5, &circuit, vec![public_input]).unwrap();
+        assert!(prover.verify().is_err());
+    }
 }
@@ -0,0 +1,86 @@
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::*,
+    poly::commitment::Params,
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+};
+use rand_core::OsRng;
+
+// params 和 pk 只跟电路的形状有关（columns、gates、行数），和witness无关
+// 所以只要 n 不变，生成一次就可以给不同的witness反复使用
+pub fn setup<C: Circuit<Fp>>(
+    k: u32,
+    circuit: &C,
+) -> Result<(Params<EqAffine>, ProvingKey<EqAffine>), Error> {
+    let params = Params::<EqAffine>::new(k);
+    let empty_circuit = circuit.without_witnesses();
+
+    let vk = keygen_vk(&params, &empty_circuit)?;
+    let pk = keygen_pk(&params, vk, &empty_circuit)?;
+
+    Ok((params, pk))
+}
+
+// 用已经生成好的pk来出proof，不需要重新keygen
+pub fn prove_with_pk<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    public_inputs: &[Fp],
+) -> Result<Vec<u8>, Error> {
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof(
+        params,
+        pk,
+        &[circuit],
+        &[&[public_inputs]],
+        OsRng,
+        &mut transcript,
+    )?;
+
+    Ok(transcript.finalize())
+}
+
+pub fn verify(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    public_inputs: &[Fp],
+) -> Result<(), Error> {
+    let strategy = SingleVerifier::new(params);
+    let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+
+    verify_proof(params, vk, strategy, &[&[public_inputs]], &mut transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fib_sequence, MyCircuit};
+
+    // keygen 只做一次，同一个pk给两组不同的种子出proof
+    #[test]
+    fn one_pk_proves_two_seeds() {
+        let (k, n) = (5, 10);
+        let (params, pk) =
+            setup(k, &MyCircuit::<Fp>::with_witness(Fp::zero(), Fp::zero(), n)).unwrap();
+
+        let seeds = [(Fp::from(1), Fp::from(1)), (Fp::from(3), Fp::from(4))];
+        let publics: Vec<Vec<Fp>> = seeds.iter().map(|(a, b)| fib_sequence(*a, *b, n)).collect();
+        let proofs: Vec<Vec<u8>> = seeds
+            .iter()
+            .zip(publics.iter())
+            .map(|((a, b), public)| {
+                prove_with_pk(&params, &pk, MyCircuit::with_witness(*a, *b, n), public).unwrap()
+            })
+            .collect();
+
+        for (proof, public) in proofs.iter().zip(publics.iter()) {
+            assert!(verify(&params, pk.get_vk(), proof, public).is_ok());
+        }
+
+        // 每个proof都只对自己的public input成立
+        assert!(verify(&params, pk.get_vk(), &proofs[0], &publics[1]).is_err());
+        assert!(verify(&params, pk.get_vk(), &proofs[1], &publics[0]).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use halo2_proofs::arithmetic::FieldExt;
+
+// 在电路外面直接算出fibonacci数列，用来和电路里面assign出来的cell做对比
+// n < 2 的时候只返回两个种子 [a, b]
+pub fn fib_sequence<F: FieldExt>(a: F, b: F, n: usize) -> Vec<F> {
+    let mut seq = vec![a, b];
+    while seq.len() < n {
+        let next = seq[seq.len() - 2] + seq[seq.len() - 1];
+        seq.push(next);
+    }
+    seq
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn fib_sequence_short_lengths_return_seeds() {
+        let (a, b) = (Fp::from(3), Fp::from(4));
+        assert_eq!(fib_sequence(a, b, 0), vec![a, b]);
+        assert_eq!(fib_sequence(a, b, 1), vec![a, b]);
+        assert_eq!(fib_sequence(a, b, 2), vec![a, b]);
+    }
+
+    #[test]
+    fn fib_sequence_matches_known_values() {
+        let expected: Vec<Fp> = [1u64, 1, 2, 3, 5, 8, 13, 21, 34, 55]
+            .iter()
+            .map(|v| Fp::from(*v))
+            .collect();
+        assert_eq!(fib_sequence(Fp::one(), Fp::one(), 10), expected);
+    }
+}